@@ -0,0 +1,136 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+use execution_core::ContractId;
+use rkyv::AlignedVec;
+use rusk_abi::{ContractData, Session, VM};
+use ttoken_client::{SessionBackend, TokenClient};
+use ttoken_types::{DeploymentSpec, EventRecord};
+
+use crate::Result;
+
+/// A chain kept in a local directory, holding the VM's state and the head of the chain - the last
+/// commit, the deployed token contract, and the block height.
+///
+/// Every command runs in a session started from the last commit, at the next block height. Commands
+/// changing the state commit the session, moving the head forward.
+pub struct LocalChain {
+    dir: PathBuf,
+    session: Session,
+    contract: Option<ContractId>,
+    block_height: u64,
+    // kept alive for as long as the session is
+    _vm: VM,
+}
+
+impl LocalChain {
+    /// Open the chain in the given directory, creating it if it doesn't exist.
+    pub fn open(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+        let vm = rusk_abi::new_vm(dir.join("vm"))?;
+
+        let (session, contract, block_height) = match fs::read_to_string(dir.join("head")) {
+            Ok(head) => {
+                let (base, contract, block_height) = parse_head(&head)?;
+                let block_height = block_height + 1;
+                let session = rusk_abi::new_session(&vm, base, block_height)?;
+                (session, Some(contract), block_height)
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                (rusk_abi::new_genesis_session(&vm), None, 0)
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            session,
+            contract,
+            block_height,
+            _vm: vm,
+        })
+    }
+
+    /// The deployed token contract.
+    pub fn contract(&self) -> Result<ContractId> {
+        self.contract
+            .ok_or_else(|| "No token is deployed, run `deploy` first".into())
+    }
+
+    /// Deploy the token contract at the id derived from the bytecode and the spec, replacing any
+    /// deployed before.
+    pub fn deploy(&mut self, bytecode: &[u8], spec: &DeploymentSpec) -> Result<ContractId> {
+        let contract = self.session.deploy(
+            bytecode,
+            ContractData::builder()
+                .owner(spec.owner.clone())
+                .constructor_arg(&spec.init_args)
+                .contract_id(spec.contract_id(bytecode)),
+            u64::MAX,
+        )?;
+
+        self.contract = Some(contract);
+        Ok(contract)
+    }
+
+    /// A client for the deployed token contract.
+    pub fn client(&mut self) -> Result<TokenClient<SessionBackend<'_>>> {
+        let contract = self.contract()?;
+        Ok(TokenClient::new(SessionBackend::new(
+            &mut self.session,
+            contract,
+        )))
+    }
+
+    /// The recent events emitted by the token contract, starting from the given sequence number.
+    pub fn events_since(&mut self, seq: u64) -> Result<Vec<EventRecord>> {
+        let contract = self.contract()?;
+
+        let (sender, receiver) = mpsc::channel();
+        self.session
+            .feeder_call::<_, ()>(contract, "events_since", &seq, u64::MAX, sender)?;
+
+        receiver
+            .into_iter()
+            .map(|data| {
+                // the archived record must be aligned, which the fed bytes may not be
+                let mut bytes = AlignedVec::with_capacity(data.len());
+                bytes.extend_from_slice(&data);
+                rkyv::from_bytes(&bytes).map_err(|_| "Invalid event record".into())
+            })
+            .collect()
+    }
+
+    /// Commit the changes made to the state, moving the head of the chain forward.
+    pub fn commit(mut self) -> Result<()> {
+        let contract = self.contract()?;
+        let base = self.session.commit()?;
+
+        let head = format!(
+            "{}\n{}\n{}\n",
+            hex::encode(base),
+            hex::encode(contract.to_bytes()),
+            self.block_height
+        );
+        fs::write(self.dir.join("head"), head)?;
+
+        Ok(())
+    }
+}
+
+fn parse_head(head: &str) -> Result<([u8; 32], ContractId, u64)> {
+    let mut lines = head.lines();
+    let mut next = || lines.next().ok_or("Invalid head file");
+
+    let mut base = [0u8; 32];
+    hex::decode_to_slice(next()?, &mut base)?;
+
+    let mut contract = [0u8; 32];
+    hex::decode_to_slice(next()?, &mut contract)?;
+
+    let block_height = next()?.parse()?;
+
+    Ok((base, ContractId::from_bytes(contract), block_height))
+}
@@ -0,0 +1,280 @@
+//! A command line tool to deploy and use the token contract.
+//!
+//! By default, the contract runs in a VM whose state is kept in a local directory, so it persists
+//! between commands. Given the URL of a rusk node with `--node`, the contract deployed on its chain
+//! is used instead, with the wallet's account paying for the transactions. Operations are signed
+//! using the secret key in a wallet file, holding the hex encoding of the key's 32 bytes. Accounts
+//! are given in their string form, e.g. `duskpk1...`.
+//!
+//! ```sh
+//! ttoken [--state <dir>] [--wallet <file>] <command> [args...]
+//! ttoken --node <url> --chain-id <id> [--contract <account>] [--wallet <file>] <command> [args...]
+//! ```
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+use dusk_bytes::Serializable;
+use execution_core::signatures::bls::{PublicKey, SecretKey};
+use ttoken_client::{Backend, NodeBackend, TokenClient};
+use ttoken_types::{decode_event, Account, DeploymentSpec, InitArgs, TokenEvent};
+
+mod local;
+
+use local::LocalChain;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const DEFAULT_STATE_DIR: &str = ".ttoken";
+const DEFAULT_WALLET: &str = "wallet.key";
+
+const OWNER: [u8; 64] = [0u8; 64];
+
+const USAGE: &str = "\
+Usage: ttoken [--state <dir>] [--wallet <file>] <command> [args...]
+       ttoken --node <url> --chain-id <id> [--contract <account>] [--wallet <file>]
+              <command> [args...]
+
+Commands:
+    deploy <bytecode> <supply> [nonce]
+                                  Deploy the token, crediting the supply to the wallet's account
+    contract-id <bytecode> <supply> [nonce]
+                                  The id the token would be deployed at, without deploying it
+    balance [account]             The balance of an account, or of the wallet's account
+    transfer <to> <value>         Transfer tokens from the wallet's account
+    approve <spender> <value>     Allow a spender to use tokens from the wallet's account
+    allowance <owner> <spender>   How many of the owner's tokens the spender may use
+    events [since]                The recent events, starting from a sequence number, on a local
+                                  chain only";
+
+fn main() {
+    if let Err(err) = run(env::args().skip(1).collect()) {
+        eprintln!("Error: {err}");
+        exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<()> {
+    let mut state_dir = PathBuf::from(DEFAULT_STATE_DIR);
+    let mut wallet = PathBuf::from(DEFAULT_WALLET);
+    let mut node = None;
+    let mut chain_id = None;
+    let mut contract = None;
+
+    let mut positional = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--state" => state_dir = args.next().ok_or(USAGE)?.into(),
+            "--wallet" => wallet = args.next().ok_or(USAGE)?.into(),
+            "--node" => node = Some(args.next().ok_or(USAGE)?),
+            "--chain-id" => chain_id = Some(args.next().ok_or(USAGE)?.parse()?),
+            "--contract" => contract = Some(args.next().ok_or(USAGE)?.parse()?),
+            _ => positional.push(arg),
+        }
+    }
+
+    let (command, params) = positional.split_first().ok_or(USAGE)?;
+    let params: Vec<&str> = params.iter().map(String::as_str).collect();
+
+    if let ("contract-id", [bytecode, supply, nonce @ ..]) = (command.as_str(), &params[..]) {
+        let spec = deployment_spec(&wallet, supply, nonce)?;
+        let bytecode = fs::read(bytecode)?;
+
+        println!("{}", Account::Contract(spec.contract_id(&bytecode)));
+        return Ok(());
+    }
+
+    if let Some(node) = node {
+        let chain_id = chain_id.ok_or("The chain id must be given with `--chain-id`")?;
+        return run_node(&node, chain_id, contract, &wallet, command, &params);
+    }
+
+    let mut chain = LocalChain::open(&state_dir)?;
+
+    match (command.as_str(), &params[..]) {
+        ("deploy", [bytecode, supply, nonce @ ..]) => {
+            let spec = deployment_spec(&wallet, supply, nonce)?;
+
+            let bytecode = fs::read(bytecode)?;
+            let contract = chain.deploy(&bytecode, &spec)?;
+            chain.commit()?;
+
+            println!("{}", Account::Contract(contract));
+        }
+        ("events", params) if params.len() <= 1 => {
+            let since = match params.first() {
+                Some(since) => since.parse()?,
+                None => 0,
+            };
+
+            for record in chain.events_since(since)? {
+                match decode_event(&record.topic, &record.data) {
+                    Some(event) => print_event(event),
+                    None => return Err("Invalid event data".into()),
+                }
+            }
+        }
+        _ => {
+            if run_client(&mut chain.client()?, &wallet, command, &params)? {
+                chain.commit()?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Run a command against the contract deployed on the chain of a node.
+fn run_node(
+    url: &str,
+    chain_id: u8,
+    contract: Option<Account>,
+    wallet: &Path,
+    command: &str,
+    params: &[&str],
+) -> Result<()> {
+    let sk = read_wallet(wallet)?;
+
+    if let ("deploy", [bytecode, supply, nonce @ ..]) = (command, params) {
+        let spec = deployment_spec(wallet, supply, nonce)?;
+        let bytecode = fs::read(bytecode)?;
+
+        let mut backend = NodeBackend::new(url, spec.contract_id(&bytecode), sk, chain_id);
+        let contract = backend.deploy(&bytecode, &spec)?;
+
+        println!("{}", Account::Contract(contract));
+        return Ok(());
+    }
+
+    if command == "events" {
+        return Err(
+            "Events can only be read from a local chain, since a node doesn't return them".into(),
+        );
+    }
+
+    let contract = match contract {
+        Some(Account::Contract(contract)) => contract,
+        Some(_) => return Err("The token must be a contract account".into()),
+        None => return Err("The token must be given with `--contract` when using a node".into()),
+    };
+
+    let mut client = TokenClient::new(NodeBackend::new(url, contract, sk, chain_id));
+    run_client(&mut client, wallet, command, params)?;
+
+    Ok(())
+}
+
+/// Run a command using the client, returning whether it changed the state of the contract.
+fn run_client<B>(
+    client: &mut TokenClient<B>,
+    wallet: &Path,
+    command: &str,
+    params: &[&str],
+) -> Result<bool>
+where
+    B: Backend,
+    B::Error: fmt::Debug + fmt::Display + 'static,
+{
+    match (command, params) {
+        ("balance", []) => {
+            let account = wallet_account(wallet)?;
+            println!("{}", client.balance_of(account)?);
+        }
+        ("balance", [account]) => {
+            let account: Account = account.parse()?;
+            println!("{}", client.balance_of(account)?);
+        }
+        ("transfer", [to, value]) => {
+            let sk = read_wallet(wallet)?;
+            let to: Account = to.parse()?;
+
+            let events = client.transfer(&sk, to, value.parse()?)?;
+            events.into_iter().for_each(print_event);
+
+            return Ok(true);
+        }
+        ("approve", [spender, value]) => {
+            let sk = read_wallet(wallet)?;
+            let spender: Account = spender.parse()?;
+
+            let events = client.approve(&sk, spender, value.parse()?)?;
+            events.into_iter().for_each(print_event);
+
+            return Ok(true);
+        }
+        ("allowance", [owner, spender]) => {
+            let owner: Account = owner.parse()?;
+            let spender: Account = spender.parse()?;
+            println!("{}", client.allowance(owner, spender)?);
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(false)
+}
+
+/// The spec of a token crediting the supply to the wallet's account, which is also its admin.
+fn deployment_spec(wallet: &Path, supply: &str, nonce: &[&str]) -> Result<DeploymentSpec> {
+    let nonce = match nonce {
+        [] => 0,
+        [nonce] => nonce.parse()?,
+        _ => return Err(USAGE.into()),
+    };
+
+    let sk = read_wallet(wallet)?;
+    let pk = PublicKey::from(&sk);
+
+    Ok(DeploymentSpec {
+        owner: OWNER.to_vec(),
+        nonce,
+        init_args: InitArgs {
+            balances: vec![(Account::External(pk), supply.parse()?)],
+            admin: Some(pk),
+            ..InitArgs::default()
+        },
+    })
+}
+
+fn read_wallet(path: &Path) -> Result<SecretKey> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Failed reading wallet {}: {err}", path.display()))?;
+
+    let mut bytes = [0u8; SecretKey::SIZE];
+    hex::decode_to_slice(contents.trim(), &mut bytes)?;
+
+    SecretKey::from_bytes(&bytes).map_err(|_| "Invalid secret key in wallet".into())
+}
+
+fn wallet_account(path: &Path) -> Result<Account> {
+    let sk = read_wallet(path)?;
+    Ok(Account::External(PublicKey::from(&sk)))
+}
+
+fn print_event(event: TokenEvent) {
+    match event {
+        TokenEvent::Transfer(transfer) => {
+            let event = transfer.event;
+            println!(
+                "{} transfer {} -> {}: {}",
+                transfer.seq, event.owner, event.to, event.value
+            );
+        }
+        TokenEvent::Approve(approve) => {
+            let event = approve.event;
+            println!(
+                "{} approve {} -> {}: {}",
+                approve.seq, event.owner, event.spender, event.value
+            );
+        }
+        TokenEvent::Burn(burn) => {
+            let event = burn.event;
+            println!("{} burn {}: {}", burn.seq, event.owner, event.value);
+        }
+        TokenEvent::Other { topic, .. } => println!("{topic}"),
+        event => println!("{event:?}"),
+    }
+}
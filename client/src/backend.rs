@@ -0,0 +1,33 @@
+/// Performs calls to a deployed token contract, with arguments and return values serialized.
+///
+/// Implementations exist for a local `Session` - with the `session` feature - and for a rusk node -
+/// with the `node` feature - and may be written for any other way of reaching the contract.
+pub trait Backend {
+    /// The error returned when a call fails.
+    type Error;
+
+    /// Query the contract, without changing its state, returning the serialized return value.
+    fn query(&mut self, fn_name: &str, arg: Vec<u8>) -> Result<Vec<u8>, Self::Error>;
+
+    /// Call the contract, possibly changing its state, returning the serialized return value and
+    /// the events emitted by the contract.
+    fn call(&mut self, fn_name: &str, arg: Vec<u8>) -> Result<Receipt, Self::Error>;
+}
+
+/// The result of a call changing the state of the contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Receipt {
+    /// The serialized return value.
+    pub data: Vec<u8>,
+    /// The events emitted by the contract, in order.
+    pub events: Vec<RawEvent>,
+}
+
+/// An event emitted by the contract, as yet undecoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawEvent {
+    /// The topic of the event.
+    pub topic: String,
+    /// The serialized data of the event.
+    pub data: Vec<u8>,
+}
@@ -0,0 +1,236 @@
+//! A client for the `ttoken-contract`.
+//!
+//! [`TokenClient`] takes care of what every consumer of the contract would otherwise have to
+//! implement: fetching and tracking nonces, signing payloads, serializing arguments, and decoding
+//! return values and events. It reaches the contract through a [`Backend`].
+
+#![deny(missing_docs)]
+
+use std::fmt;
+
+use bytecheck::CheckBytes;
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::validation::validators::DefaultValidator;
+use rkyv::{AlignedVec, Archive, Deserialize, Infallible};
+
+use execution_core::signatures::bls::{PublicKey, SecretKey};
+use ttoken_types::*;
+
+mod backend;
+#[cfg(feature = "node")]
+mod node;
+#[cfg(feature = "session")]
+mod session;
+
+pub use backend::*;
+#[cfg(feature = "node")]
+pub use node::*;
+#[cfg(feature = "session")]
+pub use session::*;
+pub use ttoken_types::TokenEvent;
+
+/// The size of the scratch space used when serializing arguments.
+const SCRATCH_SIZE: usize = 1024;
+
+/// Error returned by the client.
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The backend failed to perform the call.
+    Backend(E),
+    /// The argument of a call could not be serialized.
+    Encoding,
+    /// The return value of a call could not be deserialized.
+    Decoding,
+}
+
+impl<E: fmt::Display> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Backend(err) => write!(f, "call failed: {err}"),
+            Error::Encoding => f.write_str("failed serializing the argument"),
+            Error::Decoding => f.write_str("failed deserializing the return value"),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for Error<E> {}
+
+/// A client for a deployed token contract.
+pub struct TokenClient<B> {
+    backend: B,
+    nonces: NonceManager,
+}
+
+impl<B: Backend> TokenClient<B> {
+    /// Create a client reaching the contract through the given backend.
+    pub fn new(backend: B) -> Self {
+        Self {
+            backend,
+            nonces: NonceManager::new(),
+        }
+    }
+
+    /// The backend used by the client.
+    pub fn backend(&mut self) -> &mut B {
+        &mut self.backend
+    }
+
+    /// The balance of an account.
+    pub fn balance_of(&mut self, account: impl Into<Account>) -> Result<u64, Error<B::Error>> {
+        let info: AccountInfo = self.query("account", &account.into())?;
+        Ok(info.balance)
+    }
+
+    /// How much of an allowance a spender has of the `owner` account.
+    pub fn allowance(
+        &mut self,
+        owner: impl Into<Account>,
+        spender: impl Into<Account>,
+    ) -> Result<u64, Error<B::Error>> {
+        let allowance = Allowance {
+            owner: owner.into(),
+            spender: spender.into(),
+        };
+        self.query("allowance", &allowance)
+    }
+
+    /// The lowest nonce the account hasn't used, as reported by the contract.
+    pub fn nonce(&mut self, account: impl Into<Account>) -> Result<u64, Error<B::Error>> {
+        self.query("nonce", &account.into())
+    }
+
+    /// Transfer tokens from the account of the given key, returning the events emitted.
+    pub fn transfer(
+        &mut self,
+        sk: &SecretKey,
+        to: impl Into<Account>,
+        value: u64,
+    ) -> Result<Vec<TokenEvent>, Error<B::Error>> {
+        let from = Account::External(PublicKey::from(sk));
+        let nonce = self.next_nonce(from)?;
+
+        let transfer = Transfer::new(sk, to, value, nonce);
+        self.submit(from, "transfer", &transfer)
+    }
+
+    /// Allow a spender to use tokens from the account of the given key, returning the events
+    /// emitted.
+    pub fn approve(
+        &mut self,
+        sk: &SecretKey,
+        spender: impl Into<Account>,
+        value: u64,
+    ) -> Result<Vec<TokenEvent>, Error<B::Error>> {
+        let owner = Account::External(PublicKey::from(sk));
+        let nonce = self.next_nonce(owner)?;
+
+        let approve = Approve::new(sk, spender, value, nonce);
+        self.submit(owner, "approve", &approve)
+    }
+
+    /// Transfer tokens from the `owner`, spending the allowance of the account of the given key,
+    /// returning the events emitted.
+    pub fn transfer_from(
+        &mut self,
+        sk: &SecretKey,
+        owner: impl Into<Account>,
+        to: impl Into<Account>,
+        value: u64,
+    ) -> Result<Vec<TokenEvent>, Error<B::Error>> {
+        let spender = Account::External(PublicKey::from(sk));
+        let nonce = self.next_nonce(spender)?;
+
+        let transfer = TransferFrom::new(sk, owner, to, value, nonce);
+        self.submit(spender, "transfer_from", &transfer)
+    }
+
+    fn next_nonce(&mut self, account: Account) -> Result<u64, Error<B::Error>> {
+        let mut fetch_err = None;
+
+        let nonce = self.nonces.next(account, |account| {
+            match query(&mut self.backend, "nonce", &account) {
+                Ok(nonce) => nonce,
+                Err(err) => {
+                    fetch_err = Some(err);
+                    0
+                }
+            }
+        });
+
+        match fetch_err {
+            Some(err) => {
+                self.nonces.reset(&account);
+                Err(err)
+            }
+            None => Ok(nonce),
+        }
+    }
+
+    /// Calls the contract with a payload signed by the `signer`, forgetting its cached nonce if
+    /// the call fails.
+    fn submit<A>(
+        &mut self,
+        signer: Account,
+        fn_name: &str,
+        arg: &A,
+    ) -> Result<Vec<TokenEvent>, Error<B::Error>>
+    where
+        A: rkyv::Serialize<AllocSerializer<SCRATCH_SIZE>>,
+    {
+        let arg = encode(arg)?;
+
+        match self.backend.call(fn_name, arg) {
+            Ok(receipt) => receipt
+                .events
+                .into_iter()
+                .map(|event| decode_event(&event.topic, &event.data).ok_or(Error::Decoding))
+                .collect(),
+            Err(err) => {
+                self.nonces.reset(&signer);
+                Err(Error::Backend(err))
+            }
+        }
+    }
+
+    fn query<A, R>(&mut self, fn_name: &str, arg: &A) -> Result<R, Error<B::Error>>
+    where
+        A: rkyv::Serialize<AllocSerializer<SCRATCH_SIZE>>,
+        R: Archive,
+        R::Archived: for<'a> CheckBytes<DefaultValidator<'a>> + Deserialize<R, Infallible>,
+    {
+        query(&mut self.backend, fn_name, arg)
+    }
+}
+
+fn query<B, A, R>(backend: &mut B, fn_name: &str, arg: &A) -> Result<R, Error<B::Error>>
+where
+    B: Backend,
+    A: rkyv::Serialize<AllocSerializer<SCRATCH_SIZE>>,
+    R: Archive,
+    R::Archived: for<'a> CheckBytes<DefaultValidator<'a>> + Deserialize<R, Infallible>,
+{
+    let arg = encode(arg)?;
+    let data = backend.query(fn_name, arg).map_err(Error::Backend)?;
+    decode(&data).ok_or(Error::Decoding)
+}
+
+fn encode<A, E>(arg: &A) -> Result<Vec<u8>, Error<E>>
+where
+    A: rkyv::Serialize<AllocSerializer<SCRATCH_SIZE>>,
+{
+    rkyv::to_bytes::<_, SCRATCH_SIZE>(arg)
+        .map(|bytes| bytes.into_vec())
+        .map_err(|_| Error::Encoding)
+}
+
+fn decode<R>(data: &[u8]) -> Option<R>
+where
+    R: Archive,
+    R::Archived: for<'a> CheckBytes<DefaultValidator<'a>> + Deserialize<R, Infallible>,
+{
+    // the archived value must be aligned, which the returned bytes may not be
+    let mut bytes = AlignedVec::with_capacity(data.len());
+    bytes.extend_from_slice(data);
+
+    rkyv::from_bytes(&bytes).ok()
+}
@@ -0,0 +1,255 @@
+use std::fmt;
+use std::io::Read;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use dusk_bytes::Serializable;
+use execution_core::signatures::bls::{PublicKey, SecretKey};
+use execution_core::transfer::data::{
+    ContractBytecode, ContractCall, ContractDeploy, TransactionData,
+};
+use execution_core::transfer::moonlight::AccountData;
+use execution_core::transfer::{Transaction, TRANSFER_CONTRACT};
+use execution_core::ContractId;
+use rkyv::AlignedVec;
+use ttoken_types::DeploymentSpec;
+
+use crate::{Backend, Receipt, SCRATCH_SIZE};
+
+/// The gas limit of every call, unless set otherwise.
+const DEFAULT_GAS_LIMIT: u64 = 100_000_000;
+/// The price paid for each unit of gas, unless set otherwise.
+const DEFAULT_GAS_PRICE: u64 = 1;
+/// How long to wait for a transaction to be executed, unless set otherwise.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+/// How often to ask the node whether a transaction was executed.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A backend calling a token contract deployed on a chain, through the HTTP API of a rusk node.
+///
+/// Queries are answered by the node directly. Calls are submitted as Moonlight transactions, paying
+/// for gas from the account of the given key, and are waited on until the node has executed them.
+///
+/// A node only reports whether a transaction succeeded, so the receipts of calls carry neither the
+/// return value nor the events emitted.
+pub struct NodeBackend {
+    url: String,
+    contract: ContractId,
+    sk: SecretKey,
+    chain_id: u8,
+    gas_limit: u64,
+    gas_price: u64,
+    timeout: Duration,
+}
+
+impl NodeBackend {
+    /// Call the given contract through the node at the given URL, such as
+    /// `http://127.0.0.1:8080`, paying for gas from the account of the given key on the chain with
+    /// the given id.
+    pub fn new(url: impl Into<String>, contract: ContractId, sk: SecretKey, chain_id: u8) -> Self {
+        Self {
+            url: url.into().trim_end_matches('/').to_string(),
+            contract,
+            sk,
+            chain_id,
+            gas_limit: DEFAULT_GAS_LIMIT,
+            gas_price: DEFAULT_GAS_PRICE,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Set the gas limit of every call.
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Set the price paid for each unit of gas.
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = gas_price;
+        self
+    }
+
+    /// Set how long to wait for a transaction to be executed before giving up on it.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Deploy the token contract with the given bytecode and spec, at the id derived from them, and
+    /// call it from then on.
+    pub fn deploy(
+        &mut self,
+        bytecode: &[u8],
+        spec: &DeploymentSpec,
+    ) -> Result<ContractId, NodeError> {
+        let init_args = rkyv::to_bytes::<_, SCRATCH_SIZE>(&spec.init_args)
+            .expect("Serializing the arguments should succeed")
+            .into_vec();
+
+        self.submit(ContractDeploy {
+            bytecode: ContractBytecode {
+                hash: blake3::hash(bytecode).into(),
+                bytes: bytecode.to_vec(),
+            },
+            owner: spec.owner.clone(),
+            init_args: Some(init_args),
+            nonce: spec.nonce,
+        })?;
+
+        self.contract = spec.contract_id(bytecode);
+        Ok(self.contract)
+    }
+
+    /// Query a contract through the node.
+    fn query_contract(
+        &self,
+        contract: ContractId,
+        fn_name: &str,
+        arg: Vec<u8>,
+    ) -> Result<Vec<u8>, NodeError> {
+        let url = format!(
+            "{}/on/contracts:{}/{fn_name}",
+            self.url,
+            hex::encode(contract.to_bytes())
+        );
+        self.post(&url, &arg)
+    }
+
+    /// The nonce the next transaction paid for by the key must use.
+    fn next_tx_nonce(&self) -> Result<u64, NodeError> {
+        let pk = PublicKey::from(&self.sk);
+        let arg = rkyv::to_bytes::<_, 256>(&pk)
+            .expect("Serializing a key should succeed")
+            .into_vec();
+
+        let data = self.query_contract(TRANSFER_CONTRACT, "account", arg)?;
+
+        // the archived value must be aligned, which the returned bytes may not be
+        let mut bytes = AlignedVec::with_capacity(data.len());
+        bytes.extend_from_slice(&data);
+        let account: AccountData =
+            rkyv::from_bytes(&bytes).map_err(|_| NodeError::InvalidResponse)?;
+
+        Ok(account.nonce + 1)
+    }
+
+    /// Propagate a transaction carrying the given data, and wait for it to be executed.
+    fn submit(&self, data: impl Into<TransactionData>) -> Result<(), NodeError> {
+        let tx = Transaction::moonlight(
+            &self.sk,
+            None,
+            0,
+            0,
+            self.gas_limit,
+            self.gas_price,
+            self.next_tx_nonce()?,
+            self.chain_id,
+            Some(data),
+        )
+        .map_err(|err| NodeError::Transaction(format!("{err:?}")))?;
+
+        let hash = hex::encode(tx.hash().to_bytes());
+
+        let url = format!("{}/on/transactions/propagate", self.url);
+        self.post(&url, &tx.to_var_bytes())?;
+
+        self.wait_executed(&hash)
+    }
+
+    /// Wait until the transaction with the given hash is executed, failing if it reverted.
+    fn wait_executed(&self, hash: &str) -> Result<(), NodeError> {
+        let url = format!("{}/on/graphql/query", self.url);
+        let query = format!("query {{ tx(hash: \"{hash}\") {{ err }} }}");
+
+        let start = Instant::now();
+        loop {
+            let response = self.post(&url, query.as_bytes())?;
+            let response: serde_json::Value =
+                serde_json::from_slice(&response).map_err(|_| NodeError::InvalidResponse)?;
+
+            match &response["tx"] {
+                serde_json::Value::Null => {}
+                tx => {
+                    return match &tx["err"] {
+                        serde_json::Value::Null => Ok(()),
+                        err => Err(NodeError::Reverted(err.to_string())),
+                    }
+                }
+            }
+
+            if start.elapsed() >= self.timeout {
+                return Err(NodeError::Timeout(hash.to_string()));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    fn post(&self, url: &str, body: &[u8]) -> Result<Vec<u8>, NodeError> {
+        let response = ureq::post(url)
+            .set("Content-Type", "application/octet-stream")
+            .send_bytes(body)
+            .map_err(|err| NodeError::Http(Box::new(err)))?;
+
+        let mut bytes = Vec::new();
+        response
+            .into_reader()
+            .read_to_end(&mut bytes)
+            .map_err(NodeError::Io)?;
+
+        Ok(bytes)
+    }
+}
+
+impl Backend for NodeBackend {
+    type Error = NodeError;
+
+    fn query(&mut self, fn_name: &str, arg: Vec<u8>) -> Result<Vec<u8>, Self::Error> {
+        self.query_contract(self.contract, fn_name, arg)
+    }
+
+    fn call(&mut self, fn_name: &str, arg: Vec<u8>) -> Result<Receipt, Self::Error> {
+        self.submit(ContractCall {
+            contract: self.contract,
+            fn_name: fn_name.to_string(),
+            fn_args: arg,
+        })?;
+
+        Ok(Receipt {
+            data: Vec::new(),
+            events: Vec::new(),
+        })
+    }
+}
+
+/// Error returned by the [`NodeBackend`].
+#[derive(Debug)]
+pub enum NodeError {
+    /// The request to the node failed.
+    Http(Box<ureq::Error>),
+    /// The response of the node could not be read.
+    Io(std::io::Error),
+    /// The response of the node was not what was expected.
+    InvalidResponse,
+    /// The transaction could not be created.
+    Transaction(String),
+    /// The transaction was executed, but the call failed.
+    Reverted(String),
+    /// The transaction with the given hash was not executed in time.
+    Timeout(String),
+}
+
+impl fmt::Display for NodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeError::Http(err) => write!(f, "request to the node failed: {err}"),
+            NodeError::Io(err) => write!(f, "failed reading the response of the node: {err}"),
+            NodeError::InvalidResponse => f.write_str("invalid response from the node"),
+            NodeError::Transaction(err) => write!(f, "failed creating the transaction: {err}"),
+            NodeError::Reverted(err) => write!(f, "the transaction failed: {err}"),
+            NodeError::Timeout(hash) => write!(f, "transaction {hash} was not executed in time"),
+        }
+    }
+}
+
+impl std::error::Error for NodeError {}
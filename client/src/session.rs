@@ -0,0 +1,60 @@
+use execution_core::ContractId;
+use rusk_abi::{PiecrustError, Session};
+
+use crate::{Backend, RawEvent, Receipt};
+
+/// A backend calling a token contract deployed in a local [`Session`].
+pub struct SessionBackend<'a> {
+    session: &'a mut Session,
+    contract: ContractId,
+    gas_limit: u64,
+}
+
+impl<'a> SessionBackend<'a> {
+    /// Call the given contract in the session, with no gas limit.
+    pub fn new(session: &'a mut Session, contract: ContractId) -> Self {
+        Self {
+            session,
+            contract,
+            gas_limit: u64::MAX,
+        }
+    }
+
+    /// Set the gas limit of every call.
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = gas_limit;
+        self
+    }
+}
+
+impl Backend for SessionBackend<'_> {
+    type Error = PiecrustError;
+
+    fn query(&mut self, fn_name: &str, arg: Vec<u8>) -> Result<Vec<u8>, Self::Error> {
+        let receipt = self
+            .session
+            .call_raw(self.contract, fn_name, arg, self.gas_limit)?;
+        Ok(receipt.data)
+    }
+
+    fn call(&mut self, fn_name: &str, arg: Vec<u8>) -> Result<Receipt, Self::Error> {
+        let receipt = self
+            .session
+            .call_raw(self.contract, fn_name, arg, self.gas_limit)?;
+
+        let events = receipt
+            .events
+            .into_iter()
+            .filter(|event| event.source == self.contract)
+            .map(|event| RawEvent {
+                topic: event.topic,
+                data: event.data,
+            })
+            .collect();
+
+        Ok(Receipt {
+            data: receipt.data,
+            events,
+        })
+    }
+}
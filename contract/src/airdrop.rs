@@ -0,0 +1,129 @@
+//! Airdrops claimed using proofs of inclusion in a Merkle tree.
+
+use alloc::collections::BTreeSet;
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn create_airdrop(&mut self, create: Signed<CreateAirdrop>) -> u64 {
+        self.authorize_admin(&create);
+
+        let admin = create.signer_account();
+        let create = *create.op();
+
+        if create.expiry_height <= rusk_abi::block_height() {
+            panic!("The airdrop can't expire in the past");
+        }
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+        self.debit_limited(admin, create.total);
+
+        let id = self.next_airdrop_id;
+        self.next_airdrop_id += 1;
+
+        self.airdrops.insert(
+            id,
+            Airdrop {
+                merkle_root: create.merkle_root,
+                total: create.total,
+                claimed: 0,
+                expiry_height: create.expiry_height,
+            },
+        );
+        self.airdrop_claims.insert(id, BTreeSet::new());
+
+        self.emit(
+            "airdrop_created",
+            AirdropCreatedEvent {
+                id,
+                merkle_root: create.merkle_root,
+                total: create.total,
+                expiry_height: create.expiry_height,
+            },
+        );
+
+        id
+    }
+
+    pub(crate) fn claim_airdrop(&mut self, claim: ClaimAirdrop) {
+        let id = claim.id;
+        let account = claim.account;
+        let amount = claim.amount;
+
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(account);
+
+        let airdrop = self
+            .airdrops
+            .get_mut(&id)
+            .expect("The airdrop doesn't exist");
+
+        if rusk_abi::block_height() >= airdrop.expiry_height {
+            panic!("The airdrop has expired");
+        }
+
+        if !verify_airdrop_proof(&airdrop.merkle_root, &account, amount, &claim.proof) {
+            panic!("Invalid airdrop proof");
+        }
+
+        if airdrop.total - airdrop.claimed < amount {
+            panic!("The airdrop doesn't have enough tokens left");
+        }
+
+        let claims = self
+            .airdrop_claims
+            .get_mut(&id)
+            .expect("The airdrop should have a set of claims");
+        if !claims.insert(account) {
+            panic!("The account already claimed the airdrop");
+        }
+
+        airdrop.claimed += amount;
+
+        self.credit(account, amount);
+
+        self.emit(
+            "airdrop_claimed",
+            AirdropClaimedEvent {
+                id,
+                account,
+                amount,
+            },
+        );
+    }
+
+    pub(crate) fn sweep_airdrop(&mut self, sweep: Signed<SweepAirdrop>) {
+        self.authorize_admin(&sweep);
+
+        let admin = sweep.signer_account();
+        let id = sweep.op().id;
+
+        let airdrop = self.airdrops.get(&id).expect("The airdrop doesn't exist");
+
+        if rusk_abi::block_height() < airdrop.expiry_height {
+            panic!("The airdrop has not expired yet");
+        }
+
+        let value = airdrop.total - airdrop.claimed;
+
+        self.airdrops.remove(&id);
+        self.airdrop_claims.remove(&id);
+
+        self.credit(admin, value);
+
+        self.emit("airdrop_swept", AirdropSweptEvent { id, value });
+    }
+
+    pub(crate) fn airdrop(&self, id: u64) -> Option<Airdrop> {
+        self.airdrops.get(&id).copied()
+    }
+
+    pub(crate) fn airdrop_claimed(&self, claimed: AirdropClaimed) -> bool {
+        self.airdrop_claims
+            .get(&claimed.id)
+            .map(|claims| claims.contains(&claimed.account))
+            .unwrap_or(false)
+    }
+}
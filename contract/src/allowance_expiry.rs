@@ -0,0 +1,63 @@
+//! Block heights from which spenders can no longer use their allowances.
+
+use alloc::collections::BTreeMap;
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn set_allowance_expiry(&mut self, set: Signed<SetAllowanceExpiry>) {
+        let owner = self.authorize(&set);
+        let set = *set.op();
+
+        let spender = set.spender;
+
+        match set.expiry_height {
+            Some(expiry_height) => {
+                if expiry_height <= rusk_abi::block_height() {
+                    panic!("The expiry must be past the current block height");
+                }
+
+                self.allowance_expiries
+                    .entry(owner)
+                    .or_insert(BTreeMap::new())
+                    .insert(spender, expiry_height);
+            }
+            None => self.remove_allowance_expiry(owner, spender),
+        }
+
+        self.emit(
+            "allowance_expiry",
+            AllowanceExpiryEvent {
+                owner,
+                spender,
+                expiry_height: set.expiry_height,
+            },
+        );
+    }
+
+    pub(crate) fn allowance_expiry(&self, owner: Account, spender: Account) -> Option<u64> {
+        self.allowance_expiries
+            .get(&owner)
+            .and_then(|expiries| expiries.get(&spender))
+            .copied()
+    }
+
+    /// Whether the allowance of the spender has expired at the current block height.
+    pub(crate) fn allowance_expired(&self, owner: Account, spender: Account) -> bool {
+        match self.allowance_expiry(owner, spender) {
+            Some(expiry_height) => rusk_abi::block_height() >= expiry_height,
+            None => false,
+        }
+    }
+
+    pub(crate) fn remove_allowance_expiry(&mut self, owner: Account, spender: Account) {
+        if let Some(expiries) = self.allowance_expiries.get_mut(&owner) {
+            expiries.remove(&spender);
+            if expiries.is_empty() {
+                self.allowance_expiries.remove(&owner);
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Approving a contract and calling it in one go.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn approve_and_call(&mut self, approve: Signed<ApproveAndCall>) {
+        let owner = self.authorize(&approve);
+        let approve = approve.op();
+
+        let spender = approve.spender;
+        let value = approve.value;
+
+        self.set_allowance(owner, Account::Contract(spender), value);
+
+        // unlike the transfer hooks, the spender is expected to call back into the token to use the
+        // approval, so this isn't guarded against reentrancy. If the call fails (panic or OoG) the
+        // approval is reverted as well.
+        if let Err(err) = rusk_abi::call::<_, ()>(
+            spender,
+            &approve.fn_name,
+            &ApprovalInfo {
+                owner,
+                value,
+                data: approve.data.clone(),
+            },
+        ) {
+            panic!("Failed calling `{}` on the spender: {err}", approve.fn_name);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Approvals conditional on the current allowance.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn approve_if(&mut self, approve: Signed<ApproveIf>) {
+        let owner = self.authorize(&approve);
+        self.approve_if_by(owner, *approve.op());
+    }
+
+    pub(crate) fn approve_if_from_contract(&mut self, approve: ApproveIf) {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        self.approve_if_by(Account::Contract(contract), approve);
+    }
+
+    fn approve_if_by(&mut self, owner: Account, approve: ApproveIf) {
+        let current = self.allowance(Allowance {
+            owner,
+            spender: approve.spender,
+        });
+
+        if current != approve.expected {
+            panic!("The allowance is not the expected one");
+        }
+
+        self.set_allowance(owner, approve.spender, approve.value);
+    }
+}
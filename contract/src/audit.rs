@@ -0,0 +1,42 @@
+//! Recomputing the books of the token, checking them against the stored supply.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    /// Feeds the [`Audit`] of the token. It is fed rather than returned, since it touches the
+    /// whole state, which is only affordable in a feeder call.
+    pub(crate) fn audit(&self) {
+        let audit = Audit {
+            supply: self.supply,
+            balances: sum(self.accounts.values().map(|account| account.balance)),
+            locked: sum(self.locked_transfers.values().map(|locked| locked.value)),
+            pending: sum(self.pending_transfers.values().map(|pending| pending.value)),
+            vesting: sum(self
+                .vestings
+                .values()
+                .map(|vesting| vesting.total - vesting.claimed)),
+            staked: sum(self.stakes.values().map(|stake| stake.value)),
+            airdrops: sum(self
+                .airdrops
+                .values()
+                .map(|airdrop| airdrop.total - airdrop.claimed)),
+            rewards: self.rewards_held,
+            recoveries: sum(self.recoveries.values().copied()),
+            treasury: self.treasury.balance,
+            streams: sum(self
+                .streams
+                .values()
+                .map(|stream| stream.deposit - stream.withdrawn)),
+        };
+
+        rusk_abi::feed(audit);
+    }
+}
+
+/// Sums values, saturating rather than overflowing, so a corrupted state still yields an audit
+/// that doesn't balance.
+fn sum(values: impl Iterator<Item = u64>) -> u64 {
+    values.fold(0, u64::saturating_add)
+}
@@ -0,0 +1,62 @@
+//! Batches of transfers verified with a single signature.
+
+use ttoken_types::*;
+
+use crate::{check_message_version, TokenState};
+
+impl TokenState {
+    pub(crate) fn batch_transfer(&mut self, batch: BatchTransfer) {
+        if batch.transfers().is_empty() {
+            panic!("The batch has no transfers");
+        }
+
+        check_message_version(batch.version());
+
+        let sig = *batch.signature();
+        let sig_msg = batch.signature_message();
+        if !rusk_abi::verify_bls_multisig(sig_msg, batch.signers(), sig) {
+            panic!("Invalid signature");
+        }
+
+        let hook_policy = self.hook_policy;
+
+        for transfer in batch.transfers() {
+            let from = Account::External(transfer.from);
+
+            self.use_nonce(from, transfer.nonce);
+            self.move_tokens(
+                from,
+                None,
+                transfer.to,
+                transfer.value,
+                &[],
+                None,
+                hook_policy,
+            );
+        }
+    }
+
+    pub(crate) fn batch_transfer_from(&mut self, batch: Signed<BatchTransferFrom>) {
+        let spender = self.authorize(&batch);
+        let transfers = &batch.op().transfers;
+
+        if transfers.is_empty() {
+            panic!("The batch has no transfers");
+        }
+
+        let hook_policy = self.hook_policy;
+
+        for transfer in transfers {
+            self.spend_allowance(transfer.owner, spender, transfer.value);
+            self.move_tokens(
+                transfer.owner,
+                Some(spender),
+                transfer.to,
+                transfer.value,
+                &[],
+                None,
+                hook_policy,
+            );
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Burning tokens, by their owner or a spender allowed to use them.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn burn(&mut self, burn: Signed<Burn>) {
+        let owner = self.authorize(&burn);
+        self.burn_balance(owner, None, burn.op().value);
+    }
+
+    pub(crate) fn burn_from(&mut self, burn: Signed<BurnFrom>) {
+        let spender = self.authorize(&burn);
+        let burn = *burn.op();
+
+        self.spend_allowance(burn.owner, spender, burn.value);
+        self.burn_balance(burn.owner, Some(spender), burn.value);
+    }
+
+    /// Burns tokens from an account, subtracting them from the supply.
+    pub(crate) fn burn_balance(&mut self, owner: Account, spender: Option<Account>, value: u64) {
+        // the supply of wrapped tokens must match the Dusk held, so they are burned by withdrawing
+        if self.wrapped {
+            panic!("Wrapped tokens can only be burned by withdrawing them");
+        }
+
+        self.debit_limited(owner, value);
+
+        self.checkpoint_supply();
+        self.supply -= value;
+
+        self.prune_account(owner);
+
+        self.emit(
+            BURN_TOPIC,
+            BurnEvent {
+                owner,
+                spender,
+                value,
+            },
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! Burning a fraction of every transfer.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn init_burn_rate(&mut self, burn_rate: u16) {
+        if burn_rate > BURN_RATE_SCALE {
+            panic!("The burn rate can't be larger than the burn rate scale");
+        }
+        self.burn_rate = burn_rate;
+    }
+
+    pub(crate) fn burn_rate(&self) -> u16 {
+        self.burn_rate
+    }
+
+    /// Burns the fraction of the given value set by the burn rate, subtracting it from the supply
+    /// and returning the amount burned.
+    pub(crate) fn burn_fraction(&mut self, value: u64) -> u64 {
+        let burned = (value as u128 * self.burn_rate as u128 / BURN_RATE_SCALE as u128) as u64;
+
+        if burned > 0 {
+            self.checkpoint_supply();
+            self.supply -= burned;
+        }
+
+        burned
+    }
+}
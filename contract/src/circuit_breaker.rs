@@ -0,0 +1,117 @@
+//! Rate limits on the value transferred, and a circuit breaker halting larger transfers.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+/// The block a volume refers to, and the value transferred in it.
+pub(crate) type Volume = (u64, u64);
+
+impl TokenState {
+    pub(crate) fn set_rate_limits(&mut self, set: Signed<SetRateLimits>) {
+        self.authorize_admin(&set);
+        let limits = set.op().limits;
+
+        if limits.account_limit.is_some() && limits.window_blocks == 0 {
+            panic!("The window can't be zero blocks");
+        }
+
+        // volumes are counted in windows of the previous size, so they are started over
+        self.account_volumes.clear();
+        self.breaker.limits = limits;
+
+        self.emit_breaker();
+    }
+
+    pub(crate) fn trip_breaker(&mut self, trip: Signed<TripBreaker>) {
+        self.authorize_admin(&trip);
+        self.breaker.tripped_threshold = Some(trip.op().threshold);
+
+        self.emit_breaker();
+    }
+
+    pub(crate) fn reset_breaker(&mut self, reset: Signed<ResetBreaker>) {
+        self.authorize_admin(&reset);
+        self.breaker.tripped_threshold = None;
+
+        self.emit_breaker();
+    }
+
+    pub(crate) fn breaker(&self) -> BreakerInfo {
+        self.breaker
+    }
+
+    /// Removes tokens moved out of an account by its owner or a spender, counting them against the
+    /// rate limits first. Only debits the account's owner can't use to move tokens elsewhere skip
+    /// the limits: repaying a flash loan, clawbacks, rescues, recovering tokens refused by a hook,
+    /// and moves between accounts of the same owner, by sub-account moves and key rotation.
+    pub(crate) fn debit_limited(&mut self, account: Account, value: u64) {
+        self.debit_token_limited(DEFAULT_TOKEN_ID, account, value);
+    }
+
+    /// Removes tokens of the given token as [`debit_limited`](Self::debit_limited) does. The
+    /// limits count the value moved of every token alike.
+    pub(crate) fn debit_token_limited(&mut self, token: TokenId, account: Account, value: u64) {
+        self.check_rate_limits(account, value);
+        self.debit_token(token, account, value);
+    }
+
+    /// Counts the value moved out of an account against the rate limits, rejecting it if it would
+    /// exceed them, or if it is above the threshold of a tripped breaker.
+    fn check_rate_limits(&mut self, owner: Account, value: u64) {
+        self.assert_below_breaker_threshold(value);
+
+        let limits = self.breaker.limits;
+        let block_height = rusk_abi::block_height();
+
+        if let Some(limit) = limits.account_limit {
+            // the volumes all refer to the same window, so past ones are dropped at once
+            let window = block_height / limits.window_blocks;
+            if window != self.volume_window {
+                self.account_volumes.clear();
+                self.volume_window = window;
+            }
+
+            // the sub-accounts of a key share its limit, so it can't be multiplied by spreading the
+            // tokens across them
+            let volume = self.account_volumes.entry(owner.root()).or_insert(0);
+            *volume = volume
+                .checked_add(value)
+                .filter(|volume| *volume <= limit)
+                .expect("The account can't transfer more than its limit in the current window");
+        }
+
+        if let Some(limit) = limits.block_limit {
+            add_volume(&mut self.block_volume, block_height, value, limit)
+                .expect("The transfers in this block can't exceed the block limit");
+        }
+    }
+
+    /// Rejects moving a value above the threshold of a tripped breaker.
+    pub(crate) fn assert_below_breaker_threshold(&self, value: u64) {
+        if matches!(self.breaker.tripped_threshold, Some(threshold) if value > threshold) {
+            panic!("The circuit breaker is tripped");
+        }
+    }
+
+    fn emit_breaker(&mut self) {
+        let info = self.breaker;
+        self.emit("breaker", BreakerEvent { info });
+    }
+}
+
+/// Adds a value to a volume, starting it over if it refers to an earlier block. Fails if the volume
+/// would exceed the limit.
+fn add_volume(volume: &mut Volume, block_height: u64, value: u64, limit: u64) -> Option<()> {
+    if volume.0 != block_height {
+        *volume = (block_height, 0);
+    }
+
+    let total = volume
+        .1
+        .checked_add(value)
+        .filter(|total| *total <= limit)?;
+    volume.1 = total;
+
+    Some(())
+}
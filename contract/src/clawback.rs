@@ -0,0 +1,115 @@
+//! Clawbacks of tokens by the admin, only possible a delay after they are announced.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn announce_clawback(&mut self, announce: Signed<AnnounceClawback>) -> u64 {
+        self.authorize_admin(&announce);
+        let announce = *announce.op();
+
+        let delay = self
+            .clawback_delay
+            .expect("Clawbacks are not enabled for this token");
+        let executable_height = rusk_abi::block_height().saturating_add(delay);
+
+        let id = self.next_clawback_id;
+        self.next_clawback_id += 1;
+
+        self.clawbacks.insert(
+            id,
+            Clawback {
+                account: announce.account,
+                to: announce.to,
+                value: announce.value,
+                executable_height,
+            },
+        );
+
+        self.emit(
+            "clawback_announced",
+            ClawbackAnnouncedEvent {
+                id,
+                account: announce.account,
+                to: announce.to,
+                value: announce.value,
+                executable_height,
+            },
+        );
+
+        id
+    }
+
+    pub(crate) fn execute_clawback(&mut self, execute: Signed<ExecuteClawback>) {
+        self.authorize_admin(&execute);
+        let id = execute.op().id;
+
+        let clawback = self.remove_clawback(id);
+        if rusk_abi::block_height() < clawback.executable_height {
+            panic!("The clawback delay hasn't passed yet");
+        }
+
+        let owner = clawback.account;
+        let to = clawback.to;
+        let value = clawback.value;
+
+        // the tokens are taken even from an account removed from the whitelist, and regardless of
+        // the two-phase threshold or burn rate, so the balances are moved directly
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(to);
+
+        self.debit(owner, value);
+        self.credit(to, value);
+
+        self.prune_account(owner);
+        self.prune_account(to);
+
+        self.record_transfer(TransferRecord {
+            token: DEFAULT_TOKEN_ID,
+            owner,
+            spender: None,
+            to,
+            value,
+            block_height: rusk_abi::block_height(),
+        });
+
+        self.emit(
+            TRANSFER_TOPIC,
+            TransferEvent {
+                owner,
+                spender: None,
+                to,
+                value,
+                burned: 0,
+                memo: None,
+            },
+        );
+        self.emit("clawback", ClawbackEvent { id, executed: true });
+    }
+
+    pub(crate) fn cancel_clawback(&mut self, cancel: Signed<CancelClawback>) {
+        self.authorize_admin(&cancel);
+        let id = cancel.op().id;
+
+        self.remove_clawback(id);
+
+        self.emit(
+            "clawback",
+            ClawbackEvent {
+                id,
+                executed: false,
+            },
+        );
+    }
+
+    pub(crate) fn clawback(&self, id: u64) -> Option<Clawback> {
+        self.clawbacks.get(&id).copied()
+    }
+
+    fn remove_clawback(&mut self, id: u64) -> Clawback {
+        self.clawbacks
+            .remove(&id)
+            .expect("The clawback doesn't exist")
+    }
+}
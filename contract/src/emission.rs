@@ -0,0 +1,70 @@
+//! Tokens minted to a treasury at a fixed rate, as they accrue with every epoch.
+//!
+//! Minting is triggered by anyone calling `poke_emission`, which mints the tokens of all the epochs
+//! completed since the last call, so no key is needed to keep the emission going.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn init_emission(&mut self, schedule: EmissionSchedule) {
+        if schedule.epoch_blocks == 0 {
+            panic!("The epochs of the emission can't be zero blocks");
+        }
+
+        self.emission = Some(EmissionInfo {
+            schedule,
+            start_height: rusk_abi::block_height(),
+            epochs_emitted: 0,
+        });
+    }
+
+    pub(crate) fn poke_emission(&mut self) -> u64 {
+        let mut info = self.emission.expect("The token has no emission");
+        let schedule = info.schedule;
+
+        let mut height = rusk_abi::block_height();
+        if let Some(end_height) = schedule.end_height {
+            height = height.min(end_height);
+        }
+
+        let epochs = height.saturating_sub(info.start_height) / schedule.epoch_blocks;
+        if epochs <= info.epochs_emitted {
+            return 0;
+        }
+
+        let value = (epochs - info.epochs_emitted)
+            .checked_mul(schedule.rate)
+            .and_then(|value| self.supply.checked_add(value).map(|_| value))
+            .expect("The supply can't overflow");
+
+        self.assert_not_paused(PauseScope::MINT);
+
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(schedule.treasury);
+
+        self.checkpoint_supply();
+        self.supply += value;
+
+        self.credit(schedule.treasury, value);
+
+        info.epochs_emitted = epochs;
+        self.emission = Some(info);
+
+        self.emit(
+            EMISSION_TOPIC,
+            EmissionEvent {
+                treasury: schedule.treasury,
+                value,
+                epochs_emitted: epochs,
+            },
+        );
+
+        value
+    }
+
+    pub(crate) fn emission(&self) -> Option<EmissionInfo> {
+        self.emission
+    }
+}
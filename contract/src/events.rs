@@ -0,0 +1,52 @@
+//! Emitting events tagged with sequence numbers, and keeping the most recent ones.
+
+use alloc::string::String;
+
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::Serialize;
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    /// Emits an event tagged with the next sequence number, keeping it in the buffer of recent
+    /// events.
+    pub(crate) fn emit<E>(&mut self, topic: &'static str, event: E)
+    where
+        Sequenced<E>: Serialize<AllocSerializer<256>>,
+    {
+        let seq = self.next_event_seq;
+        self.next_event_seq += 1;
+
+        let data = rkyv::to_bytes::<_, 256>(&Sequenced { seq, event })
+            .expect("Serializing an event should succeed")
+            .into_vec();
+
+        rusk_abi::emit_raw(topic, &data);
+
+        let record = EventRecord {
+            seq,
+            topic: String::from(topic),
+            data,
+        };
+
+        // the buffer is a ring, with the oldest event overwritten once it is full
+        if self.events.len() < EVENT_BUFFER_LEN {
+            self.events.push(record);
+        } else {
+            self.events[seq as usize % EVENT_BUFFER_LEN] = record;
+        }
+    }
+
+    /// Feeds the recent events with a sequence number starting from the given one. If the first
+    /// event fed has a higher sequence number than the one asked for, the events in between are no
+    /// longer kept.
+    pub(crate) fn events_since(&self, seq: u64) {
+        let oldest = self.next_event_seq as usize % self.events.len().max(1);
+        let (newer, older) = self.events.split_at(oldest);
+
+        for record in older.iter().chain(newer).filter(|record| record.seq >= seq) {
+            rusk_abi::feed(record.clone());
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Flash loans, minting tokens to a contract that must repay them within the same call.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn flash_loan(&mut self, loan: FlashLoan) {
+        let borrower = Account::Contract(loan.borrower);
+        let value = loan.value;
+        let fee = self.flash_fee(value);
+        let owed = value.checked_add(fee).expect("The loan can't overflow");
+
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(borrower);
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+
+        self.checkpoint_supply();
+        self.supply = self
+            .supply
+            .checked_add(value)
+            .expect("The supply can't overflow");
+        self.credit(borrower, value);
+
+        let info = FlashLoanInfo {
+            initiator: rusk_abi::caller(),
+            value,
+            fee,
+            data: loan.data,
+        };
+
+        // the borrower is expected to use the tokens, calling back into the token contract, so this
+        // isn't guarded against reentrancy. Balances are settled while it is being called, and if
+        // the call fails (panic or OoG) the loan is reverted as a whole.
+        match rusk_abi::call::<_, bool>(loan.borrower, "on_flash_loan", &info) {
+            Ok(true) => {}
+            Ok(false) => panic!("The borrower rejected the flash loan"),
+            Err(err) => panic!("Failed calling `on_flash_loan` on the borrower: {err}"),
+        }
+
+        if self.account(borrower).balance < owed {
+            panic!("The flash loan wasn't repaid");
+        }
+
+        self.debit(borrower, owed);
+        self.checkpoint_supply();
+        self.supply -= value;
+
+        if fee > 0 {
+            self.credit(Account::Contract(rusk_abi::self_id()), fee);
+        }
+        self.prune_account(borrower);
+
+        self.emit(
+            "flash_loan",
+            FlashLoanEvent {
+                borrower: loan.borrower,
+                value,
+                fee,
+            },
+        );
+    }
+
+    /// The fee taken on a flash loan of the given value.
+    pub(crate) fn flash_fee(&self, value: u64) -> u64 {
+        (value as u128 * self.flash_loan_fee as u128 / FLASH_LOAN_FEE_SCALE as u128) as u64
+    }
+}
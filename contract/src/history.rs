@@ -0,0 +1,36 @@
+//! Bounded history of the most recent transfers of each account.
+
+use alloc::vec::Vec;
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    /// The most recent transfers involving the account, from oldest to newest.
+    pub(crate) fn history(&self, account: Account) -> Vec<TransferRecord> {
+        self.histories.get(&account).cloned().unwrap_or_default()
+    }
+
+    /// Records a transfer in the history of the accounts involved, dropping their oldest records
+    /// if it is full.
+    pub(crate) fn record_transfer(&mut self, record: TransferRecord) {
+        if self.history_len == 0 {
+            return;
+        }
+
+        self.push_record(record.owner, record);
+        if record.to != record.owner {
+            self.push_record(record.to, record);
+        }
+    }
+
+    fn push_record(&mut self, account: Account, record: TransferRecord) {
+        let history = self.histories.entry(account).or_default();
+
+        if history.len() == self.history_len as usize {
+            history.remove(0);
+        }
+        history.push(record);
+    }
+}
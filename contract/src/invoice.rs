@@ -0,0 +1,60 @@
+//! Invoices created by a payee and accepted off-chain by the payer, settled without an allowance.
+//!
+//! The payer's signature authorizes a single payment, and the id of a settled invoice is kept for
+//! its payee and payer, so the invoice can't be settled twice, even if the payer accepts it again.
+//! The payee signs nothing, so ids are only unique per payer: otherwise anyone could settle an
+//! invoice of their own making first, blocking the payee's real invoice with the same id.
+
+use alloc::collections::BTreeSet;
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn settle_invoice(&mut self, invoice: Signed<Invoice>) {
+        let payer = self.authorize(&invoice);
+        let invoice = *invoice.op();
+
+        if rusk_abi::block_height() > invoice.expiry {
+            panic!("The invoice has expired");
+        }
+
+        let settled = self
+            .settled_invoices
+            .entry((invoice.payee, payer))
+            .or_insert(BTreeSet::new())
+            .insert(invoice.id);
+        if !settled {
+            panic!("The invoice has already been settled");
+        }
+
+        let hook_policy = self.hook_policy;
+        self.move_tokens(
+            payer,
+            None,
+            invoice.payee,
+            invoice.value,
+            &[],
+            None,
+            hook_policy,
+        );
+
+        self.emit(
+            "invoice_settled",
+            InvoiceSettledEvent {
+                payee: invoice.payee,
+                id: invoice.id,
+                payer,
+                value: invoice.value,
+            },
+        );
+    }
+
+    pub(crate) fn invoice_settled(&self, id: InvoiceId) -> bool {
+        self.settled_invoices
+            .get(&(id.payee, id.payer))
+            .map(|ids| ids.contains(&id.id))
+            .unwrap_or(false)
+    }
+}
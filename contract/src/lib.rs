@@ -2,32 +2,289 @@
 
 extern crate alloc;
 
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::String;
 use alloc::vec::Vec;
 
+use bytecheck::CheckBytes;
+use execution_core::signatures::bls::PublicKey;
+use execution_core::ContractId;
+use rkyv::with::Skip;
+use rkyv::{Archive, Deserialize, Serialize};
+use rusk_abi::ContractError;
 use ttoken_types::*;
 
+mod airdrop;
+mod allowance_expiry;
+mod approve_and_call;
+mod approve_if;
+mod audit;
+mod batch;
+mod burn;
+#[cfg(feature = "fees")]
+mod burn_rate;
+mod circuit_breaker;
+mod clawback;
+mod emission;
+mod events;
+mod flash_loan;
+mod history;
+mod invoice;
+mod locked;
+mod migration;
+mod min_transfer;
+mod multi_token;
+mod multisig;
+mod native_staking;
+mod nonces;
+mod operator;
+mod pause;
+mod permit;
+mod prune;
+mod raw;
+#[cfg(feature = "fees")]
+mod relayed;
+mod rescue;
+mod rewards;
+mod rotate_key;
+mod session_key;
+mod snapshot;
+mod spending_limit;
+mod staking;
+mod state_root;
+mod stream;
+mod sub_account;
+mod submission;
+mod treasury;
+mod two_phase;
+mod verifier;
+mod vesting;
+#[cfg(feature = "compliance")]
+mod whitelist;
+mod wrapped;
+
+use circuit_breaker::Volume;
+use nonces::NonceWord;
+use snapshot::Checkpoints;
+
+/// The state of the contract, kept in its memory. The VM loads memory in pages as it is touched, so
+/// a call only pays for the parts of the maps it accesses.
+#[derive(Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
 struct TokenState {
     accounts: BTreeMap<Account, AccountInfo>,
+    state_tree: Vec<Vec<[u8; 32]>>,
+    state_leaves: BTreeMap<Account, u64>,
+    nonces: BTreeMap<Account, BTreeMap<u64, NonceWord>>,
     allowances: BTreeMap<Account, BTreeMap<Account, u64>>,
+    allowance_expiries: BTreeMap<Account, BTreeMap<Account, u64>>,
+    spending_limits: BTreeMap<Account, BTreeMap<Account, SpendingLimit>>,
+    recoveries: BTreeMap<Account, u64>,
+    idempotency_keys: BTreeMap<ContractId, BTreeSet<u64>>,
+    sent_hooks: BTreeSet<ContractId>,
+    verifiers: BTreeMap<ContractId, Verifier>,
+    operators: BTreeMap<Account, BTreeSet<Account>>,
+    session_keys: BTreeMap<Account, SessionKey>,
     supply: u64,
+    tokens: BTreeMap<TokenId, TokenInfo>,
+    token_balances: BTreeMap<TokenId, BTreeMap<Account, u64>>,
+    token_allowances: BTreeMap<TokenId, BTreeMap<Account, BTreeMap<Account, u64>>>,
+    admin: Option<PublicKey>,
+    hook_policy: HookPolicy,
+    snapshot_id: u64,
+    balance_checkpoints: BTreeMap<Account, Checkpoints>,
+    supply_checkpoints: Checkpoints,
+    vestings: BTreeMap<Account, Vesting>,
+    locked_transfers: BTreeMap<u64, LockedTransfer>,
+    next_lock_id: u64,
+    pending_transfers: BTreeMap<u64, PendingTransfer>,
+    next_pending_id: u64,
+    two_phase_threshold: Option<u64>,
+    min_transfer: u64,
+    flash_loan_fee: u16,
+    clawbacks: BTreeMap<u64, Clawback>,
+    next_clawback_id: u64,
+    clawback_delay: Option<u64>,
+    emission: Option<EmissionInfo>,
+    breaker: BreakerInfo,
+    paused: PauseScope,
+    volume_window: u64,
+    account_volumes: BTreeMap<Account, u64>,
+    block_volume: Volume,
+    multisigs: BTreeMap<u64, Multisig>,
+    next_multisig_id: u64,
+    airdrops: BTreeMap<u64, Airdrop>,
+    airdrop_claims: BTreeMap<u64, BTreeSet<Account>>,
+    next_airdrop_id: u64,
+    stakes: BTreeMap<Account, StakeInfo>,
+    native_stakes: BTreeMap<Account, NativeStakeInfo>,
+    distributions: Vec<Distribution>,
+    rewards_held: u64,
+    reward_cursors: BTreeMap<Account, usize>,
+    wrapped: bool,
+    events: Vec<EventRecord>,
+    next_event_seq: u64,
+    histories: BTreeMap<Account, Vec<TransferRecord>>,
+    history_len: u32,
+    submissions: BTreeMap<Account, Vec<Submission>>,
+    treasury: TreasuryInfo,
+    payees: BTreeMap<Account, Payee>,
+    settled_invoices: BTreeMap<(Account, Account), BTreeSet<u64>>,
+    streams: BTreeMap<u64, Stream>,
+    next_stream_id: u64,
+    holder_count: u64,
+    /// The sum of the balances of all accounts, leaving out tokens escrowed by the contract.
+    circulating: u64,
+    #[cfg(feature = "compliance")]
+    whitelist: Option<BTreeSet<Account>>,
+    #[cfg(feature = "fees")]
+    burn_rate: u16,
+    /// The chunks of a state being imported by a migration, which isn't part of the state itself.
+    #[with(Skip)]
+    migration_buffer: Vec<u8>,
+    /// The number of chunks imported by a migration, which isn't part of the state itself.
+    #[with(Skip)]
+    migration_chunks: u32,
+    /// Whether a state has been imported by a migration, which isn't part of the state itself.
+    #[with(Skip)]
+    migrated: bool,
+    /// Whether a hook on another contract is being called, which isn't part of the state itself.
+    #[with(Skip)]
+    in_hook: bool,
+    /// The account native Dusk is being unstaked for, which isn't part of the state itself.
+    #[with(Skip)]
+    unstaking: Option<Account>,
 }
 
 impl TokenState {
-    fn init(&mut self, accounts: Vec<(Account, u64)>) {
-        for (account, balance) in accounts {
-            let account = self.accounts.entry(account).or_insert(AccountInfo::EMPTY);
-            account.balance += balance;
+    fn init(&mut self, args: InitArgs) {
+        if args.wrapped
+            && (!args.balances.is_empty() || args.burn_rate != 0 || args.emission.is_some())
+        {
+            panic!("A wrapped token can't have initial balances, a burn rate, or an emission");
+        }
+        self.wrapped = args.wrapped;
+
+        for (account, balance) in args.balances {
+            self.credit(account, balance);
             self.supply += balance;
         }
+        self.admin = args.admin;
+        self.hook_policy = args.hook_policy;
+        self.history_len = args.history_len;
+        self.two_phase_threshold = args.two_phase_threshold;
+
+        if args.flash_loan_fee > FLASH_LOAN_FEE_SCALE {
+            panic!("The flash loan fee can't be more than the loan");
+        }
+        self.flash_loan_fee = args.flash_loan_fee;
+        self.clawback_delay = args.clawback_delay;
+
+        if let Some(schedule) = args.emission {
+            self.init_emission(schedule);
+        }
+
+        #[cfg(feature = "compliance")]
+        if let Some(whitelist) = args.whitelist {
+            self.init_whitelist(whitelist);
+        }
+        #[cfg(not(feature = "compliance"))]
+        if args.whitelist.is_some() {
+            panic!("The whitelist mode requires the `compliance` feature");
+        }
+
+        #[cfg(feature = "fees")]
+        self.init_burn_rate(args.burn_rate);
+        #[cfg(not(feature = "fees"))]
+        if args.burn_rate != 0 {
+            panic!("The burn rate requires the `fees` feature");
+        }
     }
 }
 
 static mut STATE: TokenState = TokenState {
     accounts: BTreeMap::new(),
+    state_tree: Vec::new(),
+    state_leaves: BTreeMap::new(),
+    nonces: BTreeMap::new(),
     allowances: BTreeMap::new(),
+    allowance_expiries: BTreeMap::new(),
+    spending_limits: BTreeMap::new(),
+    recoveries: BTreeMap::new(),
+    idempotency_keys: BTreeMap::new(),
+    sent_hooks: BTreeSet::new(),
+    verifiers: BTreeMap::new(),
+    operators: BTreeMap::new(),
+    session_keys: BTreeMap::new(),
     supply: 0,
+    tokens: BTreeMap::new(),
+    token_balances: BTreeMap::new(),
+    token_allowances: BTreeMap::new(),
+    admin: None,
+    hook_policy: HookPolicy::Revert,
+    snapshot_id: 0,
+    balance_checkpoints: BTreeMap::new(),
+    supply_checkpoints: Vec::new(),
+    vestings: BTreeMap::new(),
+    locked_transfers: BTreeMap::new(),
+    next_lock_id: 0,
+    pending_transfers: BTreeMap::new(),
+    next_pending_id: 0,
+    two_phase_threshold: None,
+    min_transfer: 0,
+    flash_loan_fee: 0,
+    clawbacks: BTreeMap::new(),
+    next_clawback_id: 0,
+    clawback_delay: None,
+    emission: None,
+    breaker: BreakerInfo {
+        limits: RateLimits {
+            account_limit: None,
+            window_blocks: 0,
+            block_limit: None,
+        },
+        tripped_threshold: None,
+    },
+    paused: PauseScope::NONE,
+    volume_window: 0,
+    account_volumes: BTreeMap::new(),
+    block_volume: (0, 0),
+    multisigs: BTreeMap::new(),
+    next_multisig_id: 0,
+    airdrops: BTreeMap::new(),
+    airdrop_claims: BTreeMap::new(),
+    next_airdrop_id: 0,
+    stakes: BTreeMap::new(),
+    native_stakes: BTreeMap::new(),
+    distributions: Vec::new(),
+    rewards_held: 0,
+    reward_cursors: BTreeMap::new(),
+    wrapped: false,
+    events: Vec::new(),
+    next_event_seq: 0,
+    histories: BTreeMap::new(),
+    submissions: BTreeMap::new(),
+    treasury: TreasuryInfo {
+        balance: 0,
+        payout_key: None,
+        epoch_blocks: 0,
+    },
+    payees: BTreeMap::new(),
+    settled_invoices: BTreeMap::new(),
+    streams: BTreeMap::new(),
+    next_stream_id: 0,
+    history_len: 0,
+    holder_count: 0,
+    circulating: 0,
+    #[cfg(feature = "compliance")]
+    whitelist: None,
+    #[cfg(feature = "fees")]
+    burn_rate: 0,
+    migration_buffer: Vec::new(),
+    migration_chunks: 0,
+    migrated: false,
+    in_hook: false,
+    unstaking: None,
 };
 
 impl TokenState {
@@ -47,6 +304,41 @@ impl TokenState {
         self.supply
     }
 
+    fn features(&self) -> Vec<String> {
+        let mut features = Vec::new();
+
+        if cfg!(feature = "fees") {
+            features.push(String::from("fees"));
+        }
+        if cfg!(feature = "compliance") {
+            features.push(String::from("compliance"));
+        }
+
+        features
+    }
+
+    fn supports(&self, feature: FeatureId) -> bool {
+        match feature {
+            FeatureId::Fees => cfg!(feature = "fees"),
+            FeatureId::Compliance => cfg!(feature = "compliance"),
+            FeatureId::Wrapped | FeatureId::NativeStaking => self.wrapped,
+            FeatureId::Clawback => self.clawback_delay.is_some(),
+            FeatureId::Emission => self.emission.is_some(),
+            FeatureId::Snapshots
+            | FeatureId::Multisig
+            | FeatureId::Permit
+            | FeatureId::MultiToken
+            | FeatureId::FlashLoans
+            | FeatureId::CircuitBreaker
+            | FeatureId::Pause
+            | FeatureId::Treasury => true,
+        }
+    }
+
+    fn spec_version(&self) -> u32 {
+        SPEC_VERSION
+    }
+
     fn account(&self, account: Account) -> AccountInfo {
         self.accounts
             .get(&account)
@@ -54,84 +346,257 @@ impl TokenState {
             .unwrap_or(AccountInfo::EMPTY)
     }
 
+    fn holder_count(&self) -> u64 {
+        self.holder_count
+    }
+
+    fn exists(&self, account: Account) -> bool {
+        self.accounts.contains_key(&account)
+    }
+
     fn allowance(&self, allowance: Allowance) -> u64 {
+        if self.allowance_expired(allowance.owner, allowance.spender) {
+            return 0;
+        }
         match self.allowances.get(&allowance.owner) {
             Some(allowances) => allowances.get(&allowance.spender).copied().unwrap_or(0),
             None => 0,
         }
     }
 
+    fn allowance_info(&self, allowance: Allowance) -> AllowanceInfo {
+        AllowanceInfo {
+            value: self.allowance(allowance),
+            expiry_height: self.allowance_expiry(allowance.owner, allowance.spender),
+            spending_limit: self.current_spending_limit(allowance),
+        }
+    }
+
     fn transfer(&mut self, transfer: Transfer) {
         let from_key = *transfer.from();
-        let from = Account::External(from_key);
+        let from = from_key.account();
 
-        let from_account = self
-            .accounts
-            .get_mut(&from)
-            .expect("The account has no tokens to transfer");
+        self.use_nonce(from, transfer.nonce());
 
-        let value = transfer.value();
-        if from_account.balance < value {
-            panic!("The account doesn't have enough tokens");
+        check_message_version(transfer.version());
+
+        verify_signature(
+            from_key,
+            *transfer.signature(),
+            transfer.signature_message(),
+        );
+
+        if let Some(id) = transfer.idempotency_id() {
+            self.assert_new_submission(from, id);
         }
 
-        if transfer.nonce() != from_account.nonce + 1 {
-            panic!("Nonces must be sequential");
+        // a session key transfers from the account that registered it
+        let (owner, spender) = match self.spend_session_key(from, *transfer.to(), transfer.value())
+        {
+            Some(owner) => (owner, Some(from)),
+            None => (from, None),
+        };
+
+        let hook_policy = transfer.hook_policy().unwrap_or(self.hook_policy);
+        let received = self.move_tokens(
+            owner,
+            spender,
+            *transfer.to(),
+            transfer.value(),
+            transfer.data(),
+            transfer.memo().copied(),
+            hook_policy,
+        );
+
+        if let Some(id) = transfer.idempotency_id() {
+            self.record_submission(
+                from,
+                Submission {
+                    id,
+                    nonce: transfer.nonce(),
+                    block_height: rusk_abi::block_height(),
+                    value: transfer.value(),
+                    received,
+                },
+            );
         }
+    }
+
+    fn transfer_from(&mut self, transfer: TransferFrom) {
+        let spender_key = *transfer.spender();
+        let spender = spender_key.account();
+
+        self.use_nonce(spender, transfer.nonce());
+
+        check_message_version(transfer.version());
 
-        from_account.balance -= value;
-        from_account.nonce += 1;
+        verify_signature(
+            spender_key,
+            *transfer.signature(),
+            transfer.signature_message(),
+        );
 
-        let sig = *transfer.signature();
-        let sig_msg = transfer.signature_message().to_vec();
-        if !rusk_abi::verify_bls(sig_msg, from_key, sig) {
-            panic!("Invalid signature");
+        if let Some(id) = transfer.idempotency_id() {
+            self.assert_new_submission(spender, id);
         }
 
-        let to = *transfer.to();
-        let to_account = self.accounts.entry(to).or_insert(AccountInfo::EMPTY);
+        let owner = *transfer.owner();
+        let value = transfer.value();
 
-        to_account.balance += value;
+        self.spend_allowance(owner, spender, value);
 
-        rusk_abi::emit(
-            "transfer",
-            TransferEvent {
-                owner: from,
-                spender: None,
-                to,
-                value,
-            },
+        let hook_policy = transfer.hook_policy().unwrap_or(self.hook_policy);
+        let received = self.move_tokens(
+            owner,
+            Some(spender),
+            *transfer.to(),
+            value,
+            transfer.data(),
+            transfer.memo().copied(),
+            hook_policy,
         );
 
-        // if the transfer is to a contract, the acceptance function of said contract is called. if
-        // it fails (panic or OoG) the transfer also fails.
-        if let Account::Contract(contract) = to {
-            if let Err(err) =
-                rusk_abi::call::<_, ()>(contract, "token_received", &TransferInfo { from, value })
-            {
-                panic!("Failed calling `token_received` on the receiving contract: {err}");
+        if let Some(id) = transfer.idempotency_id() {
+            self.record_submission(
+                spender,
+                Submission {
+                    id,
+                    nonce: transfer.nonce(),
+                    block_height: rusk_abi::block_height(),
+                    value,
+                    received,
+                },
+            );
+        }
+    }
+
+    fn transfer_from_contract(
+        &mut self,
+        transfer: TransferFromContract,
+    ) -> Result<(), TransferFromContractError> {
+        let contract_id = rusk_abi::caller().expect("Must be called by a contract");
+        let contract = Account::Contract(contract_id);
+
+        if let Some(key) = transfer.idempotency_key {
+            let keys = self.idempotency_keys.entry(contract_id).or_default();
+            if !keys.insert(key) {
+                return Err(TransferFromContractError::DuplicateIdempotencyKey(key));
             }
         }
+
+        // if transferring from another account, the contract must be allowed to spend its tokens
+        let owner = transfer.from.unwrap_or(contract);
+        let spender = if owner == contract {
+            None
+        } else {
+            self.spend_allowance(owner, contract, transfer.value);
+            Some(contract)
+        };
+
+        let hook_policy = transfer.hook_policy.unwrap_or(self.hook_policy);
+        self.move_tokens(
+            owner,
+            spender,
+            transfer.to,
+            transfer.value,
+            &transfer.data,
+            None,
+            hook_policy,
+        );
+
+        Ok(())
     }
 
-    fn transfer_from(&mut self, transfer: TransferFrom) {
-        let spender_key = *transfer.spender();
-        let spender = Account::External(spender_key);
+    fn idempotency_key_used(&self, key: IdempotencyKey) -> bool {
+        self.idempotency_keys
+            .get(&key.contract)
+            .map(|keys| keys.contains(&key.key))
+            .unwrap_or(false)
+    }
+
+    fn approve(&mut self, approve: Approve) {
+        let owner_key = *approve.owner();
+        let owner = owner_key.account();
+
+        self.use_nonce(owner, approve.nonce());
+
+        check_message_version(approve.version());
+
+        verify_signature(owner_key, *approve.signature(), approve.signature_message());
+
+        self.set_allowance(owner, *approve.spender(), approve.value());
+    }
+
+    fn approve_from_contract(&mut self, approve: ApproveFromContract) {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        self.set_allowance(Account::Contract(contract), approve.spender, approve.value);
+    }
+
+    fn register_token_sent_hook(&mut self, register: bool) {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+
+        if register {
+            self.sent_hooks.insert(contract);
+        } else {
+            self.sent_hooks.remove(&contract);
+        }
+    }
+
+    fn token_sent_hook(&self, contract: ContractId) -> bool {
+        self.sent_hooks.contains(&contract)
+    }
+
+    fn recoverable(&self, account: Account) -> u64 {
+        self.recoveries.get(&account).copied().unwrap_or(0)
+    }
+
+    fn claim_recovery(&mut self, claim: Signed<ClaimRecovery>) {
+        let owner = self.authorize(&claim);
+        self.claim_recovered(owner, claim.op().value);
+    }
 
-        let spender_account = self.accounts.entry(spender).or_insert(AccountInfo::EMPTY);
-        if transfer.nonce() != spender_account.nonce + 1 {
-            panic!("Nonces must be sequential");
+    fn claim_recovery_from_contract(&mut self, claim: ClaimRecovery) {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        self.claim_recovered(Account::Contract(contract), claim.value);
+    }
+}
+
+impl TokenState {
+    /// Sets the allowance a spender has of the owner's tokens.
+    fn set_allowance(&mut self, owner: Account, spender: Account, value: u64) {
+        // revoking an approval is allowed even while approvals are paused
+        if value > 0 {
+            self.assert_not_paused(PauseScope::APPROVALS);
         }
 
-        spender_account.nonce += 1;
+        let allowances = self.allowances.entry(owner).or_insert(BTreeMap::new());
+        allowances.insert(spender, value);
 
-        let sig = *transfer.signature();
-        let sig_msg = transfer.signature_message().to_vec();
-        if !rusk_abi::verify_bls(sig_msg, spender_key, sig) {
-            panic!("Invalid signature");
+        // a revoked allowance doesn't keep its expiry for the next approval
+        if value == 0 {
+            self.remove_allowance_expiry(owner, spender);
         }
 
-        let owner = *transfer.owner();
+        self.emit(
+            APPROVE_TOPIC,
+            ApproveEvent {
+                owner,
+                spender,
+                value,
+            },
+        );
+    }
+
+    /// Deducts the given value from the allowance a spender has of the owner's tokens, or from its
+    /// budget in the current window if it has a spending limit.
+    fn spend_allowance(&mut self, owner: Account, spender: Account, value: u64) {
+        if self.allowance_expired(owner, spender) {
+            panic!("The allowance has expired");
+        }
+
+        if self.spend_limited(owner, spender, value) {
+            return;
+        }
 
         let allowance = self
             .allowances
@@ -140,131 +605,365 @@ impl TokenState {
             .get_mut(&spender)
             .expect("The spender is not allowed to use the account");
 
-        let value = transfer.value();
         if value > *allowance {
             panic!("The spender can't spent the defined amount");
         }
 
-        let owner_account = self
+        *allowance -= value;
+    }
+
+    /// Moves tokens from one account to another, emitting a transfer event with the given memo and
+    /// calling the acceptance function of the receiving account with the given data. Returns the
+    /// value the receiving account is left with, which is zero if the tokens were diverted to a
+    /// recovery bucket.
+    #[allow(clippy::too_many_arguments)]
+    fn move_tokens(
+        &mut self,
+        owner: Account,
+        spender: Option<Account>,
+        to: Account,
+        value: u64,
+        data: &[u8],
+        memo: Option<Memo>,
+        hook_policy: HookPolicy,
+    ) -> u64 {
+        self.move_tokens_of(
+            DEFAULT_TOKEN_ID,
+            owner,
+            spender,
+            to,
+            value,
+            data,
+            memo,
+            hook_policy,
+        )
+    }
+
+    /// Moves tokens of the given token as [`move_tokens`](Self::move_tokens) does. Only the owners
+    /// of the default token are notified by `token_sent`, since the hook doesn't carry the token.
+    #[allow(clippy::too_many_arguments)]
+    fn move_tokens_of(
+        &mut self,
+        token: TokenId,
+        owner: Account,
+        spender: Option<Account>,
+        to: Account,
+        value: u64,
+        data: &[u8],
+        memo: Option<Memo>,
+        hook_policy: HookPolicy,
+    ) -> u64 {
+        let received = self.move_balance(token, owner, spender, to, value, memo);
+
+        if let (Some(spender), DEFAULT_TOKEN_ID) = (spender, token) {
+            self.notify_sent(owner, spender, to, received);
+        }
+        match self.notify_received(token, owner, to, received, data, hook_policy) {
+            true => received,
+            false => 0,
+        }
+    }
+
+    /// Moves tokens of the given token from one account to another and emits a transfer event,
+    /// without calling any hooks. Returns the value received, which is less than the value moved
+    /// if part of it is burned.
+    fn move_balance(
+        &mut self,
+        token: TokenId,
+        owner: Account,
+        spender: Option<Account>,
+        to: Account,
+        value: u64,
+        memo: Option<Memo>,
+    ) -> u64 {
+        #[cfg(feature = "compliance")]
+        {
+            self.assert_whitelisted(owner);
+            self.assert_whitelisted(to);
+        }
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+        self.assert_min_token_transfer(token, owner, value);
+        self.assert_below_two_phase_threshold(value);
+
+        self.debit_token_limited(token, owner, value);
+
+        // the burn rate only applies to the default token, whose supply it is taken from
+        #[cfg(feature = "fees")]
+        let burned = match token {
+            DEFAULT_TOKEN_ID => self.burn_fraction(value),
+            _ => 0,
+        };
+        #[cfg(not(feature = "fees"))]
+        let burned = 0;
+
+        let received = value - burned;
+
+        self.credit_token(token, to, received);
+
+        self.prune_account(owner);
+        self.prune_account(to);
+
+        self.record_transfer(TransferRecord {
+            token,
+            owner,
+            spender,
+            to,
+            value,
+            block_height: rusk_abi::block_height(),
+        });
+
+        if token == DEFAULT_TOKEN_ID {
+            self.emit(
+                TRANSFER_TOPIC,
+                TransferEvent {
+                    owner,
+                    spender,
+                    to,
+                    value,
+                    burned,
+                    memo,
+                },
+            );
+        } else {
+            self.emit(
+                "token_transfer",
+                TokenTransferEvent {
+                    token,
+                    owner,
+                    spender,
+                    to,
+                    value,
+                },
+            );
+        }
+
+        received
+    }
+
+    /// Adds tokens to the balance of an account, keeping track of the number of holders and of the
+    /// tokens in circulation.
+    fn credit(&mut self, account: Account, value: u64) {
+        if matches!(account, Account::SubAccount(_, 0)) {
+            panic!("Sub-account 0 is the account of the key itself");
+        }
+
+        self.checkpoint_balance(account);
+
+        let info = self.accounts.entry(account).or_insert(AccountInfo::EMPTY);
+        if info.balance == 0 && value > 0 {
+            self.holder_count += 1;
+        }
+        info.balance += value;
+        self.circulating += value;
+
+        self.update_state_leaf(account);
+    }
+
+    /// Removes tokens from the balance of an account, keeping track of the number of holders and of
+    /// the tokens in circulation.
+    fn debit(&mut self, account: Account, value: u64) {
+        self.checkpoint_balance(account);
+
+        let info = self
             .accounts
-            .get_mut(&owner)
-            .expect("The account has no tokens to transfer");
+            .get_mut(&account)
+            .expect("The account has no tokens");
 
-        if owner_account.balance < value {
+        if info.balance < value {
             panic!("The account doesn't have enough tokens");
         }
 
-        *allowance -= value;
-        owner_account.balance -= value;
+        info.balance -= value;
+        if info.balance == 0 && value > 0 {
+            self.holder_count -= 1;
+        }
+        self.circulating -= value;
 
-        let to = *transfer.to();
-        let to_account = self.accounts.entry(to).or_insert(AccountInfo::EMPTY);
+        self.update_state_leaf(account);
+    }
 
-        to_account.balance += value;
+    /// Rejects state-mutating calls made by a hook, while it is being called. Balances are credited
+    /// before calling the hooks, so a contract calling back into the token from its hook would act
+    /// on a transfer that isn't complete yet.
+    fn non_reentrant(&mut self) -> &mut Self {
+        if self.in_hook {
+            panic!("The token contract can't be called back into from a hook");
+        }
+        self
+    }
 
-        rusk_abi::emit(
-            "transfer",
-            TransferEvent {
-                owner,
-                spender: Some(spender),
-                to,
-                value,
-            },
-        );
+    /// Calls the `token_sent` function of the owning account, if it is a contract that opted in to
+    /// being notified when an approved spender transfers its tokens. If it fails (panic or OoG) the
+    /// transfer also fails.
+    fn notify_sent(&mut self, owner: Account, spender: Account, to: Account, value: u64) {
+        let contract = match owner {
+            Account::Contract(contract) if self.sent_hooks.contains(&contract) => contract,
+            _ => return,
+        };
 
-        // if the transfer is to a contract, the acceptance function of said contract is called. if
-        // it fails (panic or OoG) the transfer also fails.
-        if let Account::Contract(contract) = to {
-            if let Err(err) = rusk_abi::call::<_, ()>(
-                contract,
-                "token_received",
-                &TransferInfo { from: owner, value },
-            ) {
-                panic!("Failed calling `token_received` on the receiving contract: {err}");
-            }
+        let info = TransferSentInfo { spender, to, value };
+
+        self.in_hook = true;
+        let result = rusk_abi::call::<_, ()>(contract, "token_sent", &info);
+        self.in_hook = false;
+
+        if let Err(err) = result {
+            panic!("Failed calling `token_sent` on the owning contract: {err}");
         }
     }
 
-    fn transfer_from_contract(&mut self, transfer: TransferFromContract) {
-        let contract = rusk_abi::caller().expect("Must be called by a contract");
-        let contract = Account::Contract(contract);
+    /// Checks the nonce and signature of an operation signed by an externally owned account,
+    /// returning the signer's account.
+    fn authorize<T: Operation>(&mut self, signed: &Signed<T>) -> Account {
+        let signer_key = *signed.signer();
+        let signer = signer_key.account();
 
-        let contract_account = self
-            .accounts
-            .get_mut(&contract)
-            .expect("Contract has no tokens to transfer");
+        self.use_nonce(signer, signed.nonce());
+
+        check_message_version(signed.version());
+
+        verify_signature(signer_key, *signed.signature(), signed.signature_message());
+
+        signer
+    }
 
-        if contract_account.balance < transfer.value {
-            panic!("The contract doesn't have enough tokens");
+    /// Checks that an operation is signed by the admin, as well as its nonce and signature.
+    fn authorize_admin<T: Operation>(&mut self, signed: &Signed<T>) {
+        match self.admin {
+            Some(admin) if SignerKey::Bls(admin) == *signed.signer() => {}
+            _ => panic!("Only the admin can perform this operation"),
         }
 
-        contract_account.balance -= transfer.value;
+        self.authorize(signed);
+    }
 
-        let to_account = self
-            .accounts
-            .entry(transfer.to)
-            .or_insert(AccountInfo::EMPTY);
-
-        to_account.balance += transfer.value;
-
-        rusk_abi::emit(
-            "transfer",
-            TransferEvent {
-                owner: contract,
-                spender: None,
-                to: transfer.to,
-                value: transfer.value,
-            },
-        );
+    /// Calls the acceptance function of the receiving account, if it is a contract, applying the
+    /// given policy if the call fails (panic or OoG). Returns whether the receiving account kept the
+    /// tokens.
+    ///
+    /// The default token is announced by `token_received`, and created tokens by
+    /// `multi_token_received`. Recovery buckets only hold the default token, so a created token is
+    /// returned by reverting the transfer where it would be recovered.
+    fn notify_received(
+        &mut self,
+        token: TokenId,
+        from: Account,
+        to: Account,
+        value: u64,
+        data: &[u8],
+        policy: HookPolicy,
+    ) -> bool {
+        let contract = match to {
+            Account::Contract(contract) => contract,
+            _ => return true,
+        };
+
+        let policy = match policy {
+            HookPolicy::Recover if token != DEFAULT_TOKEN_ID => HookPolicy::Revert,
+            policy => policy,
+        };
+
+        self.in_hook = true;
+        let result = if token == DEFAULT_TOKEN_ID {
+            let info = TransferInfo {
+                from,
+                value,
+                data: data.to_vec(),
+            };
+            rusk_abi::call::<_, ()>(contract, "token_received", &info)
+        } else {
+            let info = TokenTransferInfo {
+                token,
+                from,
+                value,
+                data: data.to_vec(),
+            };
+            rusk_abi::call::<_, ()>(contract, "multi_token_received", &info)
+        };
+        self.in_hook = false;
 
-        // if the transfer is to a contract, the acceptance function of said contract is called. if
-        // it fails (panic or OoG) the transfer also fails.
-        if let Account::Contract(to_contract) = transfer.to {
-            if let Err(err) = rusk_abi::call::<_, ()>(
-                to_contract,
-                "token_received",
-                &TransferInfo {
-                    from: contract,
-                    value: transfer.value,
+        if let Err(err) = result {
+            match policy {
+                HookPolicy::Revert => match err {
+                    ContractError::DoesNotExist => {
+                        panic!("The receiving contract doesn't exist")
+                    }
+                    ContractError::Unknown if token != DEFAULT_TOKEN_ID => {
+                        panic!("The receiving contract doesn't implement `multi_token_received`")
+                    }
+                    ContractError::Unknown => panic!(
+                        "The receiving contract doesn't implement `token_received`, use \
+                         `transfer_raw` to transfer to it regardless"
+                    ),
+                    err => {
+                        panic!("Failed calling `token_received` on the receiving contract: {err}")
+                    }
                 },
-            ) {
-                panic!("Failed calling `token_received` on the receiving contract: {err}");
+                HookPolicy::Credit => {}
+                HookPolicy::Recover => {
+                    self.debit(to, value);
+                    self.prune_account(to);
+
+                    *self.recoveries.entry(from).or_insert(0) += value;
+                }
             }
+
+            self.emit(
+                "hook_failed",
+                HookFailedEvent {
+                    from,
+                    to: contract,
+                    value,
+                    policy,
+                },
+            );
+
+            return policy != HookPolicy::Recover;
         }
+
+        true
     }
 
-    fn approve(&mut self, approve: Approve) {
-        let owner_key = *approve.owner();
-        let owner = Account::External(owner_key);
+    /// Moves tokens out of the recovery bucket of an account, and back into its balance.
+    fn claim_recovered(&mut self, owner: Account, value: u64) {
+        let recovered = self
+            .recoveries
+            .get_mut(&owner)
+            .expect("The account has no tokens to recover");
 
-        let owner_account = self.accounts.entry(owner).or_insert(AccountInfo::EMPTY);
-        if approve.nonce() != owner_account.nonce + 1 {
-            panic!("Nonces must be sequential");
+        if *recovered < value {
+            panic!("The account doesn't have enough tokens to recover");
         }
 
-        owner_account.nonce += 1;
-
-        let sig = *approve.signature();
-        let sig_msg = approve.signature_message().to_vec();
-        if !rusk_abi::verify_bls(sig_msg, owner_key, sig) {
-            panic!("Invalid signature");
+        *recovered -= value;
+        if *recovered == 0 {
+            self.recoveries.remove(&owner);
         }
 
-        let spender = *approve.spender();
+        self.credit(owner, value);
 
-        let allowances = self.allowances.entry(owner).or_insert(BTreeMap::new());
+        self.emit("recovery_claimed", RecoveryClaimedEvent { owner, value });
+    }
+}
 
-        let value = approve.value();
-        allowances.insert(spender, value);
+/// Checks that a payload was signed using a version of the message format the contract supports.
+fn check_message_version(version: u8) {
+    if version != MESSAGE_VERSION {
+        panic!("Unsupported message version");
+    }
+}
 
-        rusk_abi::emit(
-            "approve",
-            ApproveEvent {
-                owner,
-                spender,
-                value,
-            },
-        );
+/// Checks that the signature over the message is valid for the key, and made with its scheme.
+fn verify_signature(signer_key: SignerKey, sig: SchemeSignature, sig_msg: Vec<u8>) {
+    // BLS signatures are verified by the host, which is much cheaper than doing so in the
+    // contract, as is done for the other schemes
+    let valid = match (signer_key, sig) {
+        (SignerKey::Bls(pk), SchemeSignature::Bls(sig)) => rusk_abi::verify_bls(sig_msg, pk, sig),
+        (signer_key, sig) => signer_key.verify(&sig, &sig_msg),
+    };
+    if !valid {
+        panic!("Invalid signature");
     }
 }
 
@@ -293,32 +992,795 @@ unsafe fn total_supply(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |_: ()| STATE.total_supply())
 }
 
+#[no_mangle]
+unsafe fn features(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.features())
+}
+
+#[no_mangle]
+unsafe fn supports(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.supports(arg))
+}
+
+#[no_mangle]
+unsafe fn spec_version(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.spec_version())
+}
+
 #[no_mangle]
 unsafe fn account(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |arg| STATE.account(arg))
 }
 
+#[no_mangle]
+unsafe fn holder_count(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.holder_count())
+}
+
+#[no_mangle]
+unsafe fn exists(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.exists(arg))
+}
+
+#[no_mangle]
+unsafe fn nonce(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.nonce(arg))
+}
+
+#[no_mangle]
+unsafe fn nonce_used(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.nonce_used(arg))
+}
+
 #[no_mangle]
 unsafe fn allowance(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |arg| STATE.allowance(arg))
 }
 
+#[no_mangle]
+unsafe fn allowance_info(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.allowance_info(arg))
+}
+
 #[no_mangle]
 unsafe fn transfer(arg_len: u32) -> u32 {
-    rusk_abi::wrap_call(arg_len, |arg| STATE.transfer(arg))
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().transfer(arg))
 }
 
 #[no_mangle]
 unsafe fn transfer_from(arg_len: u32) -> u32 {
-    rusk_abi::wrap_call(arg_len, |arg| STATE.transfer_from(arg))
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().transfer_from(arg))
 }
 
 #[no_mangle]
 unsafe fn transfer_from_contract(arg_len: u32) -> u32 {
-    rusk_abi::wrap_call(arg_len, |arg| STATE.transfer_from_contract(arg))
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().transfer_from_contract(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn idempotency_key_used(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.idempotency_key_used(arg))
 }
 
 #[no_mangle]
 unsafe fn approve(arg_len: u32) -> u32 {
-    rusk_abi::wrap_call(arg_len, |arg| STATE.approve(arg))
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().approve(arg))
+}
+
+#[no_mangle]
+unsafe fn recoverable(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.recoverable(arg))
+}
+
+#[no_mangle]
+unsafe fn claim_recovery(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().claim_recovery(arg))
+}
+
+#[no_mangle]
+unsafe fn claim_recovery_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().claim_recovery_from_contract(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn snapshot(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().snapshot(arg))
+}
+
+#[no_mangle]
+unsafe fn snapshot_id(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.snapshot_id())
+}
+
+#[no_mangle]
+unsafe fn balance_of_at(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.balance_of_at(arg))
+}
+
+#[no_mangle]
+unsafe fn total_supply_at(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.total_supply_at(arg))
+}
+
+#[no_mangle]
+unsafe fn create_vesting(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().create_vesting(arg))
+}
+
+#[no_mangle]
+unsafe fn claim_vested(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().claim_vested(arg))
+}
+
+#[no_mangle]
+unsafe fn claim_vested_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| {
+        STATE.non_reentrant().claim_vested_from_contract()
+    })
+}
+
+#[no_mangle]
+unsafe fn vesting_of(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.vesting_of(arg))
+}
+
+#[no_mangle]
+unsafe fn transfer_locked(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().transfer_locked(arg))
+}
+
+#[no_mangle]
+unsafe fn claim_locked(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().claim_locked(arg))
+}
+
+#[no_mangle]
+unsafe fn claim_locked_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().claim_locked_from_contract(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn cancel_locked(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().cancel_locked(arg))
+}
+
+#[no_mangle]
+unsafe fn locked_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.locked_transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn register_multisig(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().register_multisig(arg))
+}
+
+#[no_mangle]
+unsafe fn multisig(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.multisig(arg))
+}
+
+#[no_mangle]
+unsafe fn multisig_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().multisig_transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn batch_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().batch_transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn batch_transfer_from(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().batch_transfer_from(arg)
+    })
+}
+
+#[cfg(feature = "fees")]
+#[no_mangle]
+unsafe fn relayed_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().relayed_transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn approve_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().approve_from_contract(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn register_token_sent_hook(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().register_token_sent_hook(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn token_sent_hook(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.token_sent_hook(arg))
+}
+
+#[no_mangle]
+unsafe fn approve_and_call(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().approve_and_call(arg))
+}
+
+#[no_mangle]
+unsafe fn authorize_operator(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().authorize_operator(arg))
+}
+
+#[no_mangle]
+unsafe fn revoke_operator(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().revoke_operator(arg))
+}
+
+#[no_mangle]
+unsafe fn is_operator(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.is_operator(arg))
+}
+
+#[no_mangle]
+unsafe fn operator_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().operator_transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn transfer_raw(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().transfer_raw(arg))
+}
+
+#[cfg(feature = "compliance")]
+#[no_mangle]
+unsafe fn add_to_whitelist(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().add_to_whitelist(arg))
+}
+
+#[cfg(feature = "compliance")]
+#[no_mangle]
+unsafe fn remove_from_whitelist(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().remove_from_whitelist(arg)
+    })
+}
+
+#[cfg(feature = "compliance")]
+#[no_mangle]
+unsafe fn is_whitelisted(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.is_whitelisted(arg))
+}
+
+#[cfg(feature = "fees")]
+#[no_mangle]
+unsafe fn burn_rate(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.burn_rate())
+}
+
+#[no_mangle]
+unsafe fn create_airdrop(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().create_airdrop(arg))
+}
+
+#[no_mangle]
+unsafe fn claim_airdrop(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().claim_airdrop(arg))
+}
+
+#[no_mangle]
+unsafe fn sweep_airdrop(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().sweep_airdrop(arg))
+}
+
+#[no_mangle]
+unsafe fn airdrop(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.airdrop(arg))
+}
+
+#[no_mangle]
+unsafe fn airdrop_claimed(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.airdrop_claimed(arg))
+}
+
+#[no_mangle]
+unsafe fn stake(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().stake(arg))
+}
+
+#[no_mangle]
+unsafe fn stake_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().stake_from_contract(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn unstake(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().unstake(arg))
+}
+
+#[no_mangle]
+unsafe fn unstake_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().unstake_from_contract(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn staked_of(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.staked_of(arg))
+}
+
+#[no_mangle]
+unsafe fn distribute(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().distribute(arg))
+}
+
+#[no_mangle]
+unsafe fn distribute_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().distribute_from_contract(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn claim_rewards(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().claim_rewards(arg))
+}
+
+#[no_mangle]
+unsafe fn claim_rewards_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| {
+        STATE.non_reentrant().claim_rewards_from_contract()
+    })
+}
+
+#[no_mangle]
+unsafe fn claimable(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.claimable(arg))
+}
+
+#[no_mangle]
+unsafe fn deposit(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().deposit(arg))
+}
+
+#[no_mangle]
+unsafe fn withdraw(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().withdraw(arg))
+}
+
+#[no_mangle]
+unsafe fn state_version(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.state_version())
+}
+
+#[no_mangle]
+unsafe fn state_chunk(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.state_chunk(arg))
+}
+
+#[no_mangle]
+unsafe fn migrate(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().migrate(arg))
+}
+
+#[no_mangle]
+unsafe fn abort_migration(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().abort_migration(arg))
+}
+
+#[no_mangle]
+unsafe fn cancel_nonce(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().cancel_nonce(arg))
+}
+
+#[no_mangle]
+unsafe fn permit(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().permit(arg))
+}
+
+#[no_mangle]
+unsafe fn permit_transfer_from(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().permit_transfer_from(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn permit_transfer_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().permit_transfer_from_contract(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn set_spending_limit(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().set_spending_limit(arg))
+}
+
+#[no_mangle]
+unsafe fn set_allowance_expiry(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().set_allowance_expiry(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn spending_budget(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.spending_budget(arg))
+}
+
+#[no_mangle]
+unsafe fn approve_if(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().approve_if(arg))
+}
+
+#[no_mangle]
+unsafe fn approve_if_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().approve_if_from_contract(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn prune_empty_accounts(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().prune_empty_accounts(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn events_since(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.events_since(arg))
+}
+
+#[no_mangle]
+unsafe fn audit(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.audit())
+}
+
+#[no_mangle]
+unsafe fn history(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.history(arg))
+}
+
+#[no_mangle]
+unsafe fn rescue(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().rescue(arg))
+}
+
+#[no_mangle]
+unsafe fn propose_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().propose_transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn claim_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().claim_transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn claim_transfer_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().claim_transfer_from_contract(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn cancel_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().cancel_transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn pending_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.pending_transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn two_phase_threshold(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.two_phase_threshold())
+}
+
+#[no_mangle]
+unsafe fn register_verifier(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().register_verifier(arg))
+}
+
+#[no_mangle]
+unsafe fn verifier(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.verifier(arg))
+}
+
+#[no_mangle]
+unsafe fn transfer_authorized(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().transfer_authorized(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn create_token(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().create_token(arg))
+}
+
+#[no_mangle]
+unsafe fn mint_token(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().mint_token(arg))
+}
+
+#[no_mangle]
+unsafe fn token_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().token_transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn token_transfer_from(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().token_transfer_from(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn token_transfer_from_contract(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().token_transfer_from_contract(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn token_burn(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().token_burn(arg))
+}
+
+#[no_mangle]
+unsafe fn token_approve(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().token_approve(arg))
+}
+
+#[no_mangle]
+unsafe fn token_info(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.token_info(arg))
+}
+
+#[no_mangle]
+unsafe fn token_balance(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.token_balance(arg))
+}
+
+#[no_mangle]
+unsafe fn token_allowance(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.token_allowance(arg))
+}
+
+#[no_mangle]
+unsafe fn flash_loan(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().flash_loan(arg))
+}
+
+#[no_mangle]
+unsafe fn flash_fee(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.flash_fee(arg))
+}
+
+#[no_mangle]
+unsafe fn announce_clawback(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().announce_clawback(arg))
+}
+
+#[no_mangle]
+unsafe fn execute_clawback(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().execute_clawback(arg))
+}
+
+#[no_mangle]
+unsafe fn cancel_clawback(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().cancel_clawback(arg))
+}
+
+#[no_mangle]
+unsafe fn clawback(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.clawback(arg))
+}
+
+#[no_mangle]
+unsafe fn set_rate_limits(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().set_rate_limits(arg))
+}
+
+#[no_mangle]
+unsafe fn trip_breaker(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().trip_breaker(arg))
+}
+
+#[no_mangle]
+unsafe fn reset_breaker(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().reset_breaker(arg))
+}
+
+#[no_mangle]
+unsafe fn breaker(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.breaker())
+}
+
+#[no_mangle]
+unsafe fn stake_native(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().stake_native(arg))
+}
+
+#[no_mangle]
+unsafe fn unstake_native(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().unstake_native(arg))
+}
+
+#[no_mangle]
+unsafe fn recv_unstake(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().recv_unstake(arg))
+}
+
+#[no_mangle]
+unsafe fn native_stake(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.native_stake(arg))
+}
+
+#[no_mangle]
+unsafe fn set_min_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().set_min_transfer(arg))
+}
+
+#[no_mangle]
+unsafe fn min_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.min_transfer())
+}
+
+#[no_mangle]
+unsafe fn poke_emission(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.non_reentrant().poke_emission())
+}
+
+#[no_mangle]
+unsafe fn emission(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.emission())
+}
+
+#[no_mangle]
+unsafe fn register_session_key(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().register_session_key(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn revoke_session_key(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().revoke_session_key(arg))
+}
+
+#[no_mangle]
+unsafe fn session_key(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.session_key(arg))
+}
+
+#[no_mangle]
+unsafe fn state_root(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.state_root())
+}
+
+#[no_mangle]
+unsafe fn proof_of(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.proof_of(arg))
+}
+
+#[no_mangle]
+unsafe fn pause(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().pause(arg))
+}
+
+#[no_mangle]
+unsafe fn unpause(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().unpause(arg))
+}
+
+#[no_mangle]
+unsafe fn paused(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.paused())
+}
+
+#[no_mangle]
+unsafe fn burn(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().burn(arg))
+}
+
+#[no_mangle]
+unsafe fn burn_from(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().burn_from(arg))
+}
+
+#[no_mangle]
+unsafe fn rotate_key(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().rotate_key(arg))
+}
+
+#[no_mangle]
+unsafe fn submission_status(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.submission_status(arg))
+}
+
+#[no_mangle]
+unsafe fn fund_treasury(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().fund_treasury(arg))
+}
+
+#[no_mangle]
+unsafe fn set_treasury(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().set_treasury(arg))
+}
+
+#[no_mangle]
+unsafe fn set_payee(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().set_payee(arg))
+}
+
+#[no_mangle]
+unsafe fn payout(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().payout(arg))
+}
+
+#[no_mangle]
+unsafe fn treasury(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.treasury())
+}
+
+#[no_mangle]
+unsafe fn payee(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.payee(arg))
+}
+
+#[no_mangle]
+unsafe fn settle_invoice(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().settle_invoice(arg))
+}
+
+#[no_mangle]
+unsafe fn invoice_settled(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.invoice_settled(arg))
+}
+
+#[no_mangle]
+unsafe fn sub_account_transfer(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().sub_account_transfer(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn sub_account_move(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().sub_account_move(arg))
+}
+
+#[no_mangle]
+unsafe fn open_stream(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().open_stream(arg))
+}
+
+#[no_mangle]
+unsafe fn withdraw_from_stream(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| {
+        STATE.non_reentrant().withdraw_from_stream(arg)
+    })
+}
+
+#[no_mangle]
+unsafe fn close_stream(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.non_reentrant().close_stream(arg))
+}
+
+#[no_mangle]
+unsafe fn stream(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.stream(arg))
 }
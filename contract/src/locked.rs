@@ -0,0 +1,109 @@
+//! Transfers held by the contract until a release height.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn transfer_locked(&mut self, transfer: Signed<TransferLocked>) -> u64 {
+        let from = self.authorize(&transfer);
+        let transfer = *transfer.op();
+
+        #[cfg(feature = "compliance")]
+        {
+            self.assert_whitelisted(from);
+            self.assert_whitelisted(transfer.to);
+        }
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+        self.assert_min_transfer(from, transfer.value);
+        self.debit_limited(from, transfer.value);
+
+        let id = self.next_lock_id;
+        self.next_lock_id += 1;
+
+        self.locked_transfers.insert(
+            id,
+            LockedTransfer {
+                from,
+                to: transfer.to,
+                value: transfer.value,
+                release_height: transfer.release_height,
+            },
+        );
+
+        self.emit(
+            "transfer_locked",
+            TransferLockedEvent {
+                id,
+                from,
+                to: transfer.to,
+                value: transfer.value,
+                release_height: transfer.release_height,
+            },
+        );
+
+        id
+    }
+
+    pub(crate) fn claim_locked(&mut self, claim: Signed<ClaimLocked>) {
+        let to = self.authorize(&claim);
+        self.claim_locked_by(to, claim.op().id);
+    }
+
+    pub(crate) fn claim_locked_from_contract(&mut self, claim: ClaimLocked) {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        self.claim_locked_by(Account::Contract(contract), claim.id);
+    }
+
+    pub(crate) fn cancel_locked(&mut self, cancel: Signed<CancelLocked>) {
+        let from = self.authorize(&cancel);
+        let id = cancel.op().id;
+
+        let locked = self.remove_locked(id);
+        if locked.from != from {
+            panic!("Only the sender can cancel a locked transfer");
+        }
+
+        if rusk_abi::block_height() >= locked.release_height {
+            panic!("The locked transfer has already been released");
+        }
+
+        self.settle_locked(id, from, locked.value);
+    }
+
+    pub(crate) fn locked_transfer(&self, id: u64) -> Option<LockedTransfer> {
+        self.locked_transfers.get(&id).copied()
+    }
+
+    fn claim_locked_by(&mut self, to: Account, id: u64) {
+        let locked = self.remove_locked(id);
+        if locked.to != to {
+            panic!("Only the recipient can claim a locked transfer");
+        }
+
+        if rusk_abi::block_height() < locked.release_height {
+            panic!("The locked transfer has not been released yet");
+        }
+
+        self.settle_locked(id, to, locked.value);
+    }
+
+    fn remove_locked(&mut self, id: u64) -> LockedTransfer {
+        self.locked_transfers
+            .remove(&id)
+            .expect("The locked transfer doesn't exist")
+    }
+
+    fn settle_locked(&mut self, id: u64, to: Account, value: u64) {
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(to);
+
+        self.credit(to, value);
+
+        self.emit(
+            "locked_transfer_settled",
+            LockedTransferSettledEvent { id, to, value },
+        );
+    }
+}
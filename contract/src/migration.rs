@@ -0,0 +1,96 @@
+//! Versioning of the state, and migration of the state of a previous deployment.
+//!
+//! The state is exported by serializing it in its entirety, and split into chunks to fit in the
+//! argument buffer. Only the same variant of the contract can import the state it exported, since
+//! the fields gated by features are part of its layout.
+
+use alloc::vec::Vec;
+use core::mem;
+
+use rkyv::AlignedVec;
+use ttoken_types::*;
+
+use crate::TokenState;
+
+/// The version of the layout of the state. Must be bumped on any change to [`TokenState`], with
+/// [`TokenState::migrate`] learning how to transform the previous version.
+pub(crate) const STATE_VERSION: u32 = 1;
+
+impl TokenState {
+    pub(crate) fn state_version(&self) -> u32 {
+        STATE_VERSION
+    }
+
+    pub(crate) fn state_chunk(&self, index: u32) -> Vec<u8> {
+        let bytes = rkyv::to_bytes::<_, 4096>(self).expect("Serializing the state should succeed");
+
+        bytes
+            .chunks(STATE_CHUNK_SIZE)
+            .nth(index as usize)
+            .map(Vec::from)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn migrate(&mut self, migrate: Signed<Migrate>) {
+        self.authorize_admin(&migrate);
+        let migrate = migrate.op();
+
+        if self.migrated {
+            panic!("The state has already been migrated");
+        }
+        if migrate.index != self.migration_chunks {
+            panic!("The chunk is out of order");
+        }
+
+        self.migration_buffer.extend_from_slice(&migrate.chunk);
+        self.migration_chunks += 1;
+
+        if !migrate.last {
+            return;
+        }
+
+        let mut bytes = AlignedVec::with_capacity(self.migration_buffer.len());
+        bytes.extend_from_slice(&self.migration_buffer);
+
+        // the nonces used in this deployment, including those of the chunks just imported, must
+        // survive the import, or the operations signed with them could be replayed
+        let nonces = mem::take(&mut self.nonces);
+
+        match migrate.version {
+            STATE_VERSION => {
+                *self = rkyv::from_bytes(&bytes).expect("The state should be valid");
+            }
+            _ => panic!("The state version is not supported"),
+        }
+
+        for (account, words) in nonces {
+            let imported = self.nonces.entry(account).or_default();
+            for (index, word) in words {
+                let imported = imported.entry(index).or_insert([0; 4]);
+                for (imported, bits) in imported.iter_mut().zip(word) {
+                    *imported |= bits;
+                }
+            }
+        }
+        self.migrated = true;
+
+        self.emit(
+            "migrated",
+            MigratedEvent {
+                from_version: migrate.version,
+                to_version: STATE_VERSION,
+            },
+        );
+    }
+
+    pub(crate) fn abort_migration(&mut self, abort: Signed<AbortMigration>) {
+        self.authorize_admin(&abort);
+
+        if self.migrated {
+            panic!("The state has already been migrated");
+        }
+
+        self.migration_buffer = Vec::new();
+        self.migration_chunks = 0;
+    }
+}
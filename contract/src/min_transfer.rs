@@ -0,0 +1,37 @@
+//! A minimum value of transfers, keeping dust out of the state and the event log.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn set_min_transfer(&mut self, set: Signed<SetMinTransfer>) {
+        self.authorize_admin(&set);
+        let min_transfer = set.op().min_transfer;
+
+        self.min_transfer = min_transfer;
+
+        self.emit("min_transfer", MinTransferEvent { min_transfer });
+    }
+
+    pub(crate) fn min_transfer(&self) -> u64 {
+        self.min_transfer
+    }
+
+    /// Rejects a transfer of less than the minimum value, unless it empties the sending account.
+    pub(crate) fn assert_min_transfer(&self, owner: Account, value: u64) {
+        self.assert_min_token_transfer(DEFAULT_TOKEN_ID, owner, value);
+    }
+
+    /// Rejects a transfer of less than the minimum value of the given token, unless it empties the
+    /// sending account. The same minimum applies to every token.
+    pub(crate) fn assert_min_token_transfer(&self, token: TokenId, owner: Account, value: u64) {
+        let balance = self.token_balance(TokenBalance {
+            token,
+            account: owner,
+        });
+        if value < self.min_transfer && value != balance {
+            panic!("The value is below the minimum transfer");
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Further tokens issued by the same deployment, each keyed by a token id.
+//!
+//! The default token id refers to the token the deployment was initialized with, so operations on
+//! it act on the same balances and allowances as the single-token functions. Other tokens are
+//! created by the admin and kept in separate ledgers, but are moved through the same checks, rate
+//! limits, history, and receiver hooks.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn create_token(&mut self, create: Signed<CreateToken>) {
+        self.authorize_admin(&create);
+        let create = create.op().clone();
+
+        if create.id == DEFAULT_TOKEN_ID || self.tokens.contains_key(&create.id) {
+            panic!("The token id is already in use");
+        }
+
+        self.tokens.insert(
+            create.id,
+            TokenInfo {
+                metadata: create.metadata.clone(),
+                cap: create.cap,
+                supply: 0,
+            },
+        );
+
+        self.emit(
+            "token_created",
+            TokenCreatedEvent {
+                id: create.id,
+                metadata: create.metadata,
+                cap: create.cap,
+            },
+        );
+    }
+
+    pub(crate) fn mint_token(&mut self, mint: Signed<MintToken>) {
+        self.authorize_admin(&mint);
+        let mint = *mint.op();
+
+        if mint.token == DEFAULT_TOKEN_ID {
+            panic!("The default token can't be minted");
+        }
+
+        self.assert_not_paused(PauseScope::MINT);
+
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(mint.to);
+
+        let info = self.token_info_mut(mint.token);
+        let supply = info
+            .supply
+            .checked_add(mint.value)
+            .expect("The supply can't overflow");
+        if matches!(info.cap, Some(cap) if supply > cap) {
+            panic!("Minting would exceed the cap of the token");
+        }
+        info.supply = supply;
+
+        self.credit_token(mint.token, mint.to, mint.value);
+
+        self.emit(
+            TOKEN_MINTED_TOPIC,
+            TokenMintedEvent {
+                token: mint.token,
+                to: mint.to,
+                value: mint.value,
+            },
+        );
+    }
+
+    pub(crate) fn token_transfer(&mut self, transfer: Signed<TokenTransfer>) {
+        let from = self.authorize(&transfer);
+        let transfer = *transfer.op();
+
+        let hook_policy = self.hook_policy;
+        self.move_tokens_of(
+            transfer.token,
+            from,
+            None,
+            transfer.to,
+            transfer.value,
+            &[],
+            None,
+            hook_policy,
+        );
+    }
+
+    pub(crate) fn token_transfer_from(&mut self, transfer: Signed<TokenTransferFrom>) {
+        let spender = self.authorize(&transfer);
+        let transfer = *transfer.op();
+
+        self.spend_token_allowance(transfer.token, transfer.owner, spender, transfer.value);
+
+        let hook_policy = self.hook_policy;
+        self.move_tokens_of(
+            transfer.token,
+            transfer.owner,
+            Some(spender),
+            transfer.to,
+            transfer.value,
+            &[],
+            None,
+            hook_policy,
+        );
+    }
+
+    pub(crate) fn token_transfer_from_contract(&mut self, transfer: TokenTransferFromContract) {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        let contract = Account::Contract(contract);
+
+        // if transferring from another account, the contract must be allowed to spend its tokens
+        let owner = transfer.from.unwrap_or(contract);
+        let spender = if owner == contract {
+            None
+        } else {
+            self.spend_token_allowance(transfer.token, owner, contract, transfer.value);
+            Some(contract)
+        };
+
+        let hook_policy = transfer.hook_policy.unwrap_or(self.hook_policy);
+        self.move_tokens_of(
+            transfer.token,
+            owner,
+            spender,
+            transfer.to,
+            transfer.value,
+            &transfer.data,
+            None,
+            hook_policy,
+        );
+    }
+
+    pub(crate) fn token_burn(&mut self, burn: Signed<TokenBurn>) {
+        let owner = self.authorize(&burn);
+        let burn = *burn.op();
+
+        if burn.token == DEFAULT_TOKEN_ID {
+            self.burn_balance(owner, None, burn.value);
+            return;
+        }
+
+        self.debit_token_limited(burn.token, owner, burn.value);
+        self.token_info_mut(burn.token).supply -= burn.value;
+
+        self.emit(
+            "token_burn",
+            TokenBurnEvent {
+                token: burn.token,
+                owner,
+                value: burn.value,
+            },
+        );
+    }
+
+    pub(crate) fn token_approve(&mut self, approve: Signed<TokenApprove>) {
+        let owner = self.authorize(&approve);
+        let approve = *approve.op();
+
+        if approve.token == DEFAULT_TOKEN_ID {
+            self.set_allowance(owner, approve.spender, approve.value);
+            return;
+        }
+
+        // only checked for existence, so approvals of unknown tokens are rejected
+        self.token_info_mut(approve.token);
+
+        if approve.value > 0 {
+            self.assert_not_paused(PauseScope::APPROVALS);
+        }
+
+        self.token_allowances
+            .entry(approve.token)
+            .or_default()
+            .entry(owner)
+            .or_default()
+            .insert(approve.spender, approve.value);
+
+        self.emit(
+            "token_approve",
+            TokenApproveEvent {
+                token: approve.token,
+                owner,
+                spender: approve.spender,
+                value: approve.value,
+            },
+        );
+    }
+
+    pub(crate) fn token_info(&self, token: TokenId) -> Option<TokenInfo> {
+        if token == DEFAULT_TOKEN_ID {
+            return Some(TokenInfo {
+                metadata: TokenMetadata {
+                    name: self.name(),
+                    symbol: self.symbol(),
+                    decimals: self.decimals(),
+                },
+                cap: None,
+                supply: self.supply,
+            });
+        }
+
+        self.tokens.get(&token).cloned()
+    }
+
+    pub(crate) fn token_balance(&self, balance: TokenBalance) -> u64 {
+        if balance.token == DEFAULT_TOKEN_ID {
+            return self.account(balance.account).balance;
+        }
+
+        self.token_balances
+            .get(&balance.token)
+            .and_then(|balances| balances.get(&balance.account))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn token_allowance(&self, allowance: TokenAllowance) -> u64 {
+        if allowance.token == DEFAULT_TOKEN_ID {
+            return self.allowance(Allowance {
+                owner: allowance.owner,
+                spender: allowance.spender,
+            });
+        }
+
+        self.token_allowances
+            .get(&allowance.token)
+            .and_then(|allowances| allowances.get(&allowance.owner))
+            .and_then(|allowances| allowances.get(&allowance.spender))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Adds tokens of the given token to the balance of an account.
+    pub(crate) fn credit_token(&mut self, token: TokenId, account: Account, value: u64) {
+        if token == DEFAULT_TOKEN_ID {
+            self.credit(account, value);
+            return;
+        }
+
+        if value > 0 {
+            *self
+                .token_balances
+                .entry(token)
+                .or_default()
+                .entry(account)
+                .or_insert(0) += value;
+        }
+    }
+
+    /// Removes tokens of the given token from the balance of an account, dropping the balance once
+    /// it is empty.
+    pub(crate) fn debit_token(&mut self, token: TokenId, account: Account, value: u64) {
+        if token == DEFAULT_TOKEN_ID {
+            self.debit(account, value);
+            return;
+        }
+
+        let balances = self
+            .token_balances
+            .get_mut(&token)
+            .expect("The account has no tokens");
+
+        let balance = balances
+            .get_mut(&account)
+            .expect("The account has no tokens");
+        if *balance < value {
+            panic!("The account doesn't have enough tokens");
+        }
+        *balance -= value;
+        if *balance == 0 {
+            balances.remove(&account);
+        }
+    }
+
+    /// Deducts the given value from the allowance a spender has of the owner's tokens of the given
+    /// token.
+    fn spend_token_allowance(
+        &mut self,
+        token: TokenId,
+        owner: Account,
+        spender: Account,
+        value: u64,
+    ) {
+        if token == DEFAULT_TOKEN_ID {
+            self.spend_allowance(owner, spender, value);
+            return;
+        }
+
+        let allowance = self
+            .token_allowances
+            .get_mut(&token)
+            .and_then(|allowances| allowances.get_mut(&owner))
+            .and_then(|allowances| allowances.get_mut(&spender))
+            .expect("The spender is not allowed to use the account");
+
+        if value > *allowance {
+            panic!("The spender can't spent the defined amount");
+        }
+        *allowance -= value;
+    }
+
+    fn token_info_mut(&mut self, token: TokenId) -> &mut TokenInfo {
+        self.tokens
+            .get_mut(&token)
+            .expect("The token doesn't exist")
+    }
+}
@@ -0,0 +1,91 @@
+//! Accounts controlled by a threshold of keys.
+
+use alloc::vec::Vec;
+
+use ttoken_types::*;
+
+use crate::{check_message_version, TokenState};
+
+impl TokenState {
+    pub(crate) fn register_multisig(&mut self, multisig: Multisig) -> u64 {
+        if multisig.threshold == 0 || multisig.threshold as usize > multisig.keys.len() {
+            panic!("The threshold must be between one and the number of keys");
+        }
+
+        // signers are told apart by their index, so a repeated key could sign more than once
+        for (i, key) in multisig.keys.iter().enumerate() {
+            if multisig.keys[..i].contains(key) {
+                panic!("The keys must be distinct");
+            }
+        }
+
+        let id = self.next_multisig_id;
+        self.next_multisig_id += 1;
+
+        self.emit(
+            "multisig_registered",
+            MultisigRegisteredEvent {
+                id,
+                keys: multisig.keys.clone(),
+                threshold: multisig.threshold,
+            },
+        );
+
+        self.multisigs.insert(id, multisig);
+
+        id
+    }
+
+    pub(crate) fn multisig(&self, id: u64) -> Option<Multisig> {
+        self.multisigs.get(&id).cloned()
+    }
+
+    pub(crate) fn multisig_transfer(&mut self, transfer: MultisigTransfer) {
+        let id = transfer.multisig();
+        let from = Account::Multisig(id);
+
+        let multisig = self
+            .multisigs
+            .get(&id)
+            .expect("The multisig account doesn't exist");
+
+        check_message_version(transfer.version());
+
+        let sig_msg = transfer.signature_message();
+
+        let mut signers = Vec::with_capacity(transfer.signatures().len());
+        for (index, sig) in transfer.signatures() {
+            if signers.contains(index) {
+                panic!("Each key can only sign once");
+            }
+
+            let key = *multisig
+                .keys
+                .get(*index as usize)
+                .expect("The key index is out of bounds");
+
+            if !rusk_abi::verify_bls(sig_msg.clone(), key, *sig) {
+                panic!("Invalid signature");
+            }
+
+            signers.push(*index);
+        }
+
+        if signers.len() < multisig.threshold as usize {
+            panic!("Not enough keys signed the transfer");
+        }
+
+        self.use_nonce(from, transfer.nonce());
+
+        let hook_policy = self.hook_policy;
+        self.move_tokens(
+            from,
+            None,
+            *transfer.to(),
+            transfer.value(),
+            &[],
+            None,
+            hook_policy,
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Staking native Dusk backing wrapped tokens with the Dusk stake contract.
+//!
+//! The Dusk is staked using the stake contract's functions for stakes owned by a contract, so only
+//! the token contract can unstake it. Tokens are burned while their Dusk is staked, and minted again
+//! as the stake contract returns it.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use execution_core::stake::{WithdrawToContract, STAKE_CONTRACT};
+use execution_core::transfer::{ContractToContract, ReceiveFromContract, TRANSFER_CONTRACT};
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn stake_native(&mut self, stake: Signed<StakeNative>) {
+        self.assert_wrapped();
+
+        let account = self.authorize(&stake);
+        let stake = stake.op();
+        let value = stake.value;
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+        self.debit_limited(account, value);
+        self.prune_account(account);
+
+        self.checkpoint_supply();
+        self.supply -= value;
+
+        let info = self
+            .native_stakes
+            .entry(account)
+            .or_insert(NativeStakeInfo {
+                stake_key: stake.stake_key,
+                staked: 0,
+                pending_unstake: 0,
+            });
+        if info.stake_key != stake.stake_key {
+            panic!("The account already stakes under another key");
+        }
+        info.staked += value;
+
+        if let Err(err) = rusk_abi::call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "contract_to_contract",
+            &ContractToContract {
+                contract: STAKE_CONTRACT,
+                value,
+                fn_name: String::from("stake_from_contract"),
+                data: stake.stake_args.clone(),
+            },
+        ) {
+            panic!("Failed staking the Dusk: {err}");
+        }
+
+        self.emit(
+            "native_staked",
+            NativeStakedEvent {
+                account,
+                stake_key: stake.stake_key,
+                value,
+            },
+        );
+    }
+
+    pub(crate) fn unstake_native(&mut self, unstake: Signed<UnstakeNative>) {
+        self.assert_wrapped();
+
+        let account = self.authorize(&unstake);
+        let value = unstake.op().value;
+
+        let info = self
+            .native_stakes
+            .get_mut(&account)
+            .expect("The account has no native stake");
+        if value > info.staked {
+            panic!("The account doesn't have enough staked");
+        }
+        info.staked -= value;
+        info.pending_unstake += value;
+
+        let stake_key = info.stake_key;
+        let pending = info.pending_unstake;
+
+        // the stake contract returns the Dusk by calling `recv_unstake`, which credits it to the
+        // account being unstaked for
+        self.unstaking = Some(account);
+        let result = rusk_abi::call::<_, ()>(
+            STAKE_CONTRACT,
+            "unstake_from_contract",
+            &WithdrawToContract {
+                account: stake_key,
+                value,
+                fn_name: String::from("recv_unstake"),
+                data: Vec::new(),
+            },
+        );
+        self.unstaking = None;
+
+        if let Err(err) = result {
+            panic!("Failed unstaking the Dusk: {err}");
+        }
+
+        let info = self.native_stakes[&account];
+        let returned = pending - info.pending_unstake;
+        if info.staked == 0 && info.pending_unstake == 0 {
+            self.native_stakes.remove(&account);
+        }
+
+        self.emit(
+            "native_unstaked",
+            NativeUnstakedEvent {
+                account,
+                value,
+                returned,
+            },
+        );
+    }
+
+    pub(crate) fn recv_unstake(&mut self, recv: ReceiveFromContract) {
+        if rusk_abi::caller() != Some(TRANSFER_CONTRACT) || recv.contract != STAKE_CONTRACT {
+            panic!("Only Dusk unstaked from the stake contract can be received");
+        }
+
+        let account = self.unstaking.expect("No native stake is being unstaked");
+        let value = recv.value;
+
+        if let Some(info) = self.native_stakes.get_mut(&account) {
+            info.pending_unstake = info.pending_unstake.saturating_sub(value);
+        }
+
+        self.checkpoint_supply();
+        self.supply += value;
+
+        self.credit(account, value);
+    }
+
+    pub(crate) fn native_stake(&self, account: Account) -> Option<NativeStakeInfo> {
+        self.native_stakes.get(&account).copied()
+    }
+}
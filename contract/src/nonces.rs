@@ -0,0 +1,89 @@
+//! Unordered nonces, kept as a bitmap of the nonces used by each account.
+//!
+//! Each word of the bitmap covers 256 nonces, with only words with nonces used being stored. This
+//! allows operations signed by the same account to be executed in any order, while still preventing
+//! any of them from being replayed.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+/// The number of nonces covered by a word of the bitmap.
+const WORD_BITS: u64 = 256;
+
+/// A word of the bitmap of used nonces.
+pub(crate) type NonceWord = [u64; 4];
+
+impl TokenState {
+    /// The lowest nonce the account hasn't used yet.
+    pub(crate) fn nonce(&self, account: Account) -> u64 {
+        let words = match self.nonces.get(&account) {
+            Some(words) => words,
+            None => return 0,
+        };
+
+        let mut expected = 0;
+        for (index, word) in words {
+            if *index != expected {
+                break;
+            }
+
+            for (i, bits) in word.iter().enumerate() {
+                if *bits != u64::MAX {
+                    return index * WORD_BITS + i as u64 * 64 + bits.trailing_ones() as u64;
+                }
+            }
+
+            expected += 1;
+        }
+
+        expected * WORD_BITS
+    }
+
+    pub(crate) fn nonce_used(&self, used: NonceUsed) -> bool {
+        let (index, i, mask) = position(used.nonce);
+
+        self.nonces
+            .get(&used.account)
+            .and_then(|words| words.get(&index))
+            .map(|word| word[i] & mask != 0)
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn cancel_nonce(&mut self, cancel: Signed<CancelNonce>) {
+        let account = self.authorize(&cancel);
+        let nonce = cancel.nonce();
+
+        self.emit("nonce_cancelled", NonceCancelledEvent { account, nonce });
+    }
+
+    /// Checks that the given nonce hasn't been used by the account, and uses it.
+    pub(crate) fn use_nonce(&mut self, account: Account, nonce: u64) {
+        let (index, i, mask) = position(nonce);
+
+        let word = self
+            .nonces
+            .entry(account)
+            .or_default()
+            .entry(index)
+            .or_insert([0; 4]);
+
+        if word[i] & mask != 0 {
+            panic!("The nonce has already been used");
+        }
+        word[i] |= mask;
+
+        let info = self.accounts.entry(account).or_insert(AccountInfo::EMPTY);
+        info.nonce += 1;
+
+        self.update_state_leaf(account);
+    }
+}
+
+/// The index of the word, the index of the limb in the word, and the mask of the bit of a nonce.
+fn position(nonce: u64) -> (u64, usize, u64) {
+    let index = nonce / WORD_BITS;
+    let bit = nonce % WORD_BITS;
+
+    (index, (bit / 64) as usize, 1 << (bit % 64))
+}
@@ -0,0 +1,83 @@
+//! Operators authorized to move any amount of tokens from an account.
+
+use alloc::collections::BTreeSet;
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn authorize_operator(&mut self, authorize: Signed<AuthorizeOperator>) {
+        let owner = self.authorize(&authorize);
+        let operator = authorize.op().operator;
+
+        if owner == operator {
+            panic!("The account can't be its own operator");
+        }
+
+        self.operators
+            .entry(owner)
+            .or_insert(BTreeSet::new())
+            .insert(operator);
+
+        self.emit(
+            "operator",
+            OperatorEvent {
+                owner,
+                operator,
+                authorized: true,
+            },
+        );
+    }
+
+    pub(crate) fn revoke_operator(&mut self, revoke: Signed<RevokeOperator>) {
+        let owner = self.authorize(&revoke);
+        let operator = revoke.op().operator;
+
+        if let Some(operators) = self.operators.get_mut(&owner) {
+            operators.remove(&operator);
+            if operators.is_empty() {
+                self.operators.remove(&owner);
+            }
+        }
+
+        self.emit(
+            "operator",
+            OperatorEvent {
+                owner,
+                operator,
+                authorized: false,
+            },
+        );
+    }
+
+    pub(crate) fn is_operator(&self, query: IsOperator) -> bool {
+        self.operators
+            .get(&query.owner)
+            .map(|operators| operators.contains(&query.operator))
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn operator_transfer(&mut self, transfer: Signed<OperatorTransfer>) {
+        let operator = self.authorize(&transfer);
+        let transfer = *transfer.op();
+
+        if !self.is_operator(IsOperator {
+            owner: transfer.from,
+            operator,
+        }) {
+            panic!("The signer is not an operator of the account");
+        }
+
+        let hook_policy = self.hook_policy;
+        self.move_tokens(
+            transfer.from,
+            Some(operator),
+            transfer.to,
+            transfer.value,
+            &[],
+            None,
+            hook_policy,
+        );
+    }
+}
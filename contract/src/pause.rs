@@ -0,0 +1,61 @@
+//! Pausing transfers, approvals, and minting separately, each stopping its own operations only.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn pause(&mut self, pause: Signed<Pause>) {
+        self.authorize_admin(&pause);
+        self.set_paused(pause.op().scope, true);
+    }
+
+    pub(crate) fn unpause(&mut self, unpause: Signed<Unpause>) {
+        self.authorize_admin(&unpause);
+        self.set_paused(unpause.op().scope, false);
+    }
+
+    pub(crate) fn paused(&self) -> PauseScope {
+        self.paused
+    }
+
+    /// Rejects an operation in the given scope if it is paused.
+    pub(crate) fn assert_not_paused(&self, scope: PauseScope) {
+        if !self.paused.contains(scope) {
+            return;
+        }
+
+        match scope {
+            PauseScope::TRANSFERS => panic!("Transfers are paused"),
+            PauseScope::APPROVALS => panic!("Approvals are paused"),
+            PauseScope::MINT => panic!("Minting is paused"),
+            _ => panic!("The operation is paused"),
+        }
+    }
+
+    /// Pauses or resumes the operations in a scope, emitting an event for each that changed.
+    fn set_paused(&mut self, scope: PauseScope, paused: bool) {
+        if PauseScope::from_bits(scope.bits()).is_none() {
+            panic!("Unknown pause scope");
+        }
+
+        for single in PauseScope::SCOPES {
+            if !scope.contains(single) || self.paused.contains(single) == paused {
+                continue;
+            }
+
+            self.paused = match paused {
+                true => self.paused | single,
+                false => self.paused.difference(single),
+            };
+
+            self.emit(
+                PAUSE_TOPIC,
+                PauseEvent {
+                    scope: single,
+                    paused,
+                },
+            );
+        }
+    }
+}
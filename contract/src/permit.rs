@@ -0,0 +1,55 @@
+//! Approvals signed off-chain, submitted together with a transfer by the spender.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn permit(&mut self, permit: Signed<Permit>) {
+        self.use_permit(&permit);
+    }
+
+    pub(crate) fn permit_transfer_from(&mut self, transfer: Signed<PermitTransferFrom>) {
+        let spender = self.authorize(&transfer);
+        self.permit_transfer_from_by(spender, *transfer.op());
+    }
+
+    pub(crate) fn permit_transfer_from_contract(&mut self, transfer: PermitTransferFrom) {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        self.permit_transfer_from_by(Account::Contract(contract), transfer);
+    }
+
+    fn permit_transfer_from_by(&mut self, spender: Account, transfer: PermitTransferFrom) {
+        if transfer.permit.op().spender != spender {
+            panic!("The permit is not for the spender");
+        }
+
+        let owner = self.use_permit(&transfer.permit);
+
+        let hook_policy = self.hook_policy;
+        self.spend_allowance(owner, spender, transfer.value);
+        self.move_tokens(
+            owner,
+            Some(spender),
+            transfer.to,
+            transfer.value,
+            &[],
+            None,
+            hook_policy,
+        );
+    }
+
+    /// Checks a permit and sets the allowance it grants, returning the owner's account.
+    fn use_permit(&mut self, permit: &Signed<Permit>) -> Account {
+        let owner = self.authorize(permit);
+        let op = permit.op();
+
+        if rusk_abi::block_height() > op.deadline {
+            panic!("The permit has expired");
+        }
+
+        self.set_allowance(owner, op.spender, op.value);
+
+        owner
+    }
+}
@@ -0,0 +1,42 @@
+//! Removing the entries of empty accounts, reclaiming their storage.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn prune_empty_accounts(&mut self, prune: Signed<PruneEmptyAccounts>) -> u32 {
+        self.authorize_admin(&prune);
+
+        let count = prune
+            .op()
+            .accounts
+            .iter()
+            .filter(|account| self.prune_account(**account))
+            .count() as u32;
+
+        self.emit("accounts_pruned", AccountsPrunedEvent { count });
+
+        count
+    }
+
+    /// Removes the entry of the account if it holds no tokens, returning whether it was removed.
+    ///
+    /// The nonces the account used are kept apart from its entry, so operations it signed still
+    /// can't be replayed, while the number of nonces in its entry starts over from zero.
+    pub(crate) fn prune_account(&mut self, account: Account) -> bool {
+        match self.accounts.get(&account) {
+            Some(info) if info.balance == 0 => {
+                let nonce = info.nonce;
+                self.accounts.remove(&account);
+
+                // the leaf of an entry with no nonces used already holds empty data
+                if nonce > 0 {
+                    self.update_state_leaf(account);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! Transfers skipping the receiving contract's hook.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn transfer_raw(&mut self, transfer: Signed<TransferRaw>) {
+        let from = self.authorize(&transfer);
+        let transfer = *transfer.op();
+
+        self.move_balance(
+            DEFAULT_TOKEN_ID,
+            from,
+            None,
+            transfer.to,
+            transfer.value,
+            None,
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! Transfers submitted by a relayer, paid for with a fee in tokens.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn relayed_transfer(&mut self, transfer: Signed<RelayedTransfer>) {
+        let from = self.authorize(&transfer);
+        let transfer = *transfer.op();
+
+        let total = transfer
+            .value
+            .checked_add(transfer.fee)
+            .expect("The value and fee can't overflow");
+
+        // the fee leaves the account along with the value, so both are held to the thresholds at
+        // once, while the rate limits count each as it is debited
+        self.assert_below_two_phase_threshold(total);
+        self.assert_below_breaker_threshold(total);
+
+        let hook_policy = self.hook_policy;
+        self.move_tokens(
+            from,
+            None,
+            transfer.to,
+            transfer.value,
+            &[],
+            None,
+            hook_policy,
+        );
+
+        if transfer.fee > 0 {
+            self.pay_fee(from, transfer.relayer, transfer.fee);
+        }
+    }
+
+    /// Pays the fee to the relayer in full, regardless of the minimum transfer or burn rate, and
+    /// without calling its hook.
+    fn pay_fee(&mut self, from: Account, relayer: Account, fee: u64) {
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(relayer);
+
+        self.debit_limited(from, fee);
+        self.credit(relayer, fee);
+
+        self.prune_account(from);
+        self.prune_account(relayer);
+
+        self.record_transfer(TransferRecord {
+            token: DEFAULT_TOKEN_ID,
+            owner: from,
+            spender: None,
+            to: relayer,
+            value: fee,
+            block_height: rusk_abi::block_height(),
+        });
+
+        self.emit(
+            TRANSFER_TOPIC,
+            TransferEvent {
+                owner: from,
+                spender: None,
+                to: relayer,
+                value: fee,
+                burned: 0,
+                memo: None,
+            },
+        );
+    }
+}
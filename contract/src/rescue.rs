@@ -0,0 +1,86 @@
+//! Rescuing tokens held by the token contract itself, which would otherwise be stuck.
+
+use alloc::vec::Vec;
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn rescue(&mut self, rescue: Signed<Rescue>) {
+        self.authorize_admin(&rescue);
+
+        let rescue = *rescue.op();
+        let this_contract = rusk_abi::self_id();
+        let token = rescue.token.unwrap_or(this_contract);
+
+        if token == this_contract {
+            let owner = Account::Contract(this_contract);
+            let to = rescue.to;
+            let value = rescue.value;
+
+            // the contract can't sign a proposed transfer, so the balances are moved directly,
+            // regardless of the two-phase threshold, minimum transfer, rate limits or burn rate
+            #[cfg(feature = "compliance")]
+            self.assert_whitelisted(to);
+
+            self.debit(owner, value);
+            self.credit(to, value);
+
+            self.prune_account(owner);
+            self.prune_account(to);
+
+            self.record_transfer(TransferRecord {
+                token: DEFAULT_TOKEN_ID,
+                owner,
+                spender: None,
+                to,
+                value,
+                block_height: rusk_abi::block_height(),
+            });
+
+            self.emit(
+                TRANSFER_TOPIC,
+                TransferEvent {
+                    owner,
+                    spender: None,
+                    to,
+                    value,
+                    burned: 0,
+                    memo: None,
+                },
+            );
+
+            let hook_policy = self.hook_policy;
+            self.notify_received(DEFAULT_TOKEN_ID, owner, to, value, &[], hook_policy);
+        } else {
+            let transfer = TransferFromContract {
+                to: rescue.to,
+                from: None,
+                value: rescue.value,
+                hook_policy: None,
+                idempotency_key: None,
+                data: Vec::new(),
+            };
+
+            match rusk_abi::call::<_, Result<(), TransferFromContractError>>(
+                token,
+                "transfer_from_contract",
+                &transfer,
+            ) {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => panic!("Failed rescuing tokens: {err:?}"),
+                Err(err) => panic!("Failed rescuing tokens: {err}"),
+            }
+        }
+
+        self.emit(
+            "rescued",
+            RescuedEvent {
+                token,
+                to: rescue.to,
+                value: rescue.value,
+            },
+        );
+    }
+}
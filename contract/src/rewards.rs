@@ -0,0 +1,89 @@
+//! Rewards distributed to holders pro-rata by their balance.
+//!
+//! Each distribution takes a snapshot, and the share of each account is computed lazily from its
+//! balance at that snapshot when it claims. Rounding dust stays with the contract.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn distribute(&mut self, distribute: Signed<Distribute>) {
+        let distributor = self.authorize(&distribute);
+        self.distribute_by(distributor, distribute.op().value);
+    }
+
+    pub(crate) fn distribute_from_contract(&mut self, distribute: Distribute) {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        self.distribute_by(Account::Contract(contract), distribute.value);
+    }
+
+    pub(crate) fn claim_rewards(&mut self, claim: Signed<ClaimRewards>) -> u64 {
+        let account = self.authorize(&claim);
+        self.claim_rewards_by(account)
+    }
+
+    pub(crate) fn claim_rewards_from_contract(&mut self) -> u64 {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        self.claim_rewards_by(Account::Contract(contract))
+    }
+
+    pub(crate) fn claimable(&self, account: Account) -> u64 {
+        let next = self.reward_cursors.get(&account).copied().unwrap_or(0);
+
+        self.distributions[next..]
+            .iter()
+            .map(|distribution| {
+                let balance = self.balance_of_at(BalanceAt {
+                    account,
+                    snapshot_id: distribution.snapshot_id,
+                });
+                distribution.share_of(balance)
+            })
+            .sum()
+    }
+
+    fn distribute_by(&mut self, distributor: Account, value: u64) {
+        if value == 0 {
+            panic!("The value distributed can't be zero");
+        }
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+        self.debit_limited(distributor, value);
+        self.rewards_held += value;
+
+        let snapshot_id = self.take_snapshot();
+
+        self.distributions.push(Distribution {
+            snapshot_id,
+            value,
+            total_balance: self.circulating,
+        });
+
+        self.emit(
+            "distributed",
+            DistributedEvent {
+                distributor,
+                value,
+                snapshot_id,
+            },
+        );
+    }
+
+    fn claim_rewards_by(&mut self, account: Account) -> u64 {
+        let value = self.claimable(account);
+        self.reward_cursors
+            .insert(account, self.distributions.len());
+
+        if value == 0 {
+            panic!("The account has no rewards to claim");
+        }
+
+        self.rewards_held -= value;
+        self.credit(account, value);
+
+        self.emit("rewards_claimed", RewardsClaimedEvent { account, value });
+
+        value
+    }
+}
@@ -0,0 +1,101 @@
+//! Rotating the key of an account, moving its balance and allowances to the account of the new key.
+//!
+//! Nonces stay with the key that used them, so operations signed by the old key can't be replayed
+//! should its account be funded again. The expiries, spending limits and operators the account set
+//! move along with its allowances, while its session keys are revoked, having only agreed to sign
+//! for the old account.
+
+use alloc::vec::Vec;
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn rotate_key(&mut self, rotate: Signed<RotateKey>) {
+        let old = self.authorize(&rotate);
+        let new = rotate.op().new_key.account();
+
+        if new == old {
+            panic!("The new key must differ from the old one");
+        }
+
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(new);
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+
+        // sub-accounts are addressed by the old key, so they can't follow it to the new one
+        if let Account::External(pk) = old {
+            let sub_accounts = Account::SubAccount(pk, 1)..=Account::SubAccount(pk, u32::MAX);
+            if self
+                .accounts
+                .range(sub_accounts)
+                .any(|(_, info)| info.balance > 0)
+            {
+                panic!("The sub-accounts of the key must be emptied before rotating it");
+            }
+        }
+
+        let balance = self.account(old).balance;
+        if balance > 0 {
+            // the tokens stay with the same owner, who must be able to move them away from a
+            // compromised key regardless of the rate limits or a tripped breaker
+            self.debit(old, balance);
+            self.credit(new, balance);
+        }
+
+        let mut allowances = 0;
+        if let Some(moved) = self.allowances.remove(&old) {
+            allowances = moved.len() as u32;
+            self.allowances.entry(new).or_default().extend(moved);
+        }
+        if let Some(moved) = self.allowance_expiries.remove(&old) {
+            self.allowance_expiries
+                .entry(new)
+                .or_default()
+                .extend(moved);
+        }
+        if let Some(moved) = self.spending_limits.remove(&old) {
+            self.spending_limits.entry(new).or_default().extend(moved);
+        }
+        if let Some(mut moved) = self.operators.remove(&old) {
+            moved.remove(&new);
+            if !moved.is_empty() {
+                self.operators.entry(new).or_default().extend(moved);
+            }
+        }
+
+        let session_keys: Vec<Account> = self
+            .session_keys
+            .iter()
+            .filter(|(_, session)| session.owner == old)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in session_keys {
+            self.session_keys.remove(&key);
+            if let Account::External(key) = key {
+                self.emit(
+                    "session_key",
+                    SessionKeyEvent {
+                        owner: old,
+                        key,
+                        scope: None,
+                    },
+                );
+            }
+        }
+
+        self.prune_account(old);
+
+        self.emit(
+            KEY_ROTATED_TOPIC,
+            KeyRotated {
+                old,
+                new,
+                balance,
+                allowances,
+            },
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Session keys signing transfers from another account, within a limited scope.
+
+use execution_core::signatures::bls::PublicKey;
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn register_session_key(&mut self, register: Signed<RegisterSessionKey>) {
+        let owner = self.authorize(&register);
+        let register = register.op().clone();
+        let key = Account::External(register.key);
+
+        if key == owner {
+            panic!("An account can't be its own session key");
+        }
+
+        // the key must agree, or any account could take over the transfers it signs
+        if *register.consent.signer() != SignerKey::Bls(register.key) {
+            panic!("The consent must be signed by the session key");
+        }
+        if register.consent.op().owner != owner {
+            panic!("The consent is for another account");
+        }
+        self.authorize(&register.consent);
+
+        if register.scope.expiry_height <= rusk_abi::block_height() {
+            panic!("The session key can't expire in the past");
+        }
+        if matches!(self.session_keys.get(&key), Some(session) if session.owner != owner) {
+            panic!("The key is already a session key of another account");
+        }
+
+        self.session_keys.insert(
+            key,
+            SessionKey {
+                owner,
+                scope: register.scope.clone(),
+                spent: 0,
+            },
+        );
+
+        self.emit(
+            "session_key",
+            SessionKeyEvent {
+                owner,
+                key: register.key,
+                scope: Some(register.scope),
+            },
+        );
+    }
+
+    pub(crate) fn revoke_session_key(&mut self, revoke: Signed<RevokeSessionKey>) {
+        let signer = self.authorize(&revoke);
+        let key = revoke.op().key;
+
+        // the holder of the key may also stop signing on behalf of the account
+        let owner = match self.session_keys.get(&Account::External(key)) {
+            Some(session) if session.owner == signer || Account::External(key) == signer => {
+                session.owner
+            }
+            _ => panic!("The key is not a session key of the account"),
+        };
+        self.session_keys.remove(&Account::External(key));
+
+        self.emit(
+            "session_key",
+            SessionKeyEvent {
+                owner,
+                key,
+                scope: None,
+            },
+        );
+    }
+
+    pub(crate) fn session_key(&self, key: PublicKey) -> Option<SessionKey> {
+        self.session_keys.get(&Account::External(key)).cloned()
+    }
+
+    /// Counts a transfer signed by a key against its scope, if it is a session key, returning the
+    /// account the transfer is made from. Rejects transfers outside of the scope.
+    pub(crate) fn spend_session_key(
+        &mut self,
+        key: Account,
+        to: Account,
+        value: u64,
+    ) -> Option<Account> {
+        let block_height = rusk_abi::block_height();
+        let session = self.session_keys.get_mut(&key)?;
+        let scope = &session.scope;
+
+        if block_height >= scope.expiry_height {
+            panic!("The session key has expired");
+        }
+        if matches!(scope.max_value, Some(max_value) if value > max_value) {
+            panic!("The value exceeds the maximum of the session key");
+        }
+        if matches!(&scope.recipients, Some(recipients) if !recipients.contains(&to)) {
+            panic!("The session key can't transfer to the recipient");
+        }
+
+        let spent = session
+            .spent
+            .checked_add(value)
+            .filter(|spent| *spent <= scope.budget)
+            .expect("The value exceeds the remaining budget of the session key");
+        session.spent = spent;
+
+        Some(session.owner)
+    }
+}
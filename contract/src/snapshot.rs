@@ -0,0 +1,91 @@
+//! Snapshots of balances and the total supply.
+//!
+//! Taking a snapshot doesn't copy any state. Instead, the first time a balance - or the supply - is
+//! written to after a snapshot, its previous value is recorded as a checkpoint tagged with the id
+//! of the current snapshot.
+
+use alloc::vec::Vec;
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+/// Values recorded at given snapshots, sorted by snapshot id.
+pub(crate) type Checkpoints = Vec<(u64, u64)>;
+
+impl TokenState {
+    pub(crate) fn snapshot(&mut self, snapshot: Signed<Snapshot>) -> u64 {
+        self.authorize_admin(&snapshot);
+        self.take_snapshot()
+    }
+
+    /// Takes a new snapshot, returning its id.
+    pub(crate) fn take_snapshot(&mut self) -> u64 {
+        self.snapshot_id += 1;
+        let id = self.snapshot_id;
+
+        self.emit("snapshot", SnapshotEvent { id });
+
+        id
+    }
+
+    pub(crate) fn snapshot_id(&self) -> u64 {
+        self.snapshot_id
+    }
+
+    pub(crate) fn balance_of_at(&self, balance_at: BalanceAt) -> u64 {
+        self.assert_snapshot_exists(balance_at.snapshot_id);
+
+        self.balance_checkpoints
+            .get(&balance_at.account)
+            .and_then(|checkpoints| value_at(checkpoints, balance_at.snapshot_id))
+            .unwrap_or_else(|| self.account(balance_at.account).balance)
+    }
+
+    pub(crate) fn total_supply_at(&self, snapshot_id: u64) -> u64 {
+        self.assert_snapshot_exists(snapshot_id);
+        value_at(&self.supply_checkpoints, snapshot_id).unwrap_or(self.supply)
+    }
+
+    /// Records the current balance of an account, if it hasn't been recorded since the last
+    /// snapshot. Must be called before writing to the balance.
+    pub(crate) fn checkpoint_balance(&mut self, account: Account) {
+        if self.snapshot_id == 0 {
+            return;
+        }
+
+        let balance = self.account(account).balance;
+        let checkpoints = self.balance_checkpoints.entry(account).or_default();
+        record(checkpoints, self.snapshot_id, balance);
+    }
+
+    /// Records the current supply, if it hasn't been recorded since the last snapshot. Must be
+    /// called before writing to the supply.
+    pub(crate) fn checkpoint_supply(&mut self) {
+        if self.snapshot_id == 0 {
+            return;
+        }
+
+        record(&mut self.supply_checkpoints, self.snapshot_id, self.supply);
+    }
+
+    fn assert_snapshot_exists(&self, snapshot_id: u64) {
+        if snapshot_id == 0 || snapshot_id > self.snapshot_id {
+            panic!("The snapshot doesn't exist");
+        }
+    }
+}
+
+fn record(checkpoints: &mut Checkpoints, snapshot_id: u64, value: u64) {
+    match checkpoints.last() {
+        Some((last_id, _)) if *last_id == snapshot_id => {}
+        _ => checkpoints.push((snapshot_id, value)),
+    }
+}
+
+// The value at a snapshot is the one recorded at the first checkpoint taken at or after it. If
+// there is none, the value hasn't changed since.
+fn value_at(checkpoints: &Checkpoints, snapshot_id: u64) -> Option<u64> {
+    let index = checkpoints.partition_point(|(id, _)| *id < snapshot_id);
+    checkpoints.get(index).map(|(_, value)| *value)
+}
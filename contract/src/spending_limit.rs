@@ -0,0 +1,103 @@
+//! Limits on the value a spender may spend from an account in each window of blocks.
+
+use alloc::collections::BTreeMap;
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn set_spending_limit(&mut self, set: Signed<SetSpendingLimit>) {
+        let owner = self.authorize(&set);
+        let set = *set.op();
+
+        let spender = set.spender;
+
+        if set.limit == 0 {
+            if let Some(limits) = self.spending_limits.get_mut(&owner) {
+                limits.remove(&spender);
+                if limits.is_empty() {
+                    self.spending_limits.remove(&owner);
+                }
+            }
+        } else {
+            if set.window_blocks == 0 {
+                panic!("The window can't be zero blocks");
+            }
+
+            self.spending_limits
+                .entry(owner)
+                .or_insert(BTreeMap::new())
+                .insert(
+                    spender,
+                    SpendingLimit {
+                        limit: set.limit,
+                        window_blocks: set.window_blocks,
+                        window: rusk_abi::block_height() / set.window_blocks,
+                        spent: 0,
+                    },
+                );
+        }
+
+        self.emit(
+            "spending_limit",
+            SpendingLimitEvent {
+                owner,
+                spender,
+                limit: set.limit,
+                window_blocks: set.window_blocks,
+            },
+        );
+    }
+
+    pub(crate) fn spending_budget(&self, allowance: Allowance) -> u64 {
+        self.spending_limits
+            .get(&allowance.owner)
+            .and_then(|limits| limits.get(&allowance.spender))
+            .map(|limit| limit.budget_at(rusk_abi::block_height()))
+            .unwrap_or(0)
+    }
+
+    /// The spending limit of a spender, with the window and value spent brought up to date with the
+    /// current block height.
+    pub(crate) fn current_spending_limit(&self, allowance: Allowance) -> Option<SpendingLimit> {
+        let limit = self
+            .spending_limits
+            .get(&allowance.owner)
+            .and_then(|limits| limits.get(&allowance.spender))?;
+
+        let block_height = rusk_abi::block_height();
+        Some(SpendingLimit {
+            window: block_height / limit.window_blocks,
+            spent: limit.limit - limit.budget_at(block_height),
+            ..*limit
+        })
+    }
+
+    /// Deducts the given value from the budget of the spender in the current window, returning
+    /// `false` if the spender has no spending limit on the owner's account.
+    pub(crate) fn spend_limited(&mut self, owner: Account, spender: Account, value: u64) -> bool {
+        let limit = match self
+            .spending_limits
+            .get_mut(&owner)
+            .and_then(|limits| limits.get_mut(&spender))
+        {
+            Some(limit) => limit,
+            None => return false,
+        };
+
+        let block_height = rusk_abi::block_height();
+        if value > limit.budget_at(block_height) {
+            panic!("The spender can't spend more than its limit in the current window");
+        }
+
+        let window = block_height / limit.window_blocks;
+        if window != limit.window {
+            limit.window = window;
+            limit.spent = 0;
+        }
+        limit.spent += value;
+
+        true
+    }
+}
@@ -0,0 +1,86 @@
+//! Staking tokens, locking them for a number of blocks.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn stake(&mut self, stake: Signed<Stake>) {
+        let account = self.authorize(&stake);
+        self.stake_by(account, *stake.op());
+    }
+
+    pub(crate) fn stake_from_contract(&mut self, stake: Stake) {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        self.stake_by(Account::Contract(contract), stake);
+    }
+
+    pub(crate) fn unstake(&mut self, unstake: Signed<Unstake>) {
+        let account = self.authorize(&unstake);
+        self.unstake_by(account, unstake.op().value);
+    }
+
+    pub(crate) fn unstake_from_contract(&mut self, unstake: Unstake) {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        self.unstake_by(Account::Contract(contract), unstake.value);
+    }
+
+    pub(crate) fn staked_of(&self, account: Account) -> StakeInfo {
+        self.stakes
+            .get(&account)
+            .copied()
+            .unwrap_or(StakeInfo::EMPTY)
+    }
+
+    fn stake_by(&mut self, account: Account, stake: Stake) {
+        if stake.value == 0 {
+            panic!("The value staked can't be zero");
+        }
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+        self.debit_limited(account, stake.value);
+
+        let unlock_height = rusk_abi::block_height()
+            .checked_add(stake.lock_blocks)
+            .expect("The stake can't unlock past the largest block height");
+
+        let staked = self.stakes.entry(account).or_insert(StakeInfo::EMPTY);
+        staked.value += stake.value;
+        staked.unlock_height = staked.unlock_height.max(unlock_height);
+
+        let unlock_height = staked.unlock_height;
+
+        self.emit(
+            "staked",
+            StakedEvent {
+                account,
+                value: stake.value,
+                unlock_height,
+            },
+        );
+    }
+
+    fn unstake_by(&mut self, account: Account, value: u64) {
+        let staked = self
+            .stakes
+            .get_mut(&account)
+            .expect("The account has no tokens staked");
+
+        if rusk_abi::block_height() < staked.unlock_height {
+            panic!("The staked tokens are still locked");
+        }
+
+        if staked.value < value {
+            panic!("The account doesn't have enough tokens staked");
+        }
+
+        staked.value -= value;
+        if staked.value == 0 {
+            self.stakes.remove(&account);
+        }
+
+        self.credit(account, value);
+
+        self.emit("unstaked", UnstakedEvent { account, value });
+    }
+}
@@ -0,0 +1,90 @@
+//! A Merkle tree over the data of all accounts, updated on every write to it.
+//!
+//! Leaves are appended as accounts first get data, so updating the tree only touches the path from
+//! the leaf of the account to the root. The levels of the tree are kept in full, from the leaves up
+//! to the root.
+
+use alloc::vec::Vec;
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn state_root(&self) -> [u8; 32] {
+        match self.state_tree.last() {
+            Some(root) => root[0],
+            None => EMPTY_STATE_ROOT,
+        }
+    }
+
+    /// Feeds the [`AccountProof`] of an account.
+    pub(crate) fn proof_of(&self, account: Account) {
+        let index = *self
+            .state_leaves
+            .get(&account)
+            .expect("The account is not in the state tree");
+
+        let mut siblings = Vec::new();
+        let mut i = index as usize;
+        for level in &self.state_tree[..self.state_tree.len() - 1] {
+            if let Some(sibling) = level.get(i ^ 1) {
+                siblings.push(*sibling);
+            }
+            i /= 2;
+        }
+
+        rusk_abi::feed(AccountProof {
+            account,
+            info: self.account(account),
+            block_height: rusk_abi::block_height(),
+            root: self.state_root(),
+            index,
+            leaf_count: self.state_tree[0].len() as u64,
+            siblings,
+        });
+    }
+
+    /// Updates the leaf of an account with its current data, appending one if it has none, and
+    /// the nodes on its path to the root.
+    pub(crate) fn update_state_leaf(&mut self, account: Account) {
+        let leaf = state_leaf(&account, &self.account(account));
+
+        if self.state_tree.is_empty() {
+            self.state_tree.push(Vec::new());
+        }
+
+        let next_index = self.state_tree[0].len() as u64;
+        let mut index = *self.state_leaves.entry(account).or_insert(next_index) as usize;
+        set_node(&mut self.state_tree[0], index, leaf);
+
+        let mut level = 0;
+        while self.state_tree[level].len() > 1 {
+            let nodes = &self.state_tree[level];
+            let parent = if index % 2 == 1 {
+                state_node(&nodes[index - 1], &nodes[index])
+            } else {
+                match nodes.get(index + 1) {
+                    Some(right) => state_node(&nodes[index], right),
+                    None => nodes[index],
+                }
+            };
+
+            if self.state_tree.len() == level + 1 {
+                self.state_tree.push(Vec::new());
+            }
+
+            index /= 2;
+            level += 1;
+            set_node(&mut self.state_tree[level], index, parent);
+        }
+    }
+}
+
+/// Sets the node at the index of a level, which is at most one past its end.
+fn set_node(level: &mut Vec<[u8; 32]>, index: usize, node: [u8; 32]) {
+    match level.get_mut(index) {
+        Some(existing) => *existing = node,
+        None => level.push(node),
+    }
+}
@@ -0,0 +1,130 @@
+//! Streams of tokens paid to a recipient at a fixed rate for every block, out of a deposit.
+//!
+//! The recipient withdraws what accrued whenever it likes, and either party may close the stream,
+//! which pays the recipient what accrued and returns the rest of the deposit to the sender. A stream
+//! that has paid out its whole deposit is removed once withdrawn.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn open_stream(&mut self, open: Signed<OpenStream>) -> u64 {
+        let sender = self.authorize(&open);
+        let open = *open.op();
+
+        if open.rate_per_block == 0 {
+            panic!("The rate of the stream can't be zero");
+        }
+        if open.deposit == 0 {
+            panic!("The deposit of the stream can't be zero");
+        }
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+
+        #[cfg(feature = "compliance")]
+        {
+            self.assert_whitelisted(sender);
+            self.assert_whitelisted(open.to);
+        }
+
+        // the deposit leaves the sender's account as a transfer would, so it is checked as one
+        self.assert_min_transfer(sender, open.deposit);
+        self.assert_below_two_phase_threshold(open.deposit);
+        self.debit_limited(sender, open.deposit);
+        self.prune_account(sender);
+
+        let id = self.next_stream_id;
+        self.next_stream_id += 1;
+
+        self.streams.insert(
+            id,
+            Stream {
+                sender,
+                recipient: open.to,
+                rate_per_block: open.rate_per_block,
+                deposit: open.deposit,
+                withdrawn: 0,
+                start_height: rusk_abi::block_height(),
+            },
+        );
+
+        self.emit(
+            "stream_opened",
+            StreamOpenedEvent {
+                id,
+                sender,
+                recipient: open.to,
+                rate_per_block: open.rate_per_block,
+                deposit: open.deposit,
+            },
+        );
+
+        id
+    }
+
+    pub(crate) fn withdraw_from_stream(&mut self, withdraw: Signed<WithdrawFromStream>) -> u64 {
+        let recipient = self.authorize(&withdraw);
+        let id = withdraw.op().id;
+
+        let stream = self.streams.get_mut(&id).expect("The stream doesn't exist");
+        if stream.recipient != recipient {
+            panic!("Only the recipient can withdraw from the stream");
+        }
+
+        let value = stream.withdrawable_at(rusk_abi::block_height());
+        if value == 0 {
+            panic!("The stream has no tokens to withdraw");
+        }
+
+        stream.withdrawn += value;
+        if stream.withdrawn == stream.deposit {
+            self.streams.remove(&id);
+        }
+
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(recipient);
+
+        self.credit(recipient, value);
+
+        self.emit(
+            "stream_withdrawn",
+            StreamWithdrawnEvent {
+                id,
+                recipient,
+                value,
+            },
+        );
+
+        value
+    }
+
+    pub(crate) fn close_stream(&mut self, close: Signed<CloseStream>) {
+        let signer = self.authorize(&close);
+        let id = close.op().id;
+
+        let stream = self.streams.remove(&id).expect("The stream doesn't exist");
+        if signer != stream.sender && signer != stream.recipient {
+            panic!("Only the sender or the recipient can close the stream");
+        }
+
+        let paid = stream.withdrawable_at(rusk_abi::block_height());
+        let refunded = stream.deposit - stream.withdrawn - paid;
+
+        if paid > 0 {
+            #[cfg(feature = "compliance")]
+            self.assert_whitelisted(stream.recipient);
+
+            self.credit(stream.recipient, paid);
+        }
+        if refunded > 0 {
+            self.credit(stream.sender, refunded);
+        }
+
+        self.emit("stream_closed", StreamClosedEvent { id, paid, refunded });
+    }
+
+    pub(crate) fn stream(&self, id: u64) -> Option<Stream> {
+        self.streams.get(&id).copied()
+    }
+}
@@ -0,0 +1,81 @@
+//! Sub-accounts of a BLS key, segregating the tokens it controls under numbered indices.
+//!
+//! A sub-account holds tokens like any other account, and is spent from by its key signing a
+//! `SubAccountTransfer`. Moving tokens between the sub-accounts of the same key leaves them under
+//! its control, so it skips the checks transfers make on their recipient, and calls no hooks.
+
+use execution_core::signatures::bls::PublicKey;
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn sub_account_transfer(&mut self, transfer: Signed<SubAccountTransfer>) {
+        let key = self.authorize_key(&transfer);
+        let transfer = *transfer.op();
+
+        let hook_policy = self.hook_policy;
+        self.move_tokens(
+            Account::sub_account(key, transfer.from),
+            None,
+            transfer.to,
+            transfer.value,
+            &[],
+            None,
+            hook_policy,
+        );
+    }
+
+    pub(crate) fn sub_account_move(&mut self, sub_move: Signed<SubAccountMove>) {
+        let key = self.authorize_key(&sub_move);
+        let sub_move = *sub_move.op();
+
+        if sub_move.from == sub_move.to {
+            panic!("The sub-accounts must differ");
+        }
+
+        let from = Account::sub_account(key, sub_move.from);
+        let to = Account::sub_account(key, sub_move.to);
+        let value = sub_move.value;
+
+        // whitelisting applies to the key as a whole, and the owner is the same, so only the
+        // checks on moving tokens at all are made
+        self.assert_not_paused(PauseScope::TRANSFERS);
+
+        self.debit(from, value);
+        self.credit(to, value);
+
+        self.prune_account(from);
+        self.prune_account(to);
+
+        self.record_transfer(TransferRecord {
+            token: DEFAULT_TOKEN_ID,
+            owner: from,
+            spender: None,
+            to,
+            value,
+            block_height: rusk_abi::block_height(),
+        });
+
+        self.emit(
+            TRANSFER_TOPIC,
+            TransferEvent {
+                owner: from,
+                spender: None,
+                to,
+                value,
+                burned: 0,
+                memo: None,
+            },
+        );
+    }
+
+    /// Checks the nonce and signature of an operation on sub-accounts, returning the BLS key they
+    /// belong to.
+    fn authorize_key<T: Operation>(&mut self, signed: &Signed<T>) -> PublicKey {
+        match self.authorize(signed) {
+            Account::External(key) => key,
+            _ => panic!("Only BLS keys have sub-accounts"),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Tracking the transfers submitted with an idempotency id, so their submitters can tell whether
+//! they were executed.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn submission_status(&self, id: SubmissionId) -> SubmissionStatus {
+        self.submissions
+            .get(&id.account)
+            .and_then(|submissions| submissions.iter().find(|s| s.id == id.id))
+            .map(|submission| SubmissionStatus::Executed(*submission))
+            .unwrap_or(SubmissionStatus::Unknown)
+    }
+
+    /// Rejects a transfer if the account recently submitted another with the same idempotency id.
+    pub(crate) fn assert_new_submission(&self, account: Account, id: u64) {
+        let id = SubmissionId { account, id };
+        if self.submission_status(id) != SubmissionStatus::Unknown {
+            panic!("The idempotency id has already been used");
+        }
+    }
+
+    /// Records an executed submission of an account, dropping its oldest if it has too many.
+    pub(crate) fn record_submission(&mut self, account: Account, submission: Submission) {
+        let submissions = self.submissions.entry(account).or_default();
+
+        if submissions.len() == SUBMISSION_WINDOW {
+            submissions.remove(0);
+        }
+        submissions.push(submission);
+    }
+}
@@ -0,0 +1,149 @@
+//! A treasury held by the contract, paying registered payees up to a cap in each epoch.
+//!
+//! Anyone may fund the treasury, while the admin sets the payout key and registers the payees. The
+//! payout key can then pay many payees at once, without being able to exceed their caps.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn fund_treasury(&mut self, fund: Signed<FundTreasury>) {
+        let from = self.authorize(&fund);
+        let value = fund.op().value;
+
+        if value == 0 {
+            panic!("The value funded can't be zero");
+        }
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+        self.debit_limited(from, value);
+        self.prune_account(from);
+        self.treasury.balance += value;
+
+        self.emit("treasury_funded", TreasuryFundedEvent { from, value });
+    }
+
+    pub(crate) fn set_treasury(&mut self, set: Signed<SetTreasury>) {
+        self.authorize_admin(&set);
+        let set = *set.op();
+
+        if set.epoch_blocks == 0 {
+            panic!("The epochs can't be zero blocks");
+        }
+
+        // the values paid are counted in epochs of the previous length, so they are started over
+        if set.epoch_blocks != self.treasury.epoch_blocks {
+            let epoch = rusk_abi::block_height() / set.epoch_blocks;
+            for payee in self.payees.values_mut() {
+                payee.epoch = epoch;
+                payee.paid = 0;
+            }
+        }
+
+        self.treasury.payout_key = set.payout_key;
+        self.treasury.epoch_blocks = set.epoch_blocks;
+
+        self.emit(
+            "treasury_set",
+            TreasurySetEvent {
+                payout_key: set.payout_key,
+                epoch_blocks: set.epoch_blocks,
+            },
+        );
+    }
+
+    pub(crate) fn set_payee(&mut self, set: Signed<SetPayee>) {
+        self.authorize_admin(&set);
+        let set = *set.op();
+
+        if self.treasury.epoch_blocks == 0 {
+            panic!("The treasury hasn't been set");
+        }
+
+        if set.cap == 0 {
+            self.payees.remove(&set.payee);
+        } else {
+            let epoch = rusk_abi::block_height() / self.treasury.epoch_blocks;
+            let payee = self.payees.entry(set.payee).or_insert(Payee {
+                cap: 0,
+                epoch,
+                paid: 0,
+            });
+            // lowering the cap keeps what was already paid in the epoch
+            payee.cap = set.cap;
+        }
+
+        self.emit(
+            "payee",
+            PayeeEvent {
+                payee: set.payee,
+                cap: set.cap,
+            },
+        );
+    }
+
+    pub(crate) fn payout(&mut self, payout: Signed<Payout>) {
+        match self.treasury.payout_key {
+            Some(key) if SignerKey::Bls(key) == *payout.signer() => {}
+            _ => panic!("Only the payout key can pay out of the treasury"),
+        }
+        self.authorize(&payout);
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+
+        let block_height = rusk_abi::block_height();
+        let epoch_blocks = self.treasury.epoch_blocks;
+        let epoch = block_height / epoch_blocks;
+
+        for &(to, value) in &payout.op().payments {
+            let payee = self
+                .payees
+                .get_mut(&to)
+                .expect("The account is not a payee of the treasury");
+
+            if value > payee.budget_at(block_height, epoch_blocks) {
+                panic!("The payee can't be paid more than its cap in the current epoch");
+            }
+            if value > self.treasury.balance {
+                panic!("The treasury doesn't have enough tokens");
+            }
+
+            if payee.epoch != epoch {
+                payee.epoch = epoch;
+                payee.paid = 0;
+            }
+            payee.paid += value;
+
+            #[cfg(feature = "compliance")]
+            self.assert_whitelisted(to);
+
+            self.treasury.balance -= value;
+            self.credit(to, value);
+
+            self.emit(
+                "payout",
+                PayoutEvent {
+                    payee: to,
+                    value,
+                    epoch,
+                },
+            );
+        }
+    }
+
+    pub(crate) fn treasury(&self) -> TreasuryInfo {
+        self.treasury
+    }
+
+    pub(crate) fn payee(&self, account: Account) -> Option<Payee> {
+        let payee = self.payees.get(&account)?;
+
+        let epoch = rusk_abi::block_height() / self.treasury.epoch_blocks;
+        Some(Payee {
+            epoch,
+            paid: if payee.epoch == epoch { payee.paid } else { 0 },
+            ..*payee
+        })
+    }
+}
@@ -0,0 +1,132 @@
+//! Transfers proposed by the sender and claimed by the recipient.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn propose_transfer(&mut self, propose: Signed<ProposeTransfer>) -> u64 {
+        let from = self.authorize(&propose);
+        let propose = *propose.op();
+
+        #[cfg(feature = "compliance")]
+        {
+            self.assert_whitelisted(from);
+            self.assert_whitelisted(propose.to);
+        }
+
+        if propose.expiry_height <= rusk_abi::block_height() {
+            panic!("The proposed transfer can't expire in the past");
+        }
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+        self.assert_min_transfer(from, propose.value);
+        self.debit_limited(from, propose.value);
+
+        let id = self.next_pending_id;
+        self.next_pending_id += 1;
+
+        self.pending_transfers.insert(
+            id,
+            PendingTransfer {
+                from,
+                to: propose.to,
+                value: propose.value,
+                expiry_height: propose.expiry_height,
+            },
+        );
+
+        self.emit(
+            "transfer_proposed",
+            TransferProposedEvent {
+                id,
+                from,
+                to: propose.to,
+                value: propose.value,
+                expiry_height: propose.expiry_height,
+            },
+        );
+
+        id
+    }
+
+    pub(crate) fn claim_transfer(&mut self, claim: Signed<ClaimTransfer>) {
+        let to = self.authorize(&claim);
+        self.claim_transfer_by(to, claim.op().id);
+    }
+
+    pub(crate) fn claim_transfer_from_contract(&mut self, claim: ClaimTransfer) {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        self.claim_transfer_by(Account::Contract(contract), claim.id);
+    }
+
+    pub(crate) fn cancel_transfer(&mut self, cancel: Signed<CancelTransfer>) {
+        let from = self.authorize(&cancel);
+        let id = cancel.op().id;
+
+        let pending = self.remove_pending(id);
+        if pending.from != from {
+            panic!("Only the sender can cancel a proposed transfer");
+        }
+
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(from);
+
+        self.credit(from, pending.value);
+
+        self.emit(
+            "transfer_cancelled",
+            TransferCancelledEvent {
+                id,
+                from,
+                value: pending.value,
+            },
+        );
+    }
+
+    pub(crate) fn pending_transfer(&self, id: u64) -> Option<PendingTransfer> {
+        self.pending_transfers.get(&id).copied()
+    }
+
+    pub(crate) fn two_phase_threshold(&self) -> Option<u64> {
+        self.two_phase_threshold
+    }
+
+    /// Rejects transfers above the two-phase threshold, which must be proposed and claimed instead.
+    pub(crate) fn assert_below_two_phase_threshold(&self, value: u64) {
+        if matches!(self.two_phase_threshold, Some(threshold) if value > threshold) {
+            panic!("Transfers above the two-phase threshold must be proposed and claimed");
+        }
+    }
+
+    fn claim_transfer_by(&mut self, to: Account, id: u64) {
+        let pending = self.remove_pending(id);
+        if pending.to != to {
+            panic!("Only the recipient can claim a proposed transfer");
+        }
+
+        if rusk_abi::block_height() >= pending.expiry_height {
+            panic!("The proposed transfer has expired");
+        }
+
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(to);
+
+        self.credit(to, pending.value);
+
+        self.emit(
+            "transfer_claimed",
+            TransferClaimedEvent {
+                id,
+                to,
+                value: pending.value,
+            },
+        );
+    }
+
+    fn remove_pending(&mut self, id: u64) -> PendingTransfer {
+        self.pending_transfers
+            .remove(&id)
+            .expect("The proposed transfer doesn't exist")
+    }
+}
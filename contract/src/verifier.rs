@@ -0,0 +1,69 @@
+//! Contract accounts authorizing transfers using a verifier of their choosing.
+
+use execution_core::ContractId;
+use ttoken_types::*;
+
+use crate::{check_message_version, TokenState};
+
+impl TokenState {
+    pub(crate) fn register_verifier(&mut self, verifier: Option<Verifier>) {
+        let account = rusk_abi::caller().expect("Must be called by a contract");
+
+        match &verifier {
+            Some(verifier) => self.verifiers.insert(account, verifier.clone()),
+            None => self.verifiers.remove(&account),
+        };
+
+        self.emit(
+            "verifier_registered",
+            VerifierRegisteredEvent { account, verifier },
+        );
+    }
+
+    pub(crate) fn verifier(&self, account: ContractId) -> Option<Verifier> {
+        self.verifiers.get(&account).cloned()
+    }
+
+    pub(crate) fn transfer_authorized(&mut self, transfer: AuthorizedTransfer) {
+        let account = *transfer.from();
+        let from = Account::Contract(account);
+
+        self.use_nonce(from, transfer.nonce());
+
+        check_message_version(transfer.version());
+
+        let verifier = self
+            .verifiers
+            .get(&account)
+            .cloned()
+            .expect("The account has no verifier registered");
+
+        let verify = VerifyAuthorization {
+            account,
+            message: transfer.signature_message(),
+            authorization: transfer.authorization().to_vec(),
+        };
+
+        // the verifier is called as a hook, so it can't act on the transfer before it is authorized
+        self.in_hook = true;
+        let result = rusk_abi::call::<_, bool>(verifier.contract, &verifier.fn_name, &verify);
+        self.in_hook = false;
+
+        match result {
+            Ok(true) => {}
+            Ok(false) => panic!("Invalid authorization"),
+            Err(err) => panic!("Failed calling the verifier: {err}"),
+        }
+
+        let hook_policy = self.hook_policy;
+        self.move_tokens(
+            from,
+            None,
+            *transfer.to(),
+            transfer.value(),
+            &[],
+            None,
+            hook_policy,
+        );
+    }
+}
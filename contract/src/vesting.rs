@@ -0,0 +1,104 @@
+//! Linear vesting of tokens locked by the admin.
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn create_vesting(&mut self, create: Signed<CreateVesting>) {
+        self.authorize_admin(&create);
+
+        let admin = create.signer_account();
+        let create = *create.op();
+
+        if create.duration == 0 {
+            panic!("The vesting duration can't be zero");
+        }
+
+        if create.cliff > create.duration {
+            panic!("The cliff can't be longer than the vesting duration");
+        }
+
+        // the cliff is no longer than the duration, so it ends no later than the vesting
+        if create.start_height.checked_add(create.duration).is_none() {
+            panic!("The vesting can't end past the largest block height");
+        }
+
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(create.beneficiary);
+
+        if let Some(vesting) = self.vestings.get(&create.beneficiary) {
+            if vesting.claimed < vesting.total {
+                panic!("The beneficiary already has tokens vesting");
+            }
+        }
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+        self.debit_limited(admin, create.total);
+
+        self.vestings.insert(
+            create.beneficiary,
+            Vesting {
+                total: create.total,
+                claimed: 0,
+                start_height: create.start_height,
+                cliff: create.cliff,
+                duration: create.duration,
+            },
+        );
+
+        self.emit(
+            "vesting_created",
+            VestingCreatedEvent {
+                beneficiary: create.beneficiary,
+                total: create.total,
+                start_height: create.start_height,
+                cliff: create.cliff,
+                duration: create.duration,
+            },
+        );
+    }
+
+    pub(crate) fn claim_vested(&mut self, claim: Signed<ClaimVested>) -> u64 {
+        let beneficiary = self.authorize(&claim);
+        self.claim_vested_by(beneficiary)
+    }
+
+    pub(crate) fn claim_vested_from_contract(&mut self) -> u64 {
+        let contract = rusk_abi::caller().expect("Must be called by a contract");
+        self.claim_vested_by(Account::Contract(contract))
+    }
+
+    pub(crate) fn vesting_of(&self, account: Account) -> Option<Vesting> {
+        self.vestings.get(&account).copied()
+    }
+
+    fn claim_vested_by(&mut self, beneficiary: Account) -> u64 {
+        let vesting = self
+            .vestings
+            .get_mut(&beneficiary)
+            .expect("The account has no tokens vesting");
+
+        let value = vesting.claimable_at(rusk_abi::block_height());
+        if value == 0 {
+            panic!("The account has no vested tokens to claim");
+        }
+
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(beneficiary);
+
+        vesting.claimed += value;
+        if vesting.claimed == vesting.total {
+            self.vestings.remove(&beneficiary);
+        }
+
+        self.credit(beneficiary, value);
+
+        self.emit(
+            "vesting_claimed",
+            VestingClaimedEvent { beneficiary, value },
+        );
+
+        value
+    }
+}
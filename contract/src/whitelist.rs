@@ -0,0 +1,65 @@
+//! Whitelist mode, where only approved accounts may hold tokens.
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn init_whitelist(&mut self, accounts: Vec<Account>) {
+        self.whitelist = Some(accounts.into_iter().collect());
+    }
+
+    pub(crate) fn add_to_whitelist(&mut self, add: Signed<AddToWhitelist>) {
+        self.authorize_admin(&add);
+        let account = add.op().account;
+
+        self.whitelist_mut().insert(account);
+
+        self.emit(
+            "whitelist",
+            WhitelistEvent {
+                account,
+                whitelisted: true,
+            },
+        );
+    }
+
+    pub(crate) fn remove_from_whitelist(&mut self, remove: Signed<RemoveFromWhitelist>) {
+        self.authorize_admin(&remove);
+        let account = remove.op().account;
+
+        self.whitelist_mut().remove(&account);
+
+        self.emit(
+            "whitelist",
+            WhitelistEvent {
+                account,
+                whitelisted: false,
+            },
+        );
+    }
+
+    /// Whether the account may hold tokens. Always true when the whitelist mode is not active.
+    /// Sub-accounts are whitelisted along with the account of their key.
+    pub(crate) fn is_whitelisted(&self, account: Account) -> bool {
+        match &self.whitelist {
+            Some(whitelist) => whitelist.contains(&account.root()),
+            None => true,
+        }
+    }
+
+    pub(crate) fn assert_whitelisted(&self, account: Account) {
+        if !self.is_whitelisted(account) {
+            panic!("The account is not whitelisted");
+        }
+    }
+
+    fn whitelist_mut(&mut self) -> &mut BTreeSet<Account> {
+        self.whitelist
+            .as_mut()
+            .expect("The whitelist mode is not active")
+    }
+}
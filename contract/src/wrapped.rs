@@ -0,0 +1,61 @@
+//! Wrapping native Dusk, with every token backed by Dusk held by the contract.
+
+use execution_core::transfer::{ContractToAccount, TRANSFER_CONTRACT};
+use ttoken_types::*;
+
+use crate::TokenState;
+
+impl TokenState {
+    pub(crate) fn deposit(&mut self, deposit: Deposit) {
+        self.assert_wrapped();
+        self.assert_not_paused(PauseScope::MINT);
+
+        let to = deposit.to;
+        let value = deposit.value;
+
+        #[cfg(feature = "compliance")]
+        self.assert_whitelisted(to);
+
+        // take the Dusk deposited in the transaction, failing if it doesn't match
+        if let Err(err) = rusk_abi::call::<_, ()>(TRANSFER_CONTRACT, "deposit", &value) {
+            panic!("Failed taking the deposit: {err}");
+        }
+
+        self.checkpoint_supply();
+        self.supply += value;
+
+        self.credit(to, value);
+
+        self.emit(DEPOSIT_TOPIC, DepositEvent { to, value });
+    }
+
+    pub(crate) fn withdraw(&mut self, withdraw: Signed<Withdraw>) {
+        self.assert_wrapped();
+
+        let from = self.authorize(&withdraw);
+        let to = withdraw.op().to;
+        let value = withdraw.op().value;
+
+        self.assert_not_paused(PauseScope::TRANSFERS);
+        self.debit_limited(from, value);
+
+        self.checkpoint_supply();
+        self.supply -= value;
+
+        if let Err(err) = rusk_abi::call::<_, ()>(
+            TRANSFER_CONTRACT,
+            "contract_to_account",
+            &ContractToAccount { account: to, value },
+        ) {
+            panic!("Failed sending the withdrawn Dusk: {err}");
+        }
+
+        self.emit(WITHDRAW_TOPIC, WithdrawEvent { from, to, value });
+    }
+
+    pub(crate) fn assert_wrapped(&self) {
+        if !self.wrapped {
+            panic!("The token doesn't wrap native Dusk");
+        }
+    }
+}
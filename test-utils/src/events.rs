@@ -0,0 +1,134 @@
+//! Decoding and asserting on the events emitted by the token contract.
+//!
+//! Events are taken from the [`CallReceipt`] of a call, and only those emitted by the given token
+//! contract are considered, so events emitted by other contracts in the same call are ignored.
+
+use bytecheck::CheckBytes;
+use rkyv::validation::validators::DefaultValidator;
+use rkyv::{AlignedVec, Archive, Deserialize, Infallible};
+
+use execution_core::ContractId;
+use rusk_abi::CallReceipt;
+use ttoken_types::*;
+
+/// Decode the events with the given topic emitted by the `token` in a call, in order.
+///
+/// # Panics
+/// If the data of an event with the topic is not a valid `T`.
+pub fn decode_events<T, R>(receipt: &CallReceipt<R>, token: ContractId, topic: &str) -> Vec<T>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, Infallible> + for<'b> CheckBytes<DefaultValidator<'b>>,
+{
+    receipt
+        .events
+        .iter()
+        .filter(|event| event.source == token && event.topic == topic)
+        .map(|event| {
+            // the archived event must be aligned, which the emitted bytes may not be
+            let mut bytes = AlignedVec::with_capacity(event.data.len());
+            bytes.extend_from_slice(&event.data);
+
+            rkyv::from_bytes(&bytes)
+                .unwrap_or_else(|_| panic!("The `{topic}` event should be valid"))
+        })
+        .collect()
+}
+
+/// All the events emitted by the `token` in a call, in order, decoded using [`decode_event`].
+///
+/// # Panics
+/// If the data of an event is not valid for its topic.
+pub fn token_events<R>(receipt: &CallReceipt<R>, token: ContractId) -> Vec<TokenEvent> {
+    receipt
+        .events
+        .iter()
+        .filter(|event| event.source == token)
+        .map(|event| {
+            decode_event(&event.topic, &event.data)
+                .unwrap_or_else(|| panic!("The `{}` event should be valid", event.topic))
+        })
+        .collect()
+}
+
+/// The transfer events emitted by the `token` in a call, in order.
+pub fn transfer_events<R>(
+    receipt: &CallReceipt<R>,
+    token: ContractId,
+) -> Vec<Sequenced<TransferEvent>> {
+    decode_events(receipt, token, TRANSFER_TOPIC)
+}
+
+/// The approve events emitted by the `token` in a call, in order.
+pub fn approve_events<R>(
+    receipt: &CallReceipt<R>,
+    token: ContractId,
+) -> Vec<Sequenced<ApproveEvent>> {
+    decode_events(receipt, token, APPROVE_TOPIC)
+}
+
+/// Assert that the `token` emitted a transfer of `value` from `owner` to `to` in a call, and return
+/// the event.
+#[track_caller]
+pub fn assert_transfer<R>(
+    receipt: &CallReceipt<R>,
+    token: ContractId,
+    owner: impl Into<Account>,
+    to: impl Into<Account>,
+    value: u64,
+) -> TransferEvent {
+    let owner = owner.into();
+    let to = to.into();
+
+    let events = transfer_events(receipt, token);
+    match events
+        .iter()
+        .find(|e| e.event.owner == owner && e.event.to == to && e.event.value == value)
+    {
+        Some(event) => event.event,
+        None => panic!(
+            "A transfer of {value} from {owner} to {to} should be emitted, but got {events:?}"
+        ),
+    }
+}
+
+/// Assert that the `token` emitted an approval of `spender` for `value` on the `owner` account in
+/// a call, and return the event.
+#[track_caller]
+pub fn assert_approve<R>(
+    receipt: &CallReceipt<R>,
+    token: ContractId,
+    owner: impl Into<Account>,
+    spender: impl Into<Account>,
+    value: u64,
+) -> ApproveEvent {
+    let owner = owner.into();
+    let spender = spender.into();
+
+    let events = approve_events(receipt, token);
+    match events
+        .iter()
+        .find(|e| e.event.owner == owner && e.event.spender == spender && e.event.value == value)
+    {
+        Some(event) => event.event,
+        None => panic!(
+            "An approval of {spender} for {value} on {owner} should be emitted, but got {events:?}"
+        ),
+    }
+}
+
+/// Assert that the `token` emitted no events in a call.
+#[track_caller]
+pub fn assert_no_events<R>(receipt: &CallReceipt<R>, token: ContractId) {
+    let topics: Vec<&str> = receipt
+        .events
+        .iter()
+        .filter(|event| event.source == token)
+        .map(|event| event.topic.as_str())
+        .collect();
+
+    assert!(
+        topics.is_empty(),
+        "No events should be emitted, but got {topics:?}"
+    );
+}
@@ -0,0 +1,169 @@
+//! Utilities for testing contracts interacting with the `ttoken-contract`.
+//!
+//! [`TokenSession`] runs the token contract in an ephemeral VM, alongside any other contracts under
+//! test, and provides typed wrappers for calling it. The functions in the [`events`] module decode
+//! and assert on the events it emits.
+
+#![deny(missing_docs)]
+
+use bytecheck::CheckBytes;
+use rkyv::validation::validators::DefaultValidator;
+use rkyv::{Archive, Deserialize, Infallible, Serialize};
+
+use execution_core::{ContractId, StandardBufSerializer};
+use rusk_abi::{CallReceipt, ContractData, PiecrustError, Session, VM};
+use ttoken_types::*;
+
+pub mod events;
+
+pub use events::*;
+
+/// The owner of the contracts deployed by a [`TokenSession`].
+pub const OWNER: [u8; 64] = [0u8; 64];
+
+/// The result of calling a contract.
+pub type Result<T, Error = PiecrustError> = std::result::Result<T, Error>;
+
+/// A session in an ephemeral VM, with the token contract deployed.
+///
+/// Calls are made with no gas limit. Failing to set up the session panics, since there is nothing a
+/// test could do to recover.
+pub struct TokenSession {
+    vm: VM,
+    session: Session,
+    token: ContractId,
+}
+
+impl TokenSession {
+    /// Deploy the token contract with the given bytecode, ID, and initialization arguments.
+    pub fn new(bytecode: &[u8], token: ContractId, init_args: &InitArgs) -> Self {
+        let vm = rusk_abi::new_ephemeral_vm().expect("Creating VM should succeed");
+        let mut session = rusk_abi::new_genesis_session(&vm);
+
+        session
+            .deploy(
+                bytecode,
+                ContractData::builder()
+                    .owner(OWNER)
+                    .constructor_arg(init_args)
+                    .contract_id(token),
+                u64::MAX,
+            )
+            .expect("Deploying the token contract should succeed");
+
+        Self { vm, session, token }
+    }
+
+    /// Deploy another contract, such as one interacting with the token, with the given bytecode,
+    /// ID, and constructor argument.
+    pub fn deploy<A>(&mut self, bytecode: &[u8], contract: ContractId, arg: &A)
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.session
+            .deploy(
+                bytecode,
+                ContractData::builder()
+                    .owner(OWNER)
+                    .constructor_arg(arg)
+                    .contract_id(contract),
+                u64::MAX,
+            )
+            .expect("Deploying the contract should succeed");
+    }
+
+    /// The ID of the token contract.
+    pub fn token(&self) -> ContractId {
+        self.token
+    }
+
+    /// The underlying session, for anything not covered by the wrappers.
+    pub fn session(&mut self) -> &mut Session {
+        &mut self.session
+    }
+
+    /// Commits the current session, and continues from a new one at the given block height.
+    pub fn set_block_height(&mut self, block_height: u64) {
+        let base = self
+            .session
+            .commit()
+            .expect("Committing the session should succeed");
+        self.session = rusk_abi::new_session(&self.vm, base, block_height)
+            .expect("Starting a new session should succeed");
+    }
+
+    /// Call any deployed contract.
+    pub fn call<A, R>(
+        &mut self,
+        contract: ContractId,
+        fn_name: &str,
+        fn_arg: &A,
+    ) -> Result<CallReceipt<R>>
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible> + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.session.call(contract, fn_name, fn_arg, u64::MAX)
+    }
+
+    /// Call the token contract.
+    pub fn call_token<A, R>(&mut self, fn_name: &str, fn_arg: &A) -> Result<CallReceipt<R>>
+    where
+        A: for<'b> Serialize<StandardBufSerializer<'b>>,
+        A::Archived: for<'b> CheckBytes<DefaultValidator<'b>>,
+        R: Archive,
+        R::Archived: Deserialize<R, Infallible> + for<'b> CheckBytes<DefaultValidator<'b>>,
+    {
+        self.call(self.token, fn_name, fn_arg)
+    }
+
+    /// Submit a transfer.
+    pub fn transfer(&mut self, transfer: &Transfer) -> Result<CallReceipt<()>> {
+        self.call_token("transfer", transfer)
+    }
+
+    /// Submit a transfer spending an allowance.
+    pub fn transfer_from(&mut self, transfer: &TransferFrom) -> Result<CallReceipt<()>> {
+        self.call_token("transfer_from", transfer)
+    }
+
+    /// Submit an approval.
+    pub fn approve(&mut self, approve: &Approve) -> Result<CallReceipt<()>> {
+        self.call_token("approve", approve)
+    }
+
+    /// The balance and nonce of an account.
+    pub fn account(&mut self, account: impl Into<Account>) -> AccountInfo {
+        self.call_token("account", &account.into())
+            .expect("Querying an account should succeed")
+            .data
+    }
+
+    /// The balance of an account.
+    pub fn balance_of(&mut self, account: impl Into<Account>) -> u64 {
+        self.account(account).balance
+    }
+
+    /// How much of an allowance a spender has of the `owner` account.
+    pub fn allowance(&mut self, owner: impl Into<Account>, spender: impl Into<Account>) -> u64 {
+        self.call_token(
+            "allowance",
+            &Allowance {
+                owner: owner.into(),
+                spender: spender.into(),
+            },
+        )
+        .expect("Querying an allowance should succeed")
+        .data
+    }
+
+    /// The total supply of tokens.
+    pub fn total_supply(&mut self) -> u64 {
+        self.call_token("total_supply", &())
+            .expect("Querying the supply should succeed")
+            .data
+    }
+}
@@ -0,0 +1,273 @@
+//! Gas benchmarks for the entry points of the token contract.
+//!
+//! The gas spent by each entry point is measured with the token holding different numbers of
+//! accounts, and compared against a baseline kept per variant in `benches/baselines/`. The benchmark
+//! fails if any cost regresses beyond a threshold, given in percent by `GAS_REGRESSION_THRESHOLD`
+//! and 5% by default, or if the baseline is missing or doesn't cover every measurement.
+//!
+//! ```sh
+//! cargo bench --manifest-path=tests/Cargo.toml [--features=<variant>] [-- --save]
+//! ```
+//!
+//! Passing `--save` records the measurements as the new baseline instead.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::process::exit;
+
+use execution_core::signatures::bls::{PublicKey, SecretKey};
+use execution_core::ContractId;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use ttoken_test_utils::TokenSession;
+use ttoken_types::*;
+
+#[cfg(not(any(feature = "fees", feature = "compliance")))]
+const VARIANT: &str = "minimal";
+#[cfg(all(feature = "fees", not(feature = "compliance")))]
+const VARIANT: &str = "fees";
+#[cfg(all(feature = "compliance", not(feature = "fees")))]
+const VARIANT: &str = "compliance";
+#[cfg(all(feature = "fees", feature = "compliance"))]
+const VARIANT: &str = "full";
+
+#[cfg(not(any(feature = "fees", feature = "compliance")))]
+const TOKEN_BYTECODE: &[u8] = include_bytes!("../../build/ttoken_contract_minimal.wasm");
+#[cfg(all(feature = "fees", not(feature = "compliance")))]
+const TOKEN_BYTECODE: &[u8] = include_bytes!("../../build/ttoken_contract_fees.wasm");
+#[cfg(all(feature = "compliance", not(feature = "fees")))]
+const TOKEN_BYTECODE: &[u8] = include_bytes!("../../build/ttoken_contract_compliance.wasm");
+#[cfg(all(feature = "fees", feature = "compliance"))]
+const TOKEN_BYTECODE: &[u8] = include_bytes!("../../build/ttoken_contract_full.wasm");
+
+const TOKEN_ID: ContractId = ContractId::from_bytes([1; 32]);
+
+/// The numbers of accounts holding tokens the entry points are measured with.
+const HOLDER_COUNTS: &[u64] = &[10, 1_000, 100_000];
+
+/// The number of holders funded by each batch transfer, small enough for the batch to fit in the
+/// argument buffer.
+const FUNDING_BATCH_SIZE: u64 = 100;
+
+const INITIAL_BALANCE: u64 = u64::MAX / 2;
+const VALUE: u64 = 10;
+
+const DEFAULT_THRESHOLD: f64 = 5.0;
+
+/// The gas spent by each entry point, keyed by its name and the number of holders.
+type Measurements = BTreeMap<(String, u64), u64>;
+
+fn main() {
+    let save = env::args().any(|arg| arg == "--save");
+    let threshold = match env::var("GAS_REGRESSION_THRESHOLD") {
+        Ok(threshold) => threshold
+            .parse()
+            .expect("The regression threshold should be a number"),
+        Err(_) => DEFAULT_THRESHOLD,
+    };
+
+    let mut measurements = Measurements::new();
+    for &holder_count in HOLDER_COUNTS {
+        measure(holder_count, &mut measurements);
+    }
+
+    let path = baseline_path();
+    if save {
+        fs::create_dir_all(path.parent().unwrap()).expect("Creating the baseline directory");
+        fs::write(&path, format_measurements(&measurements)).expect("Writing the baseline");
+        println!("Saved the baseline to {}", path.display());
+        return;
+    }
+
+    let baseline = match fs::read_to_string(&path) {
+        Ok(baseline) => parse_measurements(&baseline),
+        // a missing baseline fails the benchmark, so the regression check can't be skipped
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            print!("{}", format_measurements(&measurements));
+            eprintln!(
+                "No baseline at {}, run with `--save` to record one",
+                path.display()
+            );
+            exit(1);
+        }
+        Err(err) => panic!("Reading the baseline failed: {err}"),
+    };
+
+    let mut regressed = false;
+    let mut missing = false;
+    for ((entry_point, holder_count), gas) in &measurements {
+        let key = (entry_point.clone(), *holder_count);
+
+        let Some(&base) = baseline.get(&key) else {
+            missing = true;
+            println!("{entry_point:<16} {holder_count:>8} {gas:>12} (not in baseline)");
+            continue;
+        };
+
+        let change = (*gas as f64 - base as f64) / base as f64 * 100.0;
+        let flag = if change > threshold {
+            regressed = true;
+            "REGRESSED"
+        } else {
+            ""
+        };
+        println!("{entry_point:<16} {holder_count:>8} {gas:>12} {change:>+8.2}% {flag}");
+    }
+
+    if missing {
+        eprintln!("The baseline doesn't cover every measurement, run with `--save` to record one");
+    }
+    if regressed {
+        eprintln!("Gas costs regressed by more than {threshold}%");
+    }
+    if missing || regressed {
+        exit(1);
+    }
+}
+
+fn baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("benches")
+        .join("baselines")
+        .join(format!("{VARIANT}.txt"))
+}
+
+/// Measure the gas spent by each entry point, with the token held by the given number of accounts.
+fn measure(holder_count: u64, measurements: &mut Measurements) {
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let spender_sk = SecretKey::random(&mut rng);
+    let spender_pk = PublicKey::from(&spender_sk);
+
+    let init_args = InitArgs {
+        balances: vec![(Account::External(pk), INITIAL_BALANCE)],
+        admin: Some(pk),
+        ..InitArgs::default()
+    };
+    let mut session = TokenSession::new(TOKEN_BYTECODE, TOKEN_ID, &init_args);
+
+    // external accounts are used, since transfers to contracts that don't exist are reverted. With
+    // the deploying account holding tokens, the last one is left unfunded, to measure transfers to
+    // new holders.
+    let holders: Vec<Account> = (0..holder_count)
+        .map(|_| Account::External(PublicKey::from(&SecretKey::random(&mut rng))))
+        .collect();
+
+    // the holders are funded in batches, since the initialization arguments must fit in the
+    // argument buffer
+    let mut nonce = 1;
+    let mut funded = 0;
+    while funded < holder_count - 1 {
+        let batch_size = FUNDING_BATCH_SIZE.min(holder_count - 1 - funded);
+
+        let entries = (funded..funded + batch_size)
+            .map(|index| {
+                let entry = BatchTransferEntry {
+                    from: pk,
+                    to: holders[index as usize],
+                    value: VALUE,
+                    nonce,
+                };
+                nonce += 1;
+                entry
+            })
+            .collect();
+
+        let mut batch = BatchTransfer::new(entries);
+        let signature = aggregate_signatures(&[batch.sign(&sk)]).unwrap();
+        batch.set_signature(signature);
+
+        session
+            .call_token::<_, ()>("batch_transfer", &batch)
+            .expect("Funding holders should succeed");
+
+        funded += batch_size;
+    }
+
+    let mut record = |entry_point: &str, gas_spent: u64| {
+        measurements.insert((entry_point.to_string(), holder_count), gas_spent);
+    };
+
+    let transfer = Transfer::new(&sk, holders[0], VALUE, nonce);
+    let receipt = session
+        .transfer(&transfer)
+        .expect("Transferring should succeed");
+    record("transfer", receipt.gas_spent);
+    nonce += 1;
+
+    let transfer = Transfer::new(&sk, holders[holder_count as usize - 1], VALUE, nonce);
+    let receipt = session
+        .transfer(&transfer)
+        .expect("Transferring should succeed");
+    record("transfer_new", receipt.gas_spent);
+    nonce += 1;
+
+    let approve = Approve::new(&sk, spender_pk, VALUE, nonce);
+    let receipt = session.approve(&approve).expect("Approving should succeed");
+    record("approve", receipt.gas_spent);
+
+    let transfer = TransferFrom::new(&spender_sk, pk, holders[0], VALUE, 1);
+    let receipt = session
+        .transfer_from(&transfer)
+        .expect("Transferring from should succeed");
+    record("transfer_from", receipt.gas_spent);
+
+    let receipt = session
+        .call_token::<_, AccountInfo>("account", &Account::External(pk))
+        .expect("Querying an account should succeed");
+    record("account", receipt.gas_spent);
+
+    let allowance = Allowance {
+        owner: Account::External(pk),
+        spender: Account::External(spender_pk),
+    };
+    let receipt = session
+        .call_token::<_, u64>("allowance", &allowance)
+        .expect("Querying an allowance should succeed");
+    record("allowance", receipt.gas_spent);
+
+    let receipt = session
+        .call_token::<_, u64>("total_supply", &())
+        .expect("Querying the supply should succeed");
+    record("total_supply", receipt.gas_spent);
+
+    let receipt = session
+        .call_token::<_, u64>("holder_count", &())
+        .expect("Querying the holder count should succeed");
+    record("holder_count", receipt.gas_spent);
+}
+
+fn format_measurements(measurements: &Measurements) -> String {
+    measurements
+        .iter()
+        .map(|((entry_point, holder_count), gas)| format!("{entry_point} {holder_count} {gas}\n"))
+        .collect()
+}
+
+fn parse_measurements(contents: &str) -> Measurements {
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts[..] {
+                [entry_point, holder_count, gas] => (
+                    (
+                        entry_point.to_string(),
+                        holder_count
+                            .parse()
+                            .expect("Invalid holder count in baseline"),
+                    ),
+                    gas.parse().expect("Invalid gas in baseline"),
+                ),
+                _ => panic!("Invalid baseline line: {line}"),
+            }
+        })
+        .collect()
+}
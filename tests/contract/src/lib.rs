@@ -2,14 +2,31 @@
 
 extern crate alloc;
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use dusk_bytes::Serializable;
+use execution_core::signatures::bls::{PublicKey, Signature};
 use execution_core::ContractId;
 
 use ttoken_types::*;
 
+/// Accept tokens, as a well-behaved contract would.
+const MODE_NORMAL: u8 = 0;
+/// Panic in `token_received`.
+const MODE_PANIC: u8 = 1;
+/// Consume all the gas given to `token_received`.
+const MODE_EXHAUST_GAS: u8 = 2;
+/// Call back into the token contract from `token_received`, sending the tokens back.
+const MODE_REENTER: u8 = 3;
+
 struct TokenState {
     this_contract: ContractId,
     token_contract: ContractId,
     balance: u64,
+    last_data: Vec<u8>,
+    mode: u8,
+    authorizer: Option<PublicKey>,
 }
 
 impl TokenState {
@@ -24,14 +41,22 @@ static mut STATE: TokenState = TokenState {
     this_contract: ContractId::from_bytes([0u8; 32]),
     token_contract: ContractId::from_bytes([0u8; 32]),
     balance: 0,
+    last_data: Vec::new(),
+    mode: MODE_NORMAL,
+    authorizer: None,
 };
 
 impl TokenState {
     fn token_send(&mut self, transfer: TransferFromContract) {
-        if let Err(err) =
-            rusk_abi::call::<_, ()>(self.token_contract, "transfer_from_contract", &transfer)
-        {
-            panic!("Failed sending tokens: {err}");
+        match rusk_abi::call::<_, Result<(), TransferFromContractError>>(
+            self.token_contract,
+            "transfer_from_contract",
+            &transfer,
+        ) {
+            Ok(Ok(())) => {}
+            // the transfer was already performed by a previous call
+            Ok(Err(TransferFromContractError::DuplicateIdempotencyKey(_))) => return,
+            Err(err) => panic!("Failed sending tokens: {err}"),
         }
 
         if transfer.from.is_none()
@@ -42,7 +67,141 @@ impl TokenState {
     }
 
     fn token_received(&mut self, transfer: TransferInfo) {
+        match self.mode {
+            MODE_PANIC => panic!("Refusing to receive tokens"),
+            MODE_EXHAUST_GAS => {
+                let mut n = 0u64;
+                loop {
+                    n = core::hint::black_box(n.wrapping_add(1));
+                }
+            }
+            _ => {}
+        }
+
         self.balance += transfer.value;
+        self.last_data = transfer.data;
+
+        if self.mode == MODE_REENTER {
+            self.token_send(TransferFromContract {
+                to: transfer.from,
+                from: None,
+                value: transfer.value,
+                hook_policy: None,
+                idempotency_key: None,
+                data: Vec::new(),
+            });
+        }
+    }
+
+    fn token_send_multi(&mut self, transfer: TokenTransferFromContract) {
+        if let Err(err) = rusk_abi::call::<_, ()>(
+            self.token_contract,
+            "token_transfer_from_contract",
+            &transfer,
+        ) {
+            panic!("Failed sending tokens: {err}");
+        }
+    }
+
+    /// Accepts tokens of created tokens, whose balances aren't tracked.
+    fn multi_token_received(&mut self, _transfer: TokenTransferInfo) {
+        if self.mode == MODE_PANIC {
+            panic!("Refusing to receive tokens");
+        }
+    }
+
+    fn set_mode(&mut self, mode: u8) {
+        self.mode = mode;
+    }
+
+    fn token_sent(&mut self, transfer: TransferSentInfo) {
+        self.balance -= transfer.value;
+    }
+
+    fn token_approve(&mut self, approve: ApproveFromContract) {
+        if let Err(err) =
+            rusk_abi::call::<_, ()>(self.token_contract, "approve_from_contract", &approve)
+        {
+            panic!("Failed approving: {err}");
+        }
+    }
+
+    fn token_register_sent_hook(&mut self, register: bool) {
+        if let Err(err) =
+            rusk_abi::call::<_, ()>(self.token_contract, "register_token_sent_hook", &register)
+        {
+            panic!("Failed registering the hook: {err}");
+        }
+    }
+
+    fn token_register_verifier(&mut self, authorizer: PublicKey) {
+        self.authorizer = Some(authorizer);
+
+        let verifier = Verifier {
+            contract: self.this_contract,
+            fn_name: String::from("verify_authorization"),
+        };
+        if let Err(err) =
+            rusk_abi::call::<_, ()>(self.token_contract, "register_verifier", &Some(verifier))
+        {
+            panic!("Failed registering the verifier: {err}");
+        }
+    }
+
+    /// Accepts authorizations that are signatures by the registered authorizer's key.
+    fn verify_authorization(&self, verify: VerifyAuthorization) -> bool {
+        let authorizer = match self.authorizer {
+            Some(authorizer) if verify.account == self.this_contract => authorizer,
+            _ => return false,
+        };
+
+        let bytes: [u8; Signature::SIZE] = match verify.authorization.try_into() {
+            Ok(bytes) => bytes,
+            Err(_) => return false,
+        };
+
+        match Signature::from_bytes(&bytes) {
+            Ok(sig) => rusk_abi::verify_bls(verify.message, authorizer, sig),
+            Err(_) => false,
+        }
+    }
+
+    /// Accepts any flash loan, repaying it from the tokens it holds.
+    fn on_flash_loan(&mut self, loan: FlashLoanInfo) -> bool {
+        if self.mode == MODE_PANIC {
+            panic!("Refusing the flash loan");
+        }
+
+        self.balance -= loan.fee;
+        true
+    }
+
+    fn balance(&self) -> u64 {
+        self.balance
+    }
+
+    fn last_data(&self) -> Vec<u8> {
+        self.last_data.clone()
+    }
+
+    fn token_approved(&mut self, approval: ApprovalInfo) {
+        // pull the approved tokens into this contract
+        let transfer = TransferFromContract {
+            to: Account::Contract(self.this_contract),
+            from: Some(approval.owner),
+            value: approval.value,
+            hook_policy: None,
+            idempotency_key: None,
+            data: Vec::new(),
+        };
+
+        if let Err(err) = rusk_abi::call::<_, Result<(), TransferFromContractError>>(
+            self.token_contract,
+            "transfer_from_contract",
+            &transfer,
+        ) {
+            panic!("Failed pulling tokens: {err}");
+        }
     }
 }
 
@@ -62,3 +221,63 @@ unsafe fn token_send(arg_len: u32) -> u32 {
 unsafe fn token_received(arg_len: u32) -> u32 {
     rusk_abi::wrap_call(arg_len, |arg| STATE.token_received(arg))
 }
+
+#[no_mangle]
+unsafe fn token_send_multi(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.token_send_multi(arg))
+}
+
+#[no_mangle]
+unsafe fn multi_token_received(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.multi_token_received(arg))
+}
+
+#[no_mangle]
+unsafe fn token_approved(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.token_approved(arg))
+}
+
+#[no_mangle]
+unsafe fn set_mode(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.set_mode(arg))
+}
+
+#[no_mangle]
+unsafe fn last_data(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.last_data())
+}
+
+#[no_mangle]
+unsafe fn token_sent(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.token_sent(arg))
+}
+
+#[no_mangle]
+unsafe fn token_approve(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.token_approve(arg))
+}
+
+#[no_mangle]
+unsafe fn token_register_sent_hook(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.token_register_sent_hook(arg))
+}
+
+#[no_mangle]
+unsafe fn token_register_verifier(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.token_register_verifier(arg))
+}
+
+#[no_mangle]
+unsafe fn verify_authorization(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.verify_authorization(arg))
+}
+
+#[no_mangle]
+unsafe fn on_flash_loan(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |arg| STATE.on_flash_loan(arg))
+}
+
+#[no_mangle]
+unsafe fn balance(arg_len: u32) -> u32 {
+    rusk_abi::wrap_call(arg_len, |_: ()| STATE.balance())
+}
@@ -1,40 +1,70 @@
-use execution_core::signatures::bls::{PublicKey, SecretKey};
+use dusk_bytes::Serializable;
+use execution_core::signatures::bls::{PublicKey, SecretKey, Signature};
 use execution_core::{ContractId, StandardBufSerializer};
-use rusk_abi::{CallReceipt, ContractData, PiecrustError, Session};
+use rusk_abi::{CallReceipt, PiecrustError};
 
 use bytecheck::CheckBytes;
 use rkyv::validation::validators::DefaultValidator;
-use rkyv::{Archive, Deserialize, Infallible, Serialize};
+use rkyv::{AlignedVec, Archive, Deserialize, Infallible, Serialize};
+
+use std::sync::mpsc;
 
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 
+use ttoken_client::{SessionBackend, TokenClient, TokenEvent};
+use ttoken_test_utils::{
+    assert_approve, assert_transfer, decode_events, token_events, TokenSession,
+};
 use ttoken_types::*;
 
-const TOKEN_BYTECODE: &[u8] = include_bytes!("../../build/ttoken_contract.wasm");
+#[cfg(not(any(feature = "fees", feature = "compliance")))]
+const TOKEN_BYTECODE: &[u8] = include_bytes!("../../build/ttoken_contract_minimal.wasm");
+#[cfg(all(feature = "fees", not(feature = "compliance")))]
+const TOKEN_BYTECODE: &[u8] = include_bytes!("../../build/ttoken_contract_fees.wasm");
+#[cfg(all(feature = "compliance", not(feature = "fees")))]
+const TOKEN_BYTECODE: &[u8] = include_bytes!("../../build/ttoken_contract_compliance.wasm");
+#[cfg(all(feature = "fees", feature = "compliance"))]
+const TOKEN_BYTECODE: &[u8] = include_bytes!("../../build/ttoken_contract_full.wasm");
 const HOLDER_BYTECODE: &[u8] = include_bytes!("../../build/ttoken_test_contract.wasm");
 
 const TOKEN_ID: ContractId = ContractId::from_bytes([1; 32]);
 const HOLDER_ID: ContractId = ContractId::from_bytes([2; 32]);
+const MISSING_ID: ContractId = ContractId::from_bytes([3; 32]);
 
 const INITIAL_BALANCE: u64 = 1000;
 const INITIAL_HOLDER_BALANCE: u64 = 1000;
 
-const OWNER: [u8; 64] = [0u8; 64];
+/// A gas limit low enough for a holder consuming all gas to run out quickly, but enough for a
+/// transfer to succeed.
+const GAS_LIMIT: u64 = 1_000_000_000;
+
+/// How the holder contract behaves when receiving tokens. Mirrors the modes in the holder contract.
+#[derive(Debug, Clone, Copy)]
+#[repr(u8)]
+enum HolderMode {
+    Normal = 0,
+    Panic = 1,
+    ExhaustGas = 2,
+    Reenter = 3,
+}
 
 type Result<T, Error = PiecrustError> = std::result::Result<T, Error>;
 
 struct ContractSession {
     deploy_pk: PublicKey,
     deploy_sk: SecretKey,
-    session: Session,
+    token: TokenSession,
 }
 
 impl ContractSession {
     fn new() -> Self {
-        let vm = rusk_abi::new_ephemeral_vm().expect("Creating VM should succeed");
-        let mut session = rusk_abi::new_genesis_session(&vm);
+        Self::new_with(|_| {})
+    }
 
+    /// Deploys the contracts, with the token initialized with arguments modified by the given
+    /// closure.
+    fn new_with(configure: impl FnOnce(&mut InitArgs)) -> Self {
         let mut rng = StdRng::seed_from_u64(0xF0CACC1A);
         let deploy_sk = SecretKey::random(&mut rng);
         let deploy_pk = PublicKey::from(&deploy_sk);
@@ -42,35 +72,27 @@ impl ContractSession {
         let deploy_account = Account::External(deploy_pk);
         let holder_account = Account::Contract(HOLDER_ID);
 
-        session
-            .deploy(
-                TOKEN_BYTECODE,
-                ContractData::builder()
-                    .owner(OWNER)
-                    .constructor_arg(&vec![
-                        (deploy_account, INITIAL_BALANCE),
-                        (holder_account, INITIAL_HOLDER_BALANCE),
-                    ])
-                    .contract_id(TOKEN_ID),
-                u64::MAX,
-            )
-            .expect("Deploying the token contract should succeed");
+        let mut init_args = InitArgs {
+            balances: vec![
+                (deploy_account, INITIAL_BALANCE),
+                (holder_account, INITIAL_HOLDER_BALANCE),
+            ],
+            admin: Some(deploy_pk),
+            ..InitArgs::default()
+        };
+        configure(&mut init_args);
 
-        session
-            .deploy(
-                HOLDER_BYTECODE,
-                ContractData::builder()
-                    .owner(OWNER)
-                    .constructor_arg(&(TOKEN_ID, INITIAL_HOLDER_BALANCE))
-                    .contract_id(HOLDER_ID),
-                u64::MAX,
-            )
-            .expect("Deploying the holder contract should succeed");
+        let mut token = TokenSession::new(TOKEN_BYTECODE, TOKEN_ID, &init_args);
+        token.deploy(
+            HOLDER_BYTECODE,
+            HOLDER_ID,
+            &(TOKEN_ID, INITIAL_HOLDER_BALANCE),
+        );
 
         Self {
             deploy_sk,
             deploy_pk,
-            session,
+            token,
         }
     }
 
@@ -78,6 +100,11 @@ impl ContractSession {
         self.deploy_pk
     }
 
+    /// Commits the current session, and continues from a new one at the given block height.
+    fn set_block_height(&mut self, block_height: u64) {
+        self.token.set_block_height(block_height);
+    }
+
     fn call_token<A, R>(&mut self, fn_name: &str, fn_arg: &A) -> Result<CallReceipt<R>>
     where
         A: for<'b> Serialize<StandardBufSerializer<'b>>,
@@ -85,7 +112,7 @@ impl ContractSession {
         R: Archive,
         R::Archived: Deserialize<R, Infallible> + for<'b> CheckBytes<DefaultValidator<'b>>,
     {
-        self.session.call(TOKEN_ID, fn_name, fn_arg, u64::MAX)
+        self.token.call_token(fn_name, fn_arg)
     }
 
     fn call_holder<A, R>(&mut self, fn_name: &str, fn_arg: &A) -> Result<CallReceipt<R>>
@@ -95,25 +122,54 @@ impl ContractSession {
         R: Archive,
         R::Archived: Deserialize<R, Infallible> + for<'b> CheckBytes<DefaultValidator<'b>>,
     {
-        self.session.call(HOLDER_ID, fn_name, fn_arg, u64::MAX)
+        self.token.call(HOLDER_ID, fn_name, fn_arg)
     }
 
     fn account(&mut self, account: impl Into<Account>) -> AccountInfo {
-        self.call_token("account", &account.into())
-            .expect("Querying an account should succeed")
+        self.token.account(account)
+    }
+
+    fn set_holder_mode(&mut self, mode: HolderMode) {
+        self.call_holder::<_, ()>("set_mode", &(mode as u8))
+            .expect("Setting the holder mode should succeed");
+    }
+
+    /// Asserts that the balances of the deployed account and the holder contract, and the
+    /// nonces used by the deployed account, are still as they were at deployment.
+    fn assert_untouched(&mut self) {
+        let deploy_info = self.account(self.deploy_pk());
+        assert_eq!(
+            deploy_info.balance, INITIAL_BALANCE,
+            "The deployed account should keep its balance"
+        );
+        assert_eq!(
+            deploy_info.nonce, 0,
+            "The deployed account should not have used a nonce"
+        );
+        assert_eq!(
+            self.account(HOLDER_ID).balance,
+            INITIAL_HOLDER_BALANCE,
+            "The holder contract should keep its balance"
+        );
+
+        let holder_balance: u64 = self
+            .call_holder("balance", &())
+            .expect("Querying the holder's balance should succeed")
+            .data;
+        assert_eq!(
+            holder_balance, INITIAL_HOLDER_BALANCE,
+            "The holder contract's own records should be rolled back"
+        );
+    }
+
+    fn recoverable(&mut self, account: impl Into<Account>) -> u64 {
+        self.call_token("recoverable", &account.into())
+            .expect("Querying a recovery bucket should succeed")
             .data
     }
 
     fn allowance(&mut self, owner: impl Into<Account>, spender: impl Into<Account>) -> u64 {
-        self.call_token(
-            "allowance",
-            &Allowance {
-                owner: owner.into(),
-                spender: spender.into(),
-            },
-        )
-        .expect("Querying an allowance should succeed")
-        .data
+        self.token.allowance(owner, spender)
     }
 }
 
@@ -122,6 +178,50 @@ fn deploy() {
     ContractSession::new();
 }
 
+#[test]
+fn features() {
+    let mut session = ContractSession::new();
+
+    let features: Vec<String> = session
+        .call_token("features", &())
+        .expect("Querying the features should succeed")
+        .data;
+
+    assert_eq!(
+        features.contains(&String::from("fees")),
+        cfg!(feature = "fees"),
+        "The contract should report whether it supports fees"
+    );
+    assert_eq!(
+        features.contains(&String::from("compliance")),
+        cfg!(feature = "compliance"),
+        "The contract should report whether it supports compliance"
+    );
+
+    let mut supports = |feature: FeatureId| -> bool {
+        session
+            .call_token("supports", &feature)
+            .expect("Querying a feature should succeed")
+            .data
+    };
+    assert_eq!(supports(FeatureId::Fees), cfg!(feature = "fees"));
+    assert_eq!(
+        supports(FeatureId::Compliance),
+        cfg!(feature = "compliance")
+    );
+    assert!(supports(FeatureId::Snapshots));
+    assert!(
+        !supports(FeatureId::Wrapped),
+        "The deployment should not be wrapped"
+    );
+
+    let spec_version: u32 = session
+        .call_token("spec_version", &())
+        .expect("Querying the spec version should succeed")
+        .data;
+    assert_eq!(spec_version, SPEC_VERSION);
+}
+
 #[test]
 fn empty_account() {
     let mut session = ContractSession::new();
@@ -160,10 +260,19 @@ fn transfer() {
     );
 
     let transfer = Transfer::new(&session.deploy_sk, pk, TRANSFERRED_AMOUNT, 1);
-    session
-        .call_token::<_, ()>("transfer", &transfer)
+    let receipt = session
+        .token
+        .transfer(&transfer)
         .expect("Transferring should succeed");
 
+    assert_transfer(
+        &receipt,
+        TOKEN_ID,
+        session.deploy_pk(),
+        pk,
+        TRANSFERRED_AMOUNT,
+    );
+
     assert_eq!(
         session.account(session.deploy_pk()).balance,
         INITIAL_BALANCE - TRANSFERRED_AMOUNT,
@@ -210,6 +319,34 @@ fn transfer_to_contract() {
     );
 }
 
+#[test]
+fn transfer_with_data() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const DATA: [u8; 4] = [0xDE, 0xAD, 0xBE, 0xEF];
+
+    let mut session = ContractSession::new();
+
+    let transfer = Transfer::new_with_data(
+        &session.deploy_sk,
+        HOLDER_ID,
+        TRANSFERRED_AMOUNT,
+        1,
+        DATA.to_vec(),
+    );
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    let data: Vec<u8> = session
+        .call_holder("last_data", &())
+        .expect("Querying the last received data should succeed")
+        .data;
+    assert_eq!(
+        data, DATA,
+        "The contract transferred to should receive the data"
+    );
+}
+
 #[test]
 fn transfer_from_contract() {
     const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
@@ -231,6 +368,9 @@ fn transfer_from_contract() {
         to: Account::External(session.deploy_pk()),
         from: None,
         value: TRANSFERRED_AMOUNT,
+        hook_policy: None,
+        idempotency_key: None,
+        data: Vec::new(),
     };
     session
         .call_holder::<_, ()>("token_send", &transfer)
@@ -248,6 +388,56 @@ fn transfer_from_contract() {
     );
 }
 
+#[test]
+fn transfer_from_contract_idempotent() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const IDEMPOTENCY_KEY: u64 = 42;
+
+    let mut session = ContractSession::new();
+
+    let key = IdempotencyKey {
+        contract: HOLDER_ID,
+        key: IDEMPOTENCY_KEY,
+    };
+    let used: bool = session
+        .call_token("idempotency_key_used", &key)
+        .expect("Querying an idempotency key should succeed")
+        .data;
+    assert!(!used, "The idempotency key should not be used yet");
+
+    let transfer = TransferFromContract {
+        to: Account::External(session.deploy_pk()),
+        from: None,
+        value: TRANSFERRED_AMOUNT,
+        hook_policy: None,
+        idempotency_key: Some(IDEMPOTENCY_KEY),
+        data: Vec::new(),
+    };
+    session
+        .call_holder::<_, ()>("token_send", &transfer)
+        .expect("Transferring should succeed");
+    session
+        .call_holder::<_, ()>("token_send", &transfer)
+        .expect("Retrying the transfer should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE + TRANSFERRED_AMOUNT,
+        "The deployed account should have the transferred amount added only once"
+    );
+    assert_eq!(
+        session.account(HOLDER_ID).balance,
+        INITIAL_HOLDER_BALANCE - TRANSFERRED_AMOUNT,
+        "The contract should have the transferred amount subtracted only once"
+    );
+
+    let used: bool = session
+        .call_token("idempotency_key_used", &key)
+        .expect("Querying an idempotency key should succeed")
+        .data;
+    assert!(used, "The idempotency key should be used");
+}
+
 #[test]
 fn approve() {
     const APPROVED_AMOUNT: u64 = INITIAL_BALANCE / 2;
@@ -265,10 +455,12 @@ fn approve() {
     );
 
     let approve = Approve::new(&session.deploy_sk, pk, APPROVED_AMOUNT, 1);
-    session
-        .call_token::<_, ()>("approve", &approve)
+    let receipt = session
+        .token
+        .approve(&approve)
         .expect("Approving should succeed");
 
+    assert_approve(&receipt, TOKEN_ID, session.deploy_pk(), pk, APPROVED_AMOUNT);
     assert_eq!(
         session.allowance(session.deploy_pk(), pk),
         APPROVED_AMOUNT,
@@ -336,6 +528,4948 @@ fn transfer_from() {
     );
 }
 
+#[test]
+fn transfer_hook_failure_reverts() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let transfer = Transfer::new(&session.deploy_sk, MISSING_ID, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring to a contract without a hook should fail");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE,
+        "The deployed account should keep its balance"
+    );
+    assert_eq!(
+        session.account(MISSING_ID).balance,
+        0,
+        "The contract transferred to should not be credited"
+    );
+}
+
+#[test]
+fn transfer_hook_failure_credits() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let transfer = Transfer::new_with_hook_policy(
+        &session.deploy_sk,
+        MISSING_ID,
+        TRANSFERRED_AMOUNT,
+        1,
+        Some(HookPolicy::Credit),
+    );
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - TRANSFERRED_AMOUNT,
+        "The deployed account should have the transferred amount subtracted"
+    );
+    assert_eq!(
+        session.account(MISSING_ID).balance,
+        TRANSFERRED_AMOUNT,
+        "The contract transferred to should be credited despite its hook failing"
+    );
+}
+
+#[test]
+fn transfer_hook_failure_recovers() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let transfer = Transfer::new_with_hook_policy(
+        &session.deploy_sk,
+        MISSING_ID,
+        TRANSFERRED_AMOUNT,
+        1,
+        Some(HookPolicy::Recover),
+    );
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    assert_eq!(
+        session.account(MISSING_ID).balance,
+        0,
+        "The contract transferred to should not be credited"
+    );
+    assert_eq!(
+        session.recoverable(session.deploy_pk()),
+        TRANSFERRED_AMOUNT,
+        "The transferred amount should be in the recovery bucket of the sender"
+    );
+
+    let claim = Signed::new(
+        &session.deploy_sk,
+        ClaimRecovery {
+            value: TRANSFERRED_AMOUNT,
+        },
+        2,
+    );
+    session
+        .call_token::<_, ()>("claim_recovery", &claim)
+        .expect("Claiming the recovered tokens should succeed");
+
+    assert_eq!(
+        session.recoverable(session.deploy_pk()),
+        0,
+        "The recovery bucket should be empty after claiming"
+    );
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE,
+        "The deployed account should have its balance restored"
+    );
+}
+
+#[test]
+fn transfer_to_panicking_holder() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+    session.set_holder_mode(HolderMode::Panic);
+
+    let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring to a panicking holder should fail");
+
+    session.assert_untouched();
+}
+
+#[test]
+fn transfer_to_gas_exhausting_holder() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+    session.set_holder_mode(HolderMode::ExhaustGas);
+
+    // the hook consumes the gas of the whole call, so the failure can't be handled by any policy
+    for hook_policy in [HookPolicy::Revert, HookPolicy::Credit, HookPolicy::Recover] {
+        let transfer = Transfer::new_with_hook_policy(
+            &session.deploy_sk,
+            HOLDER_ID,
+            TRANSFERRED_AMOUNT,
+            1,
+            Some(hook_policy),
+        );
+        session
+            .token
+            .session()
+            .call::<_, ()>(TOKEN_ID, "transfer", &transfer, GAS_LIMIT)
+            .expect_err("Transferring to a gas exhausting holder should fail");
+
+        session.assert_untouched();
+    }
+
+    session.set_holder_mode(HolderMode::Normal);
+
+    let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, TRANSFERRED_AMOUNT, 1);
+    session
+        .token
+        .session()
+        .call::<_, ()>(TOKEN_ID, "transfer", &transfer, GAS_LIMIT)
+        .expect("The gas limit should suffice for transferring to a well-behaved holder");
+}
+
+#[test]
+fn transfer_to_reentering_holder() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+    session.set_holder_mode(HolderMode::Reenter);
+
+    let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring to a reentering holder should fail");
+
+    session.assert_untouched();
+
+    // crediting regardless of the hook failing, the reentrant call is still rejected
+    let transfer = Transfer::new_with_hook_policy(
+        &session.deploy_sk,
+        HOLDER_ID,
+        TRANSFERRED_AMOUNT,
+        1,
+        Some(HookPolicy::Credit),
+    );
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring with the credit policy should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - TRANSFERRED_AMOUNT,
+        "The tokens should not be sent back to the deployed account"
+    );
+    assert_eq!(
+        session.account(HOLDER_ID).balance,
+        INITIAL_HOLDER_BALANCE + TRANSFERRED_AMOUNT,
+        "The holder contract should be credited"
+    );
+}
+
+#[test]
+fn snapshot() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let snapshot = Signed::new(&session.deploy_sk, Snapshot, 1);
+    let snapshot_id = session
+        .call_token::<_, u64>("snapshot", &snapshot)
+        .expect("Taking a snapshot should succeed")
+        .data;
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, TRANSFERRED_AMOUNT, 2);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    let balance_at = |session: &mut ContractSession, account: Account| -> u64 {
+        session
+            .call_token(
+                "balance_of_at",
+                &BalanceAt {
+                    account,
+                    snapshot_id,
+                },
+            )
+            .expect("Querying a balance at a snapshot should succeed")
+            .data
+    };
+
+    let deploy_account = Account::External(session.deploy_pk());
+    assert_eq!(
+        balance_at(&mut session, deploy_account),
+        INITIAL_BALANCE,
+        "The deployed account should have the initial balance at the snapshot"
+    );
+    assert_eq!(
+        balance_at(&mut session, Account::External(pk)),
+        0,
+        "The account transferred to should have no balance at the snapshot"
+    );
+    assert_eq!(
+        session.account(pk).balance,
+        TRANSFERRED_AMOUNT,
+        "The account transferred to should currently have the transferred amount"
+    );
+
+    let supply: u64 = session
+        .call_token("total_supply_at", &snapshot_id)
+        .expect("Querying the supply at a snapshot should succeed")
+        .data;
+    assert_eq!(
+        supply,
+        INITIAL_BALANCE + INITIAL_HOLDER_BALANCE,
+        "The supply at the snapshot should be the initial supply"
+    );
+
+    let snapshot = Signed::new(&sk, Snapshot, 1);
+    session
+        .call_token::<_, u64>("snapshot", &snapshot)
+        .expect_err("Only the admin should be able to take snapshots");
+}
+
+#[test]
+fn vesting() {
+    const VESTED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const DURATION: u64 = 100;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let overflowing = Signed::new(
+        &session.deploy_sk,
+        CreateVesting {
+            beneficiary: Account::External(pk),
+            total: VESTED_AMOUNT,
+            start_height: u64::MAX - DURATION / 2,
+            cliff: DURATION / 10,
+            duration: DURATION,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("create_vesting", &overflowing)
+        .expect_err("Creating a vesting schedule ending past the largest height should fail");
+
+    let create = Signed::new(
+        &session.deploy_sk,
+        CreateVesting {
+            beneficiary: Account::External(pk),
+            total: VESTED_AMOUNT,
+            start_height: 0,
+            cliff: DURATION / 10,
+            duration: DURATION,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("create_vesting", &create)
+        .expect("Creating a vesting schedule should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - VESTED_AMOUNT,
+        "The admin should have the vested amount locked"
+    );
+
+    let claim = Signed::new(&sk, ClaimVested, 1);
+    session
+        .call_token::<_, u64>("claim_vested", &claim)
+        .expect_err("Claiming before the cliff should fail");
+
+    session.set_block_height(DURATION / 2);
+
+    let claim = Signed::new(&sk, ClaimVested, 1);
+    let claimed = session
+        .call_token::<_, u64>("claim_vested", &claim)
+        .expect("Claiming vested tokens should succeed")
+        .data;
+
+    assert_eq!(
+        claimed,
+        VESTED_AMOUNT / 2,
+        "Half of the tokens should be vested halfway through"
+    );
+    assert_eq!(
+        session.account(pk).balance,
+        VESTED_AMOUNT / 2,
+        "The beneficiary should have the claimed amount"
+    );
+
+    let vesting: Option<Vesting> = session
+        .call_token("vesting_of", &Account::External(pk))
+        .expect("Querying a vesting schedule should succeed")
+        .data;
+    assert_eq!(
+        vesting.map(|vesting| vesting.claimed),
+        Some(VESTED_AMOUNT / 2),
+        "The vesting schedule should record the claimed amount"
+    );
+}
+
+#[test]
+fn transfer_locked() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const RELEASE_HEIGHT: u64 = 10;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let transfer = Signed::new(
+        &session.deploy_sk,
+        TransferLocked {
+            to: Account::External(pk),
+            value: TRANSFERRED_AMOUNT,
+            release_height: RELEASE_HEIGHT,
+        },
+        1,
+    );
+    let id = session
+        .call_token::<_, u64>("transfer_locked", &transfer)
+        .expect("Making a locked transfer should succeed")
+        .data;
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - TRANSFERRED_AMOUNT,
+        "The deployed account should have the transferred amount subtracted"
+    );
+
+    let claim = Signed::new(&sk, ClaimLocked { id }, 1);
+    session
+        .call_token::<_, ()>("claim_locked", &claim)
+        .expect_err("Claiming before the release height should fail");
+
+    session.set_block_height(RELEASE_HEIGHT);
+
+    let cancel = Signed::new(&session.deploy_sk, CancelLocked { id }, 2);
+    session
+        .call_token::<_, ()>("cancel_locked", &cancel)
+        .expect_err("Cancelling after the release height should fail");
+
+    session
+        .call_token::<_, ()>("claim_locked", &claim)
+        .expect("Claiming after the release height should succeed");
+
+    assert_eq!(
+        session.account(pk).balance,
+        TRANSFERRED_AMOUNT,
+        "The recipient should have the transferred amount"
+    );
+}
+
+#[test]
+fn cancel_locked() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let transfer = Signed::new(
+        &session.deploy_sk,
+        TransferLocked {
+            to: Account::External(pk),
+            value: TRANSFERRED_AMOUNT,
+            release_height: 10,
+        },
+        1,
+    );
+    let id = session
+        .call_token::<_, u64>("transfer_locked", &transfer)
+        .expect("Making a locked transfer should succeed")
+        .data;
+
+    let cancel = Signed::new(&session.deploy_sk, CancelLocked { id }, 2);
+    session
+        .call_token::<_, ()>("cancel_locked", &cancel)
+        .expect("Cancelling before the release height should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE,
+        "The deployed account should have its balance restored"
+    );
+
+    let locked: Option<LockedTransfer> = session
+        .call_token("locked_transfer", &id)
+        .expect("Querying a locked transfer should succeed")
+        .data;
+    assert_eq!(locked, None, "The locked transfer should be removed");
+}
+
+#[test]
+fn multisig_transfer() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sks: Vec<SecretKey> = (0..3).map(|_| SecretKey::random(&mut rng)).collect();
+    let keys: Vec<PublicKey> = sks.iter().map(PublicKey::from).collect();
+
+    let repeated = vec![keys[0], keys[0], keys[1]];
+    session
+        .call_token::<_, u64>(
+            "register_multisig",
+            &Multisig {
+                keys: repeated,
+                threshold: 2,
+            },
+        )
+        .expect_err("Registering a multisig account with a repeated key should fail");
+
+    let id = session
+        .call_token::<_, u64>("register_multisig", &Multisig { keys, threshold: 2 })
+        .expect("Registering a multisig account should succeed")
+        .data;
+    let multisig = Account::Multisig(id);
+
+    let transfer = Transfer::new(&session.deploy_sk, multisig, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring to a multisig account should succeed");
+
+    let mut transfer = MultisigTransfer::new(id, session.deploy_pk(), TRANSFERRED_AMOUNT, 1);
+    transfer.sign(0, &sks[0]);
+    session
+        .call_token::<_, ()>("multisig_transfer", &transfer)
+        .expect_err("Transferring without reaching the threshold should fail");
+
+    transfer.sign(2, &sks[2]);
+    session
+        .call_token::<_, ()>("multisig_transfer", &transfer)
+        .expect("Transferring after reaching the threshold should succeed");
+
+    assert_eq!(
+        session.account(multisig).balance,
+        0,
+        "The multisig account should have the transferred amount subtracted"
+    );
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE,
+        "The deployed account should have its balance restored"
+    );
+}
+
+#[test]
+fn batch_transfer() {
+    const FUNDED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const TRANSFERRED_AMOUNT: u64 = FUNDED_AMOUNT / 2;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let receiver_sk = SecretKey::random(&mut rng);
+    let receiver_pk = PublicKey::from(&receiver_sk);
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, FUNDED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    let mut batch = BatchTransfer::new(vec![
+        BatchTransferEntry {
+            from: session.deploy_pk(),
+            to: Account::External(receiver_pk),
+            value: TRANSFERRED_AMOUNT,
+            nonce: 2,
+        },
+        BatchTransferEntry {
+            from: pk,
+            to: Account::External(receiver_pk),
+            value: TRANSFERRED_AMOUNT,
+            nonce: 1,
+        },
+    ]);
+
+    let sigs = [batch.sign(&session.deploy_sk), batch.sign(&sk)];
+    batch.set_signature(aggregate_signatures(&sigs[..1]).unwrap());
+    session
+        .call_token::<_, ()>("batch_transfer", &batch)
+        .expect_err("A batch not signed by all senders should fail");
+
+    batch.set_signature(aggregate_signatures(&sigs).unwrap());
+    session
+        .call_token::<_, ()>("batch_transfer", &batch)
+        .expect("A batch signed by all senders should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - FUNDED_AMOUNT - TRANSFERRED_AMOUNT,
+        "The deployed account should have the transferred amounts subtracted"
+    );
+    assert_eq!(
+        session.account(pk).balance,
+        FUNDED_AMOUNT - TRANSFERRED_AMOUNT,
+        "The funded account should have the transferred amount subtracted"
+    );
+    assert_eq!(
+        session.account(receiver_pk).balance,
+        2 * TRANSFERRED_AMOUNT,
+        "The receiving account should have both transferred amounts"
+    );
+}
+
+#[test]
+fn batch_transfer_from() {
+    const FUNDED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const TRANSFERRED_AMOUNT: u64 = FUNDED_AMOUNT / 2;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let spender_sk = SecretKey::random(&mut rng);
+    let spender_pk = PublicKey::from(&spender_sk);
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, FUNDED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    let approve = Approve::new(&session.deploy_sk, spender_pk, TRANSFERRED_AMOUNT, 2);
+    session
+        .call_token::<_, ()>("approve", &approve)
+        .expect("Approving should succeed");
+    let approve = Approve::new(&sk, spender_pk, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("approve", &approve)
+        .expect("Approving should succeed");
+
+    let entries = vec![
+        BatchTransferFromEntry {
+            owner: Account::External(session.deploy_pk()),
+            to: Account::External(spender_pk),
+            value: TRANSFERRED_AMOUNT,
+        },
+        BatchTransferFromEntry {
+            owner: Account::External(pk),
+            to: Account::External(spender_pk),
+            value: TRANSFERRED_AMOUNT,
+        },
+    ];
+
+    // the second transfer exceeds the allowance, so the first must be reverted as well
+    let mut exceeding = entries.clone();
+    exceeding[1].value += 1;
+    let batch = BatchTransferFrom {
+        transfers: exceeding,
+    };
+    session
+        .call_token::<_, ()>("batch_transfer_from", &Signed::new(&spender_sk, batch, 1))
+        .expect_err("A batch exceeding an allowance should fail");
+
+    let batch = BatchTransferFrom { transfers: entries };
+    let receipt = session
+        .call_token::<_, ()>("batch_transfer_from", &Signed::new(&spender_sk, batch, 1))
+        .expect("A batch within the allowances should succeed");
+
+    let event = assert_transfer(
+        &receipt,
+        TOKEN_ID,
+        session.deploy_pk(),
+        spender_pk,
+        TRANSFERRED_AMOUNT,
+    );
+    assert_eq!(event.spender, Some(Account::External(spender_pk)));
+    assert_transfer(&receipt, TOKEN_ID, pk, spender_pk, TRANSFERRED_AMOUNT);
+
+    assert_eq!(
+        session.account(spender_pk).balance,
+        2 * TRANSFERRED_AMOUNT,
+        "The receiving account should have both transferred amounts"
+    );
+    assert_eq!(
+        session.allowance(pk, spender_pk),
+        0,
+        "The allowances should be spent"
+    );
+}
+
+#[cfg(feature = "fees")]
+#[test]
+fn relayed_transfer() {
+    const BURN_RATE: u16 = BURN_RATE_SCALE / 10;
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const BURNED_AMOUNT: u64 = TRANSFERRED_AMOUNT / 10;
+    const FEE: u64 = 10;
+
+    let mut session = ContractSession::new_with(|args| args.burn_rate = BURN_RATE);
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let relayer_sk = SecretKey::random(&mut rng);
+    let relayer_pk = PublicKey::from(&relayer_sk);
+
+    let set = SetMinTransfer {
+        min_transfer: 2 * FEE,
+    };
+    session
+        .call_token::<_, ()>("set_min_transfer", &Signed::new(&session.deploy_sk, set, 1))
+        .expect("Setting the minimum transfer should succeed");
+
+    let transfer = Signed::new(
+        &session.deploy_sk,
+        RelayedTransfer {
+            to: Account::External(pk),
+            value: TRANSFERRED_AMOUNT,
+            fee: FEE,
+            relayer: Account::External(relayer_pk),
+        },
+        2,
+    );
+    session
+        .call_token::<_, ()>("relayed_transfer", &transfer)
+        .expect("Relaying a transfer with a fee below the minimum transfer should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - TRANSFERRED_AMOUNT - FEE,
+        "The deployed account should have the transferred amount and the fee subtracted"
+    );
+    assert_eq!(
+        session.account(pk).balance,
+        TRANSFERRED_AMOUNT - BURNED_AMOUNT,
+        "The account transferred to should have the transferred amount, less the burn"
+    );
+    assert_eq!(
+        session.account(relayer_pk).balance,
+        FEE,
+        "The relayer should have the full fee, without any burn"
+    );
+}
+
+#[test]
+fn approve_and_call() {
+    const APPROVED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let approve = Signed::new(
+        &session.deploy_sk,
+        ApproveAndCall {
+            spender: HOLDER_ID,
+            value: APPROVED_AMOUNT,
+            fn_name: String::from("token_approved"),
+            data: Vec::new(),
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("approve_and_call", &approve)
+        .expect("Approving and calling should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - APPROVED_AMOUNT,
+        "The deployed account should have the pulled amount subtracted"
+    );
+    assert_eq!(
+        session.account(HOLDER_ID).balance,
+        INITIAL_HOLDER_BALANCE + APPROVED_AMOUNT,
+        "The called contract should have pulled the approved amount"
+    );
+    assert_eq!(
+        session.allowance(session.deploy_pk(), HOLDER_ID),
+        0,
+        "The allowance should be spent"
+    );
+
+    let approve = Signed::new(
+        &session.deploy_sk,
+        ApproveAndCall {
+            spender: HOLDER_ID,
+            value: APPROVED_AMOUNT,
+            fn_name: String::from("missing_function"),
+            data: Vec::new(),
+        },
+        2,
+    );
+    session
+        .call_token::<_, ()>("approve_and_call", &approve)
+        .expect_err("Approving and calling a missing function should fail");
+
+    assert_eq!(
+        session.allowance(session.deploy_pk(), HOLDER_ID),
+        0,
+        "The allowance should be reverted when the call fails"
+    );
+}
+
+#[test]
+fn transfer_from_contract_owner() {
+    const APPROVED_AMOUNT: u64 = INITIAL_HOLDER_BALANCE / 2;
+    const TRANSFERRED_AMOUNT: u64 = APPROVED_AMOUNT / 2;
+
+    let mut session = ContractSession::new();
+
+    session
+        .call_holder::<_, ()>("token_register_sent_hook", &true)
+        .expect("Registering the hook should succeed");
+
+    let approve = ApproveFromContract {
+        spender: Account::External(session.deploy_pk()),
+        value: APPROVED_AMOUNT,
+    };
+    session
+        .call_holder::<_, ()>("token_approve", &approve)
+        .expect("Approving should succeed");
+
+    let transfer_from = TransferFrom::new(
+        &session.deploy_sk,
+        HOLDER_ID,
+        session.deploy_pk(),
+        TRANSFERRED_AMOUNT,
+        1,
+    );
+    session
+        .call_token::<_, ()>("transfer_from", &transfer_from)
+        .expect("Transferring from a contract should succeed");
+
+    assert_eq!(
+        session.account(HOLDER_ID).balance,
+        INITIAL_HOLDER_BALANCE - TRANSFERRED_AMOUNT,
+        "The contract should have the transferred amount subtracted"
+    );
+
+    let holder_balance: u64 = session
+        .call_holder("balance", &())
+        .expect("Querying the holder's balance should succeed")
+        .data;
+    assert_eq!(
+        holder_balance,
+        INITIAL_HOLDER_BALANCE - TRANSFERRED_AMOUNT,
+        "The contract should have been notified of the transfer"
+    );
+}
+
+#[test]
+fn operator_transfer() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 4;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let operator_sk = SecretKey::random(&mut rng);
+    let operator = Account::External(PublicKey::from(&operator_sk));
+    let owner = Account::External(session.deploy_pk());
+
+    let transfer = Signed::new(
+        &operator_sk,
+        OperatorTransfer {
+            from: owner,
+            to: operator,
+            value: TRANSFERRED_AMOUNT,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("operator_transfer", &transfer)
+        .expect_err("Transferring without being an operator should fail");
+
+    let authorize = Signed::new(&session.deploy_sk, AuthorizeOperator { operator }, 1);
+    session
+        .call_token::<_, ()>("authorize_operator", &authorize)
+        .expect("Authorizing an operator should succeed");
+
+    let is_operator: bool = session
+        .call_token("is_operator", &IsOperator { owner, operator })
+        .expect("Querying an operator should succeed")
+        .data;
+    assert!(is_operator, "The account should be an operator");
+
+    for nonce in 1..=2 {
+        let transfer = Signed::new(
+            &operator_sk,
+            OperatorTransfer {
+                from: owner,
+                to: operator,
+                value: TRANSFERRED_AMOUNT,
+            },
+            nonce,
+        );
+        session
+            .call_token::<_, ()>("operator_transfer", &transfer)
+            .expect("Transferring as an operator should succeed");
+    }
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - 2 * TRANSFERRED_AMOUNT,
+        "The deployed account should have the transferred amounts subtracted"
+    );
+    assert_eq!(
+        session.account(PublicKey::from(&operator_sk)).balance,
+        2 * TRANSFERRED_AMOUNT,
+        "The operator should have received the transferred amounts"
+    );
+
+    let revoke = Signed::new(&session.deploy_sk, RevokeOperator { operator }, 2);
+    session
+        .call_token::<_, ()>("revoke_operator", &revoke)
+        .expect("Revoking an operator should succeed");
+
+    let transfer = Signed::new(
+        &operator_sk,
+        OperatorTransfer {
+            from: owner,
+            to: operator,
+            value: TRANSFERRED_AMOUNT,
+        },
+        3,
+    );
+    session
+        .call_token::<_, ()>("operator_transfer", &transfer)
+        .expect_err("Transferring as a revoked operator should fail");
+}
+
+#[test]
+fn transfer_raw() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let transfer = Transfer::new(&session.deploy_sk, MISSING_ID, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring to a contract without the hook should fail");
+
+    let transfer = Signed::new(
+        &session.deploy_sk,
+        TransferRaw {
+            to: Account::Contract(MISSING_ID),
+            value: TRANSFERRED_AMOUNT,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("transfer_raw", &transfer)
+        .expect("Transferring raw should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - TRANSFERRED_AMOUNT,
+        "The deployed account should have the transferred amount subtracted"
+    );
+    assert_eq!(
+        session.account(MISSING_ID).balance,
+        TRANSFERRED_AMOUNT,
+        "The contract should have received the tokens without the hook being called"
+    );
+}
+
+#[cfg(feature = "compliance")]
+#[test]
+fn whitelist() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 4;
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let receiver_sk = SecretKey::random(&mut rng);
+    let receiver_pk = PublicKey::from(&receiver_sk);
+
+    let mut session = ContractSession::new_with(|args| {
+        args.whitelist = Some(args.balances.iter().map(|(account, _)| *account).collect());
+    });
+
+    let transfer = Transfer::new(&session.deploy_sk, receiver_pk, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring to an account not whitelisted should fail");
+
+    let add = Signed::new(
+        &session.deploy_sk,
+        AddToWhitelist {
+            account: Account::External(receiver_pk),
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("add_to_whitelist", &add)
+        .expect("Adding to the whitelist should succeed");
+
+    let transfer = Transfer::new(&session.deploy_sk, receiver_pk, TRANSFERRED_AMOUNT, 2);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring to a whitelisted account should succeed");
+
+    assert_eq!(
+        session.account(receiver_pk).balance,
+        TRANSFERRED_AMOUNT,
+        "The whitelisted account should have received the tokens"
+    );
+
+    let remove = Signed::new(
+        &session.deploy_sk,
+        RemoveFromWhitelist {
+            account: Account::External(receiver_pk),
+        },
+        3,
+    );
+    session
+        .call_token::<_, ()>("remove_from_whitelist", &remove)
+        .expect("Removing from the whitelist should succeed");
+
+    let transfer = Transfer::new(&receiver_sk, session.deploy_pk(), TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring from an account not whitelisted should fail");
+
+    let is_whitelisted: bool = session
+        .call_token("is_whitelisted", &Account::External(receiver_pk))
+        .expect("Querying the whitelist should succeed")
+        .data;
+    assert!(
+        !is_whitelisted,
+        "The account should no longer be whitelisted"
+    );
+}
+
+#[cfg(feature = "fees")]
+#[test]
+fn burn_rate() {
+    const BURN_RATE: u16 = BURN_RATE_SCALE / 10;
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const BURNED_AMOUNT: u64 = TRANSFERRED_AMOUNT / 10;
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let receiver_pk = PublicKey::from(&SecretKey::random(&mut rng));
+
+    let mut session = ContractSession::new_with(|args| args.burn_rate = BURN_RATE);
+
+    let transfer = Transfer::new(&session.deploy_sk, receiver_pk, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    let deploy_balance = session.account(session.deploy_pk()).balance;
+    let receiver_balance = session.account(receiver_pk).balance;
+    let holder_balance = session.account(HOLDER_ID).balance;
+
+    assert_eq!(
+        deploy_balance,
+        INITIAL_BALANCE - TRANSFERRED_AMOUNT,
+        "The deployed account should have the gross amount subtracted"
+    );
+    assert_eq!(
+        receiver_balance,
+        TRANSFERRED_AMOUNT - BURNED_AMOUNT,
+        "The receiver should have the net amount added"
+    );
+
+    let supply: u64 = session
+        .call_token("total_supply", &())
+        .expect("Querying the supply should succeed")
+        .data;
+    assert_eq!(
+        supply,
+        INITIAL_BALANCE + INITIAL_HOLDER_BALANCE - BURNED_AMOUNT,
+        "The burned amount should be subtracted from the supply"
+    );
+    assert_eq!(
+        supply,
+        deploy_balance + receiver_balance + holder_balance,
+        "The supply should equal the sum of all balances"
+    );
+}
+
+#[test]
+fn airdrop() {
+    const AIRDROP_AMOUNT: u64 = 100;
+    const EXPIRY_HEIGHT: u64 = 10;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let entries: Vec<(Account, u64)> = (0..5)
+        .map(|_| {
+            let pk = PublicKey::from(&SecretKey::random(&mut rng));
+            (Account::External(pk), AIRDROP_AMOUNT)
+        })
+        .collect();
+    let tree = AirdropTree::new(&entries);
+
+    let total = AIRDROP_AMOUNT * entries.len() as u64;
+    let create = Signed::new(
+        &session.deploy_sk,
+        CreateAirdrop {
+            merkle_root: tree.root(),
+            total,
+            expiry_height: EXPIRY_HEIGHT,
+        },
+        1,
+    );
+    let id: u64 = session
+        .call_token("create_airdrop", &create)
+        .expect("Creating an airdrop should succeed")
+        .data;
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - total,
+        "The deployed account should have the airdrop escrowed"
+    );
+
+    let (account, amount) = entries[3];
+    let claim = ClaimAirdrop {
+        id,
+        account,
+        amount,
+        proof: tree.proof(3),
+    };
+
+    let mut bad_claim = claim.clone();
+    bad_claim.amount += 1;
+    session
+        .call_token::<_, ()>("claim_airdrop", &bad_claim)
+        .expect_err("Claiming with an invalid proof should fail");
+
+    session
+        .call_token::<_, ()>("claim_airdrop", &claim)
+        .expect("Claiming the airdrop should succeed");
+    session
+        .call_token::<_, ()>("claim_airdrop", &claim)
+        .expect_err("Claiming the airdrop twice should fail");
+
+    assert_eq!(
+        session.account(account).balance,
+        AIRDROP_AMOUNT,
+        "The account should have been credited the airdrop"
+    );
+
+    session.set_block_height(EXPIRY_HEIGHT);
+
+    let (account, amount) = entries[0];
+    let claim = ClaimAirdrop {
+        id,
+        account,
+        amount,
+        proof: tree.proof(0),
+    };
+    session
+        .call_token::<_, ()>("claim_airdrop", &claim)
+        .expect_err("Claiming an expired airdrop should fail");
+
+    let sweep = Signed::new(&session.deploy_sk, SweepAirdrop { id }, 2);
+    session
+        .call_token::<_, ()>("sweep_airdrop", &sweep)
+        .expect("Sweeping an expired airdrop should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - AIRDROP_AMOUNT,
+        "The deployed account should have the unclaimed tokens swept back"
+    );
+}
+
+#[test]
+fn stake() {
+    const STAKED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const LOCK_BLOCKS: u64 = 10;
+
+    let mut session = ContractSession::new();
+
+    let overflowing = Signed::new(
+        &session.deploy_sk,
+        Stake {
+            value: STAKED_AMOUNT,
+            lock_blocks: u64::MAX,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("stake", &overflowing)
+        .expect_err("Staking until past the largest height should fail");
+
+    let stake = Signed::new(
+        &session.deploy_sk,
+        Stake {
+            value: STAKED_AMOUNT,
+            lock_blocks: LOCK_BLOCKS,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("stake", &stake)
+        .expect("Staking should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - STAKED_AMOUNT,
+        "The deployed account should have the staked amount subtracted"
+    );
+
+    let staked: StakeInfo = session
+        .call_token("staked_of", &Account::External(session.deploy_pk()))
+        .expect("Querying the stake should succeed")
+        .data;
+    assert_eq!(
+        staked.value, STAKED_AMOUNT,
+        "The staked amount should be recorded"
+    );
+
+    let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, INITIAL_BALANCE, 2);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring staked tokens should fail");
+
+    let unstake = Signed::new(
+        &session.deploy_sk,
+        Unstake {
+            value: STAKED_AMOUNT,
+        },
+        2,
+    );
+    session
+        .call_token::<_, ()>("unstake", &unstake)
+        .expect_err("Unstaking before the lock period passed should fail");
+
+    session.set_block_height(LOCK_BLOCKS);
+
+    session
+        .call_token::<_, ()>("unstake", &unstake)
+        .expect("Unstaking after the lock period passed should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE,
+        "The deployed account should have the staked amount back"
+    );
+}
+
+#[test]
+fn distribute() {
+    const DISTRIBUTED_AMOUNT: u64 = 100;
+
+    let mut session = ContractSession::new();
+
+    let distribute = Signed::new(
+        &session.deploy_sk,
+        Distribute {
+            value: DISTRIBUTED_AMOUNT,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("distribute", &distribute)
+        .expect("Distributing should succeed");
+
+    // the distributor's balance is subtracted before the snapshot, so the holder owns 1000 out of
+    // the 1900 tokens
+    let expected = DISTRIBUTED_AMOUNT * INITIAL_HOLDER_BALANCE
+        / (INITIAL_BALANCE - DISTRIBUTED_AMOUNT + INITIAL_HOLDER_BALANCE);
+
+    let claimable: u64 = session
+        .call_token("claimable", &Account::Contract(HOLDER_ID))
+        .expect("Querying claimable rewards should succeed")
+        .data;
+    assert_eq!(
+        claimable, expected,
+        "The holder should have its pro-rata share claimable"
+    );
+
+    let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, INITIAL_BALANCE / 2, 2);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    let claimable: u64 = session
+        .call_token("claimable", &Account::Contract(HOLDER_ID))
+        .expect("Querying claimable rewards should succeed")
+        .data;
+    assert_eq!(
+        claimable, expected,
+        "Changes in balance after the distribution shouldn't affect the share"
+    );
+
+    let deploy_balance = session.account(session.deploy_pk()).balance;
+
+    let claim = Signed::new(&session.deploy_sk, ClaimRewards, 3);
+    let claimed: u64 = session
+        .call_token("claim_rewards", &claim)
+        .expect("Claiming rewards should succeed")
+        .data;
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        deploy_balance + claimed,
+        "The claimed rewards should be added to the balance"
+    );
+
+    let claim = Signed::new(&session.deploy_sk, ClaimRewards, 4);
+    session
+        .call_token::<_, u64>("claim_rewards", &claim)
+        .expect_err("Claiming rewards twice should fail");
+}
+
+#[test]
+fn deposit_not_wrapped() {
+    let mut session = ContractSession::new();
+
+    let deposit = Deposit {
+        to: Account::External(session.deploy_pk()),
+        value: INITIAL_BALANCE,
+    };
+    session
+        .call_token::<_, ()>("deposit", &deposit)
+        .expect_err("Depositing into a token not wrapping Dusk should fail");
+
+    let supply: u64 = session
+        .call_token("total_supply", &())
+        .expect("Querying the supply should succeed")
+        .data;
+    assert_eq!(
+        supply,
+        INITIAL_BALANCE + INITIAL_HOLDER_BALANCE,
+        "The supply should be unchanged"
+    );
+}
+
+#[test]
+fn stake_native_not_wrapped() {
+    let mut session = ContractSession::new();
+
+    let stake = Signed::new(
+        &session.deploy_sk,
+        StakeNative {
+            stake_key: session.deploy_pk(),
+            value: INITIAL_BALANCE,
+            stake_args: Vec::new(),
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("stake_native", &stake)
+        .expect_err("Staking with a token not wrapping Dusk should fail");
+
+    let unstake = Signed::new(
+        &session.deploy_sk,
+        UnstakeNative {
+            value: INITIAL_BALANCE,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("unstake_native", &unstake)
+        .expect_err("Unstaking with a token not wrapping Dusk should fail");
+
+    let stake: Option<NativeStakeInfo> = session
+        .call_token("native_stake", &Account::External(session.deploy_pk()))
+        .expect("Querying a native stake should succeed")
+        .data;
+    assert_eq!(stake, None, "No native stake should be recorded");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE,
+        "The balance should be unchanged"
+    );
+}
+
+#[test]
+fn migrate() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    let version: u32 = session
+        .call_token("state_version", &())
+        .expect("Querying the state version should succeed")
+        .data;
+
+    let mut chunks = Vec::new();
+    loop {
+        let chunk: Vec<u8> = session
+            .call_token("state_chunk", &(chunks.len() as u32))
+            .expect("Exporting the state should succeed")
+            .data;
+        if chunk.is_empty() {
+            break;
+        }
+        chunks.push(chunk);
+    }
+
+    // a fresh deployment, importing the state of the previous one
+    let mut new_session = ContractSession::new();
+
+    let n_chunks = chunks.len();
+    let admin_sk = new_session.deploy_sk.clone();
+    let migrate = |i: usize, chunk: Vec<u8>, nonce: u64| {
+        Signed::new(
+            &admin_sk,
+            Migrate {
+                version,
+                index: i as u32,
+                chunk,
+                last: i + 1 == n_chunks,
+            },
+            nonce,
+        )
+    };
+
+    let out_of_order = migrate(n_chunks - 1, chunks[n_chunks - 1].clone(), 1);
+    new_session
+        .call_token::<_, ()>("migrate", &out_of_order)
+        .expect_err("Importing a chunk out of order should fail");
+
+    // a chunk imported before the migration is aborted doesn't count towards it
+    new_session
+        .call_token::<_, ()>("migrate", &migrate(0, chunks[0].clone(), 1))
+        .expect("Migrating should succeed");
+    let abort = Signed::new(&new_session.deploy_sk, AbortMigration, 2);
+    new_session
+        .call_token::<_, ()>("abort_migration", &abort)
+        .expect("Aborting the migration should succeed");
+
+    let mut signed = Vec::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let migrate = migrate(i, chunk.clone(), i as u64 + 3);
+        new_session
+            .call_token::<_, ()>("migrate", &migrate)
+            .expect("Migrating should succeed");
+        signed.push(migrate);
+    }
+
+    for nonce in 1..n_chunks as u64 + 3 {
+        let used: bool = new_session
+            .call_token(
+                "nonce_used",
+                &NonceUsed {
+                    account: Account::External(new_session.deploy_pk()),
+                    nonce,
+                },
+            )
+            .expect("Querying a nonce should succeed")
+            .data;
+        assert!(used, "Nonce {nonce} should stay used after the migration");
+    }
+    for migrate in &signed {
+        new_session
+            .call_token::<_, ()>("migrate", migrate)
+            .expect_err("Replaying a chunk after the migration should fail");
+    }
+    let migrate = migrate(0, chunks[0].clone(), n_chunks as u64 + 3);
+    new_session
+        .call_token::<_, ()>("migrate", &migrate)
+        .expect_err("Migrating a second time should fail");
+
+    assert_eq!(
+        new_session.account(new_session.deploy_pk()),
+        session.account(session.deploy_pk()),
+        "The deployed account should be migrated"
+    );
+    assert_eq!(
+        new_session.account(HOLDER_ID).balance,
+        INITIAL_HOLDER_BALANCE + TRANSFERRED_AMOUNT,
+        "The holder's balance should be migrated"
+    );
+}
+
+#[test]
+fn nonce() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 4;
+
+    let mut session = ContractSession::new();
+    let deploy_account = Account::External(session.deploy_pk());
+
+    let mut nonces = NonceManager::new();
+    for _ in 0..3 {
+        let nonce = nonces.next(deploy_account, |account| {
+            session
+                .call_token("nonce", &account)
+                .expect("Querying the nonce should succeed")
+                .data
+        });
+
+        let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, TRANSFERRED_AMOUNT, nonce);
+        session
+            .call_token::<_, ()>("transfer", &transfer)
+            .expect("Transferring with a managed nonce should succeed");
+    }
+
+    let nonce: u64 = session
+        .call_token("nonce", &deploy_account)
+        .expect("Querying the nonce should succeed")
+        .data;
+    assert_eq!(nonce, 3, "The next nonce should follow the ones used");
+}
+
+#[test]
+fn unordered_nonces() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 4;
+
+    let mut session = ContractSession::new();
+    let deploy_account = Account::External(session.deploy_pk());
+
+    for nonce in [300, 2] {
+        let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, TRANSFERRED_AMOUNT, nonce);
+        session
+            .call_token::<_, ()>("transfer", &transfer)
+            .expect("Transferring with an unused nonce should succeed");
+    }
+
+    let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, TRANSFERRED_AMOUNT, 300);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring with a used nonce should fail");
+
+    for (nonce, expected) in [(2, true), (300, true), (1, false)] {
+        let used: bool = session
+            .call_token(
+                "nonce_used",
+                &NonceUsed {
+                    account: deploy_account,
+                    nonce,
+                },
+            )
+            .expect("Querying a nonce should succeed")
+            .data;
+        assert_eq!(used, expected, "Nonce {nonce} should be reported correctly");
+    }
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - 2 * TRANSFERRED_AMOUNT,
+        "Both transfers should have been performed"
+    );
+}
+
+#[test]
+fn cancel_nonce() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 4;
+
+    let mut session = ContractSession::new();
+
+    let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, TRANSFERRED_AMOUNT, 1);
+
+    let cancel = Signed::new(&session.deploy_sk, CancelNonce, 1);
+    session
+        .call_token::<_, ()>("cancel_nonce", &cancel)
+        .expect("Cancelling a nonce should succeed");
+
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring with a cancelled nonce should fail");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE,
+        "The deployed account should keep its balance"
+    );
+}
+
+#[test]
+fn permit_transfer_from() {
+    const APPROVED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const TRANSFERRED_AMOUNT: u64 = APPROVED_AMOUNT / 2;
+    const DEADLINE: u64 = 10;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let spender_sk = SecretKey::random(&mut rng);
+    let spender_pk = PublicKey::from(&spender_sk);
+
+    let permit = Signed::new(
+        &session.deploy_sk,
+        Permit {
+            spender: Account::External(spender_pk),
+            value: APPROVED_AMOUNT,
+            deadline: DEADLINE,
+        },
+        1,
+    );
+    let transfer = Signed::new(
+        &spender_sk,
+        PermitTransferFrom {
+            permit,
+            to: Account::External(spender_pk),
+            value: TRANSFERRED_AMOUNT,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("permit_transfer_from", &transfer)
+        .expect("Transferring with a permit should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - TRANSFERRED_AMOUNT,
+        "The deployed account should have the transferred amount subtracted"
+    );
+    assert_eq!(
+        session.account(spender_pk).balance,
+        TRANSFERRED_AMOUNT,
+        "The spender should have received the transferred amount"
+    );
+    assert_eq!(
+        session.allowance(session.deploy_pk(), spender_pk),
+        APPROVED_AMOUNT - TRANSFERRED_AMOUNT,
+        "The allowance should be partially spent"
+    );
+
+    session.set_block_height(DEADLINE + 1);
+
+    let permit = Signed::new(
+        &session.deploy_sk,
+        Permit {
+            spender: Account::External(spender_pk),
+            value: APPROVED_AMOUNT,
+            deadline: DEADLINE,
+        },
+        2,
+    );
+    session
+        .call_token::<_, ()>("permit", &permit)
+        .expect_err("Using an expired permit should fail");
+}
+
+#[test]
+fn spending_limit() {
+    const LIMIT: u64 = INITIAL_BALANCE / 4;
+    const WINDOW_BLOCKS: u64 = 10;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let spender_sk = SecretKey::random(&mut rng);
+    let spender_pk = PublicKey::from(&spender_sk);
+
+    let set = Signed::new(
+        &session.deploy_sk,
+        SetSpendingLimit {
+            spender: Account::External(spender_pk),
+            limit: LIMIT,
+            window_blocks: WINDOW_BLOCKS,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("set_spending_limit", &set)
+        .expect("Setting a spending limit should succeed");
+
+    let transfer_from = TransferFrom::new(&spender_sk, session.deploy_pk(), spender_pk, LIMIT, 1);
+    session
+        .call_token::<_, ()>("transfer_from", &transfer_from)
+        .expect("Spending within the limit should succeed");
+
+    let budget = |session: &mut ContractSession| -> u64 {
+        session
+            .call_token(
+                "spending_budget",
+                &Allowance {
+                    owner: Account::External(session.deploy_pk()),
+                    spender: Account::External(spender_pk),
+                },
+            )
+            .expect("Querying the spending budget should succeed")
+            .data
+    };
+    assert_eq!(budget(&mut session), 0, "The budget should be spent");
+
+    let transfer_from = TransferFrom::new(&spender_sk, session.deploy_pk(), spender_pk, 1, 2);
+    session
+        .call_token::<_, ()>("transfer_from", &transfer_from)
+        .expect_err("Spending over the limit should fail");
+
+    session.set_block_height(WINDOW_BLOCKS);
+    assert_eq!(
+        budget(&mut session),
+        LIMIT,
+        "The budget should reset in a new window"
+    );
+
+    session
+        .call_token::<_, ()>("transfer_from", &transfer_from)
+        .expect("Spending in a new window should succeed");
+
+    assert_eq!(
+        session.account(spender_pk).balance,
+        LIMIT + 1,
+        "The spender should have received the spent amounts"
+    );
+
+    let info = session
+        .call_token::<_, AllowanceInfo>(
+            "allowance_info",
+            &Allowance {
+                owner: Account::External(session.deploy_pk()),
+                spender: Account::External(spender_pk),
+            },
+        )
+        .expect("Querying the allowance info should succeed")
+        .data;
+    assert_eq!(info.value, 0, "The spender should have no plain allowance");
+    assert_eq!(
+        info.spending_limit,
+        Some(SpendingLimit {
+            limit: LIMIT,
+            window_blocks: WINDOW_BLOCKS,
+            window: 1,
+            spent: 1,
+        }),
+        "The spending limit should include the value spent in the current window"
+    );
+}
+
+#[test]
+fn allowance_expiry() {
+    const APPROVED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const EXPIRY_HEIGHT: u64 = 10;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let spender_sk = SecretKey::random(&mut rng);
+    let spender_pk = PublicKey::from(&spender_sk);
+
+    let allowance = Allowance {
+        owner: Account::External(session.deploy_pk()),
+        spender: Account::External(spender_pk),
+    };
+
+    let approve = Approve::new(&session.deploy_sk, spender_pk, APPROVED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("approve", &approve)
+        .expect("Approving should succeed");
+
+    let set = Signed::new(
+        &session.deploy_sk,
+        SetAllowanceExpiry {
+            spender: Account::External(spender_pk),
+            expiry_height: Some(EXPIRY_HEIGHT),
+        },
+        2,
+    );
+    session
+        .call_token::<_, ()>("set_allowance_expiry", &set)
+        .expect("Setting an allowance expiry should succeed");
+
+    let info = session
+        .call_token::<_, AllowanceInfo>("allowance_info", &allowance)
+        .expect("Querying the allowance info should succeed")
+        .data;
+    assert_eq!(
+        info.expiry_height,
+        Some(EXPIRY_HEIGHT),
+        "The allowance info should return the stored expiry"
+    );
+
+    let transfer_from = TransferFrom::new(&spender_sk, session.deploy_pk(), spender_pk, 1, 1);
+    session
+        .call_token::<_, ()>("transfer_from", &transfer_from)
+        .expect("Spending before the expiry should succeed");
+
+    session.set_block_height(EXPIRY_HEIGHT);
+
+    let transfer_from = TransferFrom::new(&spender_sk, session.deploy_pk(), spender_pk, 1, 2);
+    session
+        .call_token::<_, ()>("transfer_from", &transfer_from)
+        .expect_err("Spending an expired allowance should fail");
+
+    let value: u64 = session
+        .call_token("allowance", &allowance)
+        .expect("Querying the allowance should succeed")
+        .data;
+    assert_eq!(value, 0, "An expired allowance should be zero");
+
+    let set = Signed::new(
+        &session.deploy_sk,
+        SetAllowanceExpiry {
+            spender: Account::External(spender_pk),
+            expiry_height: Some(EXPIRY_HEIGHT),
+        },
+        3,
+    );
+    session
+        .call_token::<_, ()>("set_allowance_expiry", &set)
+        .expect_err("Setting an expiry that has been reached should fail");
+
+    let revoke = Approve::new(&session.deploy_sk, spender_pk, 0, 3);
+    session
+        .call_token::<_, ()>("approve", &revoke)
+        .expect("Revoking the allowance should succeed");
+
+    let info = session
+        .call_token::<_, AllowanceInfo>("allowance_info", &allowance)
+        .expect("Querying the allowance info should succeed")
+        .data;
+    assert_eq!(
+        info.expiry_height, None,
+        "Revoking the allowance should remove its expiry"
+    );
+}
+
+#[test]
+fn approve_if() {
+    const APPROVED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let spender_pk = PublicKey::from(&SecretKey::random(&mut rng));
+
+    let approve = Signed::new(
+        &session.deploy_sk,
+        ApproveIf {
+            spender: Account::External(spender_pk),
+            expected: APPROVED_AMOUNT,
+            value: APPROVED_AMOUNT / 2,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("approve_if", &approve)
+        .expect_err("Approving with the wrong expected allowance should fail");
+
+    let approve = Signed::new(
+        &session.deploy_sk,
+        ApproveIf {
+            spender: Account::External(spender_pk),
+            expected: 0,
+            value: APPROVED_AMOUNT,
+        },
+        1,
+    );
+    session
+        .call_token::<_, ()>("approve_if", &approve)
+        .expect("Approving with the expected allowance should succeed");
+
+    assert_eq!(
+        session.allowance(session.deploy_pk(), spender_pk),
+        APPROVED_AMOUNT,
+        "The spender should have the approved allowance"
+    );
+}
+
+#[test]
+fn prune_empty_accounts() {
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let empty_pk = PublicKey::from(&SecretKey::random(&mut rng));
+    let signer_sk = SecretKey::random(&mut rng);
+    let signer_pk = PublicKey::from(&signer_sk);
+
+    // an initial balance of zero leaves an empty entry in the state
+    let mut session = ContractSession::new_with(|args| {
+        args.balances.push((Account::External(empty_pk), 0));
+    });
+
+    // as does signing with an account holding no tokens
+    let approve = Approve::new(&signer_sk, empty_pk, 1, 1);
+    session
+        .call_token::<_, ()>("approve", &approve)
+        .expect("Approving should succeed");
+
+    let prune = Signed::new(
+        &session.deploy_sk,
+        PruneEmptyAccounts {
+            accounts: vec![
+                Account::External(empty_pk),
+                Account::External(signer_pk),
+                Account::External(session.deploy_pk()),
+                Account::Contract(HOLDER_ID),
+            ],
+        },
+        1,
+    );
+    let pruned: u32 = session
+        .call_token("prune_empty_accounts", &prune)
+        .expect("Pruning should succeed")
+        .data;
+    assert_eq!(
+        pruned, 2,
+        "Only the accounts with no tokens should be pruned"
+    );
+
+    assert_eq!(
+        session.account(empty_pk),
+        AccountInfo::EMPTY,
+        "The pruned account should still be reported as empty"
+    );
+    session
+        .call_token::<_, ()>("approve", &approve)
+        .expect_err("Replaying an operation signed by a pruned account should fail");
+    assert_eq!(
+        session.account(HOLDER_ID).balance,
+        INITIAL_HOLDER_BALANCE,
+        "Accounts with a balance should be kept"
+    );
+}
+
+#[test]
+fn events_since() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 4;
+
+    let mut session = ContractSession::new();
+
+    let mut emitted = Vec::new();
+    for nonce in 1..=2 {
+        let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, TRANSFERRED_AMOUNT, nonce);
+        let receipt = session
+            .call_token::<_, ()>("transfer", &transfer)
+            .expect("Transferring should succeed");
+        emitted.extend(receipt.events);
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    session
+        .token
+        .session()
+        .feeder_call::<_, ()>(TOKEN_ID, "events_since", &0u64, u64::MAX, sender)
+        .expect("Feeding events should succeed");
+
+    let records: Vec<EventRecord> = receiver
+        .into_iter()
+        .map(|bytes| rkyv::from_bytes(&bytes).expect("The fed events should be valid records"))
+        .collect();
+
+    assert_eq!(
+        records.len(),
+        emitted.len(),
+        "All events emitted should be kept"
+    );
+    for (seq, (record, event)) in records.iter().zip(&emitted).enumerate() {
+        assert_eq!(record.seq, seq as u64, "The events should be sequential");
+        assert_eq!(record.topic, event.topic, "The topics should match");
+        assert_eq!(record.data, event.data, "The data should match");
+    }
+
+    let transfer: Sequenced<TransferEvent> =
+        rkyv::from_bytes(&records[0].data).expect("The event should be a transfer");
+    assert_eq!(
+        transfer.seq, 0,
+        "The event should carry its sequence number"
+    );
+    assert_eq!(
+        transfer.event.value, TRANSFERRED_AMOUNT,
+        "The event should carry the transferred value"
+    );
+}
+
+#[test]
+fn history() {
+    const HISTORY_LEN: u32 = 2;
+
+    let mut session = ContractSession::new_with(|args| args.history_len = HISTORY_LEN);
+
+    for (nonce, value) in [(1, 10), (2, 20), (3, 30)] {
+        let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, value, nonce);
+        session
+            .call_token::<_, ()>("transfer", &transfer)
+            .expect("Transferring should succeed");
+    }
+
+    let history: Vec<TransferRecord> = session
+        .call_token("history", &Account::Contract(HOLDER_ID))
+        .expect("Querying the history should succeed")
+        .data;
+
+    let values: Vec<u64> = history.iter().map(|record| record.value).collect();
+    assert_eq!(
+        values,
+        vec![20, 30],
+        "Only the most recent transfers should be kept, from oldest to newest"
+    );
+    assert_eq!(
+        history[0].owner,
+        Account::External(session.deploy_pk()),
+        "The records should carry the owner"
+    );
+}
+
+#[test]
+fn holder_count() {
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let holder_count = |session: &mut ContractSession| -> u64 {
+        session
+            .call_token("holder_count", &())
+            .expect("Querying the holder count should succeed")
+            .data
+    };
+    let exists = |session: &mut ContractSession, account: Account| -> bool {
+        session
+            .call_token("exists", &account)
+            .expect("Querying existence should succeed")
+            .data
+    };
+
+    assert_eq!(
+        holder_count(&mut session),
+        2,
+        "Both initial accounts should hold tokens"
+    );
+    assert!(
+        !exists(&mut session, Account::External(pk)),
+        "The account should not exist before receiving tokens"
+    );
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, INITIAL_BALANCE / 2, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    assert_eq!(
+        holder_count(&mut session),
+        3,
+        "The receiver should be counted"
+    );
+    assert!(
+        exists(&mut session, Account::External(pk)),
+        "The account should exist after receiving tokens"
+    );
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, INITIAL_BALANCE / 2, 2);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    assert_eq!(
+        holder_count(&mut session),
+        2,
+        "An account with no tokens left should not be counted"
+    );
+    assert!(
+        !exists(&mut session, Account::External(session.deploy_pk())),
+        "An account with no tokens left should be pruned, even if it used nonces"
+    );
+    assert_eq!(
+        session.account(session.deploy_pk()),
+        AccountInfo::EMPTY,
+        "The pruned account should be reported as empty"
+    );
+
+    let used: bool = session
+        .call_token(
+            "nonce_used",
+            &NonceUsed {
+                account: Account::External(session.deploy_pk()),
+                nonce: 2,
+            },
+        )
+        .expect("Querying a nonce should succeed")
+        .data;
+    assert!(used, "The nonces of a pruned account should stay used");
+
+    let transfer = Transfer::new(&sk, session.deploy_pk(), INITIAL_BALANCE, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring back should succeed");
+    let replayed = Transfer::new(&session.deploy_sk, pk, INITIAL_BALANCE / 2, 2);
+    session
+        .call_token::<_, ()>("transfer", &replayed)
+        .expect_err("Replaying a transfer signed by a pruned account should fail");
+}
+
+#[test]
+fn account_string() {
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    for account in [
+        Account::External(pk),
+        Account::Contract(HOLDER_ID),
+        Account::Multisig(42),
+        Account::SubAccount(pk, 7),
+    ] {
+        let s = account.to_string();
+        assert_eq!(
+            s.parse::<Account>(),
+            Ok(account),
+            "The account should be parsed back from its string"
+        );
+        assert_eq!(
+            s.to_uppercase().parse::<Account>(),
+            Ok(account),
+            "Upper case strings should be accepted"
+        );
+    }
+
+    let s = Account::Contract(HOLDER_ID).to_string();
+    assert!(
+        s.starts_with(CONTRACT_PREFIX),
+        "The prefix should match the kind"
+    );
+
+    let mut corrupted = s.clone().into_bytes();
+    let last = corrupted.len() - 1;
+    corrupted[last] = if corrupted[last] == b'q' { b'p' } else { b'q' };
+    let corrupted = String::from_utf8(corrupted).unwrap();
+    assert_eq!(
+        corrupted.parse::<Account>(),
+        Err(ParseAccountError::InvalidChecksum),
+        "A corrupted string should fail the checksum"
+    );
+
+    let external = s.replacen(CONTRACT_PREFIX, EXTERNAL_PREFIX, 1);
+    assert_eq!(
+        external.parse::<Account>(),
+        Err(ParseAccountError::InvalidChecksum),
+        "The prefix should be covered by the checksum"
+    );
+}
+
+#[test]
+fn externally_signed_transfer() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let deploy_pk = session.deploy_pk();
+
+    // the message is produced without the secret key, and signed "elsewhere"
+    let unsigned = Transfer::from_parts(deploy_pk, pk, TRANSFERRED_AMOUNT, 1, Signature::default());
+    assert!(!unsigned.verify(), "An unsigned transfer should not verify");
+
+    let signature = session.deploy_sk.sign(&unsigned.signature_message());
+    let transfer = Transfer::from_parts(deploy_pk, pk, TRANSFERRED_AMOUNT, 1, signature);
+    assert!(transfer.verify(), "The signed transfer should verify");
+
+    let tampered = Transfer::from_parts(deploy_pk, pk, TRANSFERRED_AMOUNT + 1, 1, signature);
+    assert!(!tampered.verify(), "A tampered transfer should not verify");
+
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    assert_eq!(
+        session.account(pk).balance,
+        TRANSFERRED_AMOUNT,
+        "The account should have received the transferred amount"
+    );
+}
+
+#[test]
+fn transfer_builder() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let transfer = Transfer::builder()
+        .to(HOLDER_ID)
+        .value(TRANSFERRED_AMOUNT)
+        .nonce(1)
+        .hook_policy(HookPolicy::Revert)
+        .data(vec![1, 2, 3])
+        .sign(&session.deploy_sk);
+
+    assert!(transfer.verify(), "The built transfer should verify");
+    assert_eq!(transfer.data(), &[1, 2, 3], "The data should be set");
+
+    let unsigned = Transfer::builder()
+        .to(HOLDER_ID)
+        .value(TRANSFERRED_AMOUNT)
+        .nonce(1)
+        .hook_policy(HookPolicy::Revert)
+        .data(vec![1, 2, 3])
+        .with_signature(session.deploy_pk(), Signature::default());
+    let signature = session.deploy_sk.sign(&unsigned.signature_message());
+    assert_eq!(
+        SchemeSignature::Bls(signature),
+        *transfer.signature(),
+        "Signing elsewhere should produce the same signature"
+    );
+
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    assert_eq!(
+        session.account(HOLDER_ID).balance,
+        INITIAL_HOLDER_BALANCE + TRANSFERRED_AMOUNT,
+        "The holder should have received the transferred amount"
+    );
+}
+
+#[test]
+fn token_amount() {
+    const DECIMALS: u8 = 6;
+
+    let amount = TokenAmount::from_units("12.5", DECIMALS).expect("The amount should parse");
+    assert_eq!(amount.base_units(), 12_500_000);
+    assert_eq!(amount.to_display(DECIMALS), "12.5");
+    assert_eq!(format!("{amount:.6}"), "12.500000");
+    assert_eq!(format!("{amount}"), "12500000");
+
+    assert_eq!(
+        TokenAmount::from_units("0.000001", DECIMALS),
+        Ok(TokenAmount::from_base_units(1))
+    );
+    assert_eq!(
+        TokenAmount::from_units("0.0000001", DECIMALS),
+        Err(AmountError::TooPrecise)
+    );
+    assert_eq!(
+        TokenAmount::from_units("1e5", DECIMALS),
+        Err(AmountError::Invalid)
+    );
+    assert_eq!(
+        TokenAmount::from_units("18446744073709.551616", DECIMALS),
+        Err(AmountError::Overflow)
+    );
+
+    // 0.1 + 0.2 isn't exactly 0.3 as a float, but is rounded to the nearest base unit
+    assert_eq!(
+        TokenAmount::from_f64(0.1 + 0.2, DECIMALS),
+        TokenAmount::from_units("0.3", DECIMALS)
+    );
+    assert_eq!(
+        TokenAmount::from_f64(-1.0, DECIMALS),
+        Err(AmountError::Invalid)
+    );
+
+    assert_eq!(
+        amount
+            .checked_add(amount)
+            .map(|sum| sum.to_display(DECIMALS)),
+        Some(String::from("25"))
+    );
+    assert_eq!(amount.checked_sub(amount.checked_mul(2).unwrap()), None);
+
+    let transfer = Transfer::builder()
+        .to(HOLDER_ID)
+        .amount(amount)
+        .nonce(1)
+        .sign(&SecretKey::random(&mut StdRng::seed_from_u64(0xBEEF)));
+    assert_eq!(transfer.value(), amount.base_units());
+}
+
+#[test]
+fn signature_message_format() {
+    let session = ContractSession::new();
+
+    let transfer = Transfer::new(&session.deploy_sk, HOLDER_ID, 10, 1);
+    let msg = transfer.signature_message();
+
+    assert_eq!(
+        msg[0], MESSAGE_VERSION,
+        "The message should start with the version"
+    );
+    assert_eq!(
+        &msg[1..5],
+        &(b"transfer".len() as u32).to_le_bytes(),
+        "The name should be prefixed by its length"
+    );
+    assert_eq!(
+        &msg[5..13],
+        b"transfer",
+        "The name should follow the version"
+    );
+
+    // name, from, to, value, nonce, hook policy, data, memo, and idempotency id, each prefixed by a
+    // 4-byte length
+    let field_lens = [8, 193, 194, 8, 8, 1, 0, 0, 0];
+    let expected_len = 1 + field_lens.iter().map(|len| 4 + len).sum::<usize>();
+    assert_eq!(
+        msg.len(),
+        expected_len,
+        "The message should hold every field, prefixed by its length"
+    );
+}
+
+#[test]
+fn account_bytes() {
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    for account in [
+        Account::External(pk),
+        Account::Contract(HOLDER_ID),
+        Account::Multisig(42),
+        Account::SubAccount(pk, 7),
+    ] {
+        let bytes = account.to_bytes();
+        assert_eq!(
+            Account::from_bytes(&bytes),
+            Ok(account),
+            "The account should be parsed back from its bytes"
+        );
+    }
+
+    let mut bytes = Account::Contract(HOLDER_ID).to_bytes();
+    bytes[0] = 6;
+    assert_eq!(
+        Account::from_bytes(&bytes),
+        Err(AccountBytesError::InvalidTag(6)),
+        "An unknown tag should be rejected"
+    );
+
+    let mut bytes = Account::Multisig(42).to_bytes();
+    bytes[Account::SIZE - 1] = 1;
+    assert_eq!(
+        Account::from_bytes(&bytes),
+        Err(AccountBytesError::InvalidPadding),
+        "Non-zero padding should be rejected"
+    );
+
+    let mut bytes = Account::External(pk).to_bytes();
+    bytes[1..].fill(0xff);
+    assert_eq!(
+        Account::from_bytes(&bytes),
+        Err(AccountBytesError::InvalidPublicKey),
+        "An invalid public key should be rejected"
+    );
+}
+
+#[test]
+fn client() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let deploy_sk = session.deploy_sk.clone();
+    let deploy_pk = session.deploy_pk();
+
+    let mut client = TokenClient::new(SessionBackend::new(session.token.session(), TOKEN_ID));
+
+    // the nonces are fetched and tracked by the client
+    for _ in 0..2 {
+        let events = client
+            .transfer(&deploy_sk, pk, TRANSFERRED_AMOUNT / 2)
+            .expect("Transferring should succeed");
+
+        match &events[..] {
+            [TokenEvent::Transfer(transfer)] => {
+                assert_eq!(
+                    transfer.event.value,
+                    TRANSFERRED_AMOUNT / 2,
+                    "The event should carry the transferred value"
+                );
+            }
+            _ => panic!("A single transfer event should be emitted"),
+        }
+    }
+
+    assert_eq!(
+        client.balance_of(pk).expect("Querying should succeed"),
+        TRANSFERRED_AMOUNT,
+        "The account should have received the transferred amount"
+    );
+
+    client
+        .approve(&sk, deploy_pk, TRANSFERRED_AMOUNT)
+        .expect("Approving should succeed");
+    assert_eq!(
+        client
+            .allowance(pk, deploy_pk)
+            .expect("Querying should succeed"),
+        TRANSFERRED_AMOUNT,
+        "The allowance should be set"
+    );
+
+    client
+        .transfer_from(&deploy_sk, pk, HOLDER_ID, TRANSFERRED_AMOUNT)
+        .expect("Transferring from should succeed");
+    assert_eq!(
+        client
+            .balance_of(HOLDER_ID)
+            .expect("Querying should succeed"),
+        INITIAL_HOLDER_BALANCE + TRANSFERRED_AMOUNT,
+        "The holder should have received the transferred amount"
+    );
+}
+
+#[test]
+fn two_phase_transfer() {
+    const THRESHOLD: u64 = INITIAL_BALANCE / 4;
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const EXPIRY_HEIGHT: u64 = 10;
+
+    let mut session = ContractSession::new_with(|args| {
+        args.two_phase_threshold = Some(THRESHOLD);
+    });
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring above the threshold directly should fail");
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, THRESHOLD, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring up to the threshold directly should succeed");
+
+    let propose = ProposeTransfer {
+        to: Account::External(pk),
+        value: TRANSFERRED_AMOUNT,
+        expiry_height: EXPIRY_HEIGHT,
+    };
+    let id = session
+        .call_token::<_, u64>(
+            "propose_transfer",
+            &Signed::new(&session.deploy_sk, propose, 2),
+        )
+        .expect("Proposing a transfer should succeed")
+        .data;
+    let cancelled_id = session
+        .call_token::<_, u64>(
+            "propose_transfer",
+            &Signed::new(&session.deploy_sk, propose, 3),
+        )
+        .expect("Proposing a transfer should succeed")
+        .data;
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - THRESHOLD - 2 * TRANSFERRED_AMOUNT,
+        "The proposed transfers should be debited from the sender"
+    );
+
+    let cancel = Signed::new(&sk, CancelTransfer { id: cancelled_id }, 1);
+    session
+        .call_token::<_, ()>("cancel_transfer", &cancel)
+        .expect_err("Only the sender should be able to cancel a proposed transfer");
+
+    let cancel = Signed::new(&session.deploy_sk, CancelTransfer { id: cancelled_id }, 4);
+    session
+        .call_token::<_, ()>("cancel_transfer", &cancel)
+        .expect("Cancelling a proposed transfer should succeed");
+
+    let claim = Signed::new(&sk, ClaimTransfer { id: cancelled_id }, 2);
+    session
+        .call_token::<_, ()>("claim_transfer", &claim)
+        .expect_err("Claiming a cancelled transfer should fail");
+
+    let claim = Signed::new(&session.deploy_sk, ClaimTransfer { id }, 5);
+    session
+        .call_token::<_, ()>("claim_transfer", &claim)
+        .expect_err("Only the recipient should be able to claim a proposed transfer");
+
+    let claim = Signed::new(&sk, ClaimTransfer { id }, 3);
+    session
+        .call_token::<_, ()>("claim_transfer", &claim)
+        .expect("Claiming a proposed transfer should succeed");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - THRESHOLD - TRANSFERRED_AMOUNT,
+        "The cancelled transfer should be returned to the sender"
+    );
+    assert_eq!(
+        session.account(pk).balance,
+        THRESHOLD + TRANSFERRED_AMOUNT,
+        "The claimed transfer should be credited to the recipient"
+    );
+    assert_eq!(
+        session
+            .call_token::<_, Option<PendingTransfer>>("pending_transfer", &id)
+            .expect("Querying a proposed transfer should succeed")
+            .data,
+        None,
+        "The claimed transfer should no longer be pending"
+    );
+
+    let id = session
+        .call_token::<_, u64>(
+            "propose_transfer",
+            &Signed::new(&session.deploy_sk, propose, 6),
+        )
+        .expect("Proposing a transfer should succeed")
+        .data;
+
+    session.set_block_height(EXPIRY_HEIGHT);
+
+    let claim = Signed::new(&sk, ClaimTransfer { id }, 4);
+    session
+        .call_token::<_, ()>("claim_transfer", &claim)
+        .expect_err("Claiming an expired transfer should fail");
+}
+
+#[test]
+fn authorized_transfer() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_HOLDER_BALANCE / 2;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let authorizer_sk = SecretKey::random(&mut rng);
+    let authorizer_pk = PublicKey::from(&authorizer_sk);
+    let other_sk = SecretKey::random(&mut rng);
+
+    let mut transfer =
+        AuthorizedTransfer::new(HOLDER_ID, session.deploy_pk(), TRANSFERRED_AMOUNT, 1);
+    let sig = authorizer_sk.sign(&transfer.signature_message());
+    transfer.set_authorization(sig.to_bytes().to_vec());
+
+    session
+        .call_token::<_, ()>("transfer_authorized", &transfer)
+        .expect_err("Transferring from an account without a verifier should fail");
+
+    session
+        .call_holder::<_, ()>("token_register_verifier", &authorizer_pk)
+        .expect("Registering a verifier should succeed");
+
+    let verifier = session
+        .call_token::<_, Option<Verifier>>("verifier", &HOLDER_ID)
+        .expect("Querying the verifier should succeed")
+        .data
+        .expect("The holder should have a verifier registered");
+    assert_eq!(verifier.contract, HOLDER_ID);
+
+    let mut forged = transfer.clone();
+    let sig = other_sk.sign(&forged.signature_message());
+    forged.set_authorization(sig.to_bytes().to_vec());
+
+    session
+        .call_token::<_, ()>("transfer_authorized", &forged)
+        .expect_err("Transferring with an invalid authorization should fail");
+
+    session
+        .call_token::<_, ()>("transfer_authorized", &transfer)
+        .expect("Transferring with a valid authorization should succeed");
+
+    assert_eq!(
+        session.account(HOLDER_ID).balance,
+        INITIAL_HOLDER_BALANCE - TRANSFERRED_AMOUNT,
+        "The holder should have the transferred amount subtracted"
+    );
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE + TRANSFERRED_AMOUNT,
+        "The deployed account should have the transferred amount added"
+    );
+
+    session
+        .call_token::<_, ()>("transfer_authorized", &transfer)
+        .expect_err("Replaying an authorized transfer should fail");
+}
+
+#[test]
+fn rescue() {
+    const STUCK_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const OTHER_ID: ContractId = ContractId::from_bytes([4; 32]);
+
+    // the stuck tokens are above the two-phase threshold, which the contract can't go through
+    let mut session = ContractSession::new_with(|args| {
+        args.two_phase_threshold = Some(STUCK_AMOUNT / 2);
+    });
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    for nonce in 1..=2 {
+        let transfer = Signed::new(
+            &session.deploy_sk,
+            TransferRaw {
+                to: Account::Contract(TOKEN_ID),
+                value: STUCK_AMOUNT / 2,
+            },
+            nonce,
+        );
+        session
+            .call_token::<_, ()>("transfer_raw", &transfer)
+            .expect("Transferring raw should succeed");
+    }
+
+    let rescue = Rescue {
+        token: None,
+        to: Account::External(pk),
+        value: STUCK_AMOUNT,
+    };
+
+    session
+        .call_token::<_, ()>("rescue", &Signed::new(&sk, rescue, 1))
+        .expect_err("Only the admin should be able to rescue tokens");
+
+    let receipt = session
+        .call_token::<_, ()>("rescue", &Signed::new(&session.deploy_sk, rescue, 3))
+        .expect("Rescuing above the two-phase threshold should succeed");
+
+    assert_transfer(&receipt, TOKEN_ID, TOKEN_ID, pk, STUCK_AMOUNT);
+    assert_eq!(
+        session.account(TOKEN_ID).balance,
+        0,
+        "The token contract should no longer hold tokens"
+    );
+    assert_eq!(
+        session.account(pk).balance,
+        STUCK_AMOUNT,
+        "The rescued tokens should be moved to the given account"
+    );
+
+    // tokens of another token contract held by this one
+    let init_args = InitArgs {
+        balances: vec![(Account::Contract(TOKEN_ID), STUCK_AMOUNT)],
+        ..InitArgs::default()
+    };
+    session.token.deploy(TOKEN_BYTECODE, OTHER_ID, &init_args);
+
+    let rescue = Rescue {
+        token: Some(OTHER_ID),
+        to: Account::External(pk),
+        value: STUCK_AMOUNT,
+    };
+    session
+        .call_token::<_, ()>("rescue", &Signed::new(&session.deploy_sk, rescue, 4))
+        .expect("Rescuing another token should succeed");
+
+    let other_balance = |session: &mut ContractSession, account: Account| {
+        session
+            .token
+            .call::<_, AccountInfo>(OTHER_ID, "account", &account)
+            .expect("Querying an account should succeed")
+            .data
+            .balance
+    };
+    assert_eq!(
+        other_balance(&mut session, Account::Contract(TOKEN_ID)),
+        0,
+        "The token contract should no longer hold the other tokens"
+    );
+    assert_eq!(
+        other_balance(&mut session, Account::External(pk)),
+        STUCK_AMOUNT,
+        "The rescued tokens should be moved to the given account"
+    );
+}
+
+#[test]
+fn multi_token() {
+    const TOKEN: TokenId = 1;
+    const CAP: u64 = 500;
+    const TRANSFERRED_AMOUNT: u64 = 100;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let token_balance = |session: &mut ContractSession, token: TokenId, account: PublicKey| {
+        session
+            .call_token::<_, u64>(
+                "token_balance",
+                &TokenBalance {
+                    token,
+                    account: Account::External(account),
+                },
+            )
+            .expect("Querying a token balance should succeed")
+            .data
+    };
+
+    let create = CreateToken {
+        id: TOKEN,
+        metadata: TokenMetadata {
+            name: String::from("Second Token"),
+            symbol: String::from("SECOND"),
+            decimals: 6,
+        },
+        cap: Some(CAP),
+    };
+    session
+        .call_token::<_, ()>("create_token", &Signed::new(&sk, create.clone(), 1))
+        .expect_err("Only the admin should be able to create tokens");
+    session
+        .call_token::<_, ()>(
+            "create_token",
+            &Signed::new(&session.deploy_sk, create.clone(), 1),
+        )
+        .expect("Creating a token should succeed");
+    session
+        .call_token::<_, ()>("create_token", &Signed::new(&session.deploy_sk, create, 2))
+        .expect_err("Creating a token with an id in use should fail");
+
+    let mint = MintToken {
+        token: TOKEN,
+        to: Account::External(session.deploy_pk()),
+        value: CAP + 1,
+    };
+    session
+        .call_token::<_, ()>("mint_token", &Signed::new(&session.deploy_sk, mint, 2))
+        .expect_err("Minting above the cap should fail");
+
+    let mint = MintToken { value: CAP, ..mint };
+    session
+        .call_token::<_, ()>("mint_token", &Signed::new(&session.deploy_sk, mint, 2))
+        .expect("Minting up to the cap should succeed");
+
+    let info = session
+        .call_token::<_, Option<TokenInfo>>("token_info", &TOKEN)
+        .expect("Querying a token should succeed")
+        .data
+        .expect("The token should exist");
+    assert_eq!(info.supply, CAP, "The minted tokens should be in supply");
+
+    let transfer = TokenTransfer {
+        token: TOKEN,
+        to: Account::External(pk),
+        value: TRANSFERRED_AMOUNT,
+    };
+    session
+        .call_token::<_, ()>(
+            "token_transfer",
+            &Signed::new(&session.deploy_sk, transfer, 3),
+        )
+        .expect("Transferring a created token should succeed");
+
+    assert_eq!(
+        token_balance(&mut session, TOKEN, pk),
+        TRANSFERRED_AMOUNT,
+        "The created token should be credited"
+    );
+    assert_eq!(
+        session.account(pk).balance,
+        0,
+        "The default token should be untouched"
+    );
+
+    // the default id acts on the balances of the token the deployment was initialized with
+    let transfer = TokenTransfer {
+        token: DEFAULT_TOKEN_ID,
+        ..transfer
+    };
+    let receipt = session
+        .call_token::<_, ()>(
+            "token_transfer",
+            &Signed::new(&session.deploy_sk, transfer, 4),
+        )
+        .expect("Transferring the default token should succeed");
+
+    assert_transfer(
+        &receipt,
+        TOKEN_ID,
+        session.deploy_pk(),
+        pk,
+        TRANSFERRED_AMOUNT,
+    );
+    assert_eq!(
+        token_balance(&mut session, DEFAULT_TOKEN_ID, pk),
+        session.account(pk).balance,
+        "The default token balance should be the account balance"
+    );
+
+    let approve = TokenApprove {
+        token: TOKEN,
+        spender: Account::External(pk),
+        value: TRANSFERRED_AMOUNT,
+    };
+    session
+        .call_token::<_, ()>(
+            "token_approve",
+            &Signed::new(&session.deploy_sk, approve, 5),
+        )
+        .expect("Approving a created token should succeed");
+
+    let transfer = TokenTransferFrom {
+        token: TOKEN,
+        owner: Account::External(session.deploy_pk()),
+        to: Account::External(pk),
+        value: TRANSFERRED_AMOUNT,
+    };
+    session
+        .call_token::<_, ()>("token_transfer_from", &Signed::new(&sk, transfer, 1))
+        .expect("Transferring a created token from should succeed");
+    session
+        .call_token::<_, ()>("token_transfer_from", &Signed::new(&sk, transfer, 2))
+        .expect_err("Transferring beyond the allowance should fail");
+
+    assert_eq!(
+        token_balance(&mut session, TOKEN, pk),
+        2 * TRANSFERRED_AMOUNT,
+        "The created token should be credited"
+    );
+    assert_eq!(
+        session.allowance(session.deploy_pk(), pk),
+        0,
+        "The allowance of the default token should be untouched"
+    );
+
+    let holder_balance = |session: &mut ContractSession| -> u64 {
+        session
+            .call_token(
+                "token_balance",
+                &TokenBalance {
+                    token: TOKEN,
+                    account: Account::Contract(HOLDER_ID),
+                },
+            )
+            .expect("Querying a token balance should succeed")
+            .data
+    };
+
+    // created tokens are announced to receiving contracts, which may refuse them
+    let transfer = TokenTransfer {
+        token: TOKEN,
+        to: Account::Contract(HOLDER_ID),
+        value: TRANSFERRED_AMOUNT,
+    };
+    session.set_holder_mode(HolderMode::Panic);
+    session
+        .call_token::<_, ()>("token_transfer", &Signed::new(&sk, transfer, 2))
+        .expect_err("Transferring to a contract refusing the tokens should fail");
+    session.set_holder_mode(HolderMode::Normal);
+    session
+        .call_token::<_, ()>("token_transfer", &Signed::new(&sk, transfer, 2))
+        .expect("Transferring a created token to a contract should succeed");
+    assert_eq!(
+        holder_balance(&mut session),
+        TRANSFERRED_AMOUNT,
+        "The contract should be credited"
+    );
+
+    let receipt = session
+        .call_holder::<_, ()>(
+            "token_send_multi",
+            &TokenTransferFromContract {
+                token: TOKEN,
+                to: Account::External(pk),
+                from: None,
+                value: TRANSFERRED_AMOUNT,
+                hook_policy: None,
+                data: Vec::new(),
+            },
+        )
+        .expect("A contract should be able to transfer a created token");
+    assert_eq!(
+        holder_balance(&mut session),
+        0,
+        "The contract should be debited"
+    );
+    assert!(
+        receipt
+            .events
+            .iter()
+            .any(|event| event.topic == "token_transfer"),
+        "The transfer should be announced"
+    );
+
+    let burn = TokenBurn {
+        token: TOKEN,
+        value: TRANSFERRED_AMOUNT,
+    };
+    session
+        .call_token::<_, ()>("token_burn", &Signed::new(&sk, burn, 3))
+        .expect("Burning a created token should succeed");
+    assert_eq!(
+        token_balance(&mut session, TOKEN, pk),
+        TRANSFERRED_AMOUNT,
+        "The burned tokens should be debited"
+    );
+    let info = session
+        .call_token::<_, Option<TokenInfo>>("token_info", &TOKEN)
+        .expect("Querying a token should succeed")
+        .data
+        .expect("The token should exist");
+    assert_eq!(
+        info.supply,
+        CAP - TRANSFERRED_AMOUNT,
+        "The burned tokens should leave the supply"
+    );
+}
+
+#[test]
+fn flash_loan() {
+    const FEE: u16 = FLASH_LOAN_FEE_SCALE / 100;
+    const LOAN: u64 = 10 * INITIAL_HOLDER_BALANCE;
+    const LOAN_FEE: u64 = LOAN / 100;
+
+    let mut session = ContractSession::new_with(|args| {
+        args.flash_loan_fee = FEE;
+    });
+    let supply = session.token.total_supply();
+
+    let fee = session
+        .call_token::<_, u64>("flash_fee", &LOAN)
+        .expect("Querying the flash loan fee should succeed")
+        .data;
+    assert_eq!(
+        fee, LOAN_FEE,
+        "The fee should be the set fraction of the loan"
+    );
+
+    let loan = FlashLoan {
+        borrower: HOLDER_ID,
+        value: LOAN,
+        data: Vec::new(),
+    };
+    let receipt = session
+        .call_token::<_, ()>("flash_loan", &loan)
+        .expect("Taking a flash loan should succeed");
+
+    let events: Vec<Sequenced<FlashLoanEvent>> = decode_events(&receipt, TOKEN_ID, "flash_loan");
+    assert_eq!(events.len(), 1, "A flash loan event should be emitted");
+    assert_eq!(events[0].event.fee, LOAN_FEE);
+
+    assert_eq!(
+        session.account(HOLDER_ID).balance,
+        INITIAL_HOLDER_BALANCE - LOAN_FEE,
+        "The borrower should pay the fee"
+    );
+    assert_eq!(
+        session.account(TOKEN_ID).balance,
+        LOAN_FEE,
+        "The fee should be paid to the token contract"
+    );
+    assert_eq!(
+        session.token.total_supply(),
+        supply,
+        "The lent tokens should be burned on repayment"
+    );
+
+    // the fee on this loan is more than the borrower holds
+    let loan = FlashLoan {
+        value: 100 * LOAN,
+        ..loan
+    };
+    session
+        .call_token::<_, ()>("flash_loan", &loan)
+        .expect_err("A flash loan that isn't repaid should fail");
+
+    session.set_holder_mode(HolderMode::Panic);
+    let loan = FlashLoan {
+        value: LOAN,
+        ..loan
+    };
+    session
+        .call_token::<_, ()>("flash_loan", &loan)
+        .expect_err("A flash loan to a panicking borrower should fail");
+
+    let loan = FlashLoan {
+        borrower: MISSING_ID,
+        ..loan
+    };
+    session
+        .call_token::<_, ()>("flash_loan", &loan)
+        .expect_err("A flash loan to a missing contract should fail");
+
+    assert_eq!(
+        session.account(HOLDER_ID).balance,
+        INITIAL_HOLDER_BALANCE - LOAN_FEE,
+        "Failed flash loans should leave the borrower untouched"
+    );
+}
+
+#[test]
+fn clawback() {
+    const DELAY: u64 = 10;
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 2;
+    const CLAWED_AMOUNT: u64 = TRANSFERRED_AMOUNT / 2;
+
+    let mut session = ContractSession::new_with(|args| {
+        args.clawback_delay = Some(DELAY);
+    });
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+
+    let announce = AnnounceClawback {
+        account: Account::External(pk),
+        to: Account::External(session.deploy_pk()),
+        value: CLAWED_AMOUNT,
+    };
+    session
+        .call_token::<_, u64>("announce_clawback", &Signed::new(&sk, announce, 1))
+        .expect_err("Only the admin should be able to announce a clawback");
+
+    let id = session
+        .call_token::<_, u64>(
+            "announce_clawback",
+            &Signed::new(&session.deploy_sk, announce, 2),
+        )
+        .expect("Announcing a clawback should succeed")
+        .data;
+    let cancelled_id = session
+        .call_token::<_, u64>(
+            "announce_clawback",
+            &Signed::new(&session.deploy_sk, announce, 3),
+        )
+        .expect("Announcing a clawback should succeed")
+        .data;
+
+    let execute = ExecuteClawback { id };
+    session
+        .call_token::<_, ()>(
+            "execute_clawback",
+            &Signed::new(&session.deploy_sk, execute, 4),
+        )
+        .expect_err("Executing a clawback before its delay should fail");
+
+    let cancel = CancelClawback { id: cancelled_id };
+    session
+        .call_token::<_, ()>(
+            "cancel_clawback",
+            &Signed::new(&session.deploy_sk, cancel, 4),
+        )
+        .expect("Cancelling a clawback should succeed");
+
+    session.set_block_height(DELAY);
+
+    let receipt = session
+        .call_token::<_, ()>(
+            "execute_clawback",
+            &Signed::new(&session.deploy_sk, execute, 5),
+        )
+        .expect("Executing a clawback after its delay should succeed");
+    assert_transfer(&receipt, TOKEN_ID, pk, session.deploy_pk(), CLAWED_AMOUNT);
+
+    let execute = ExecuteClawback { id: cancelled_id };
+    session
+        .call_token::<_, ()>(
+            "execute_clawback",
+            &Signed::new(&session.deploy_sk, execute, 6),
+        )
+        .expect_err("Executing a cancelled clawback should fail");
+
+    assert_eq!(
+        session.account(pk).balance,
+        TRANSFERRED_AMOUNT - CLAWED_AMOUNT,
+        "The clawed back tokens should be taken from the account"
+    );
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - TRANSFERRED_AMOUNT + CLAWED_AMOUNT,
+        "The clawed back tokens should be moved to the given account"
+    );
+}
+
+#[test]
+fn circuit_breaker() {
+    const ACCOUNT_LIMIT: u64 = 100;
+    const WINDOW_BLOCKS: u64 = 10;
+    const BLOCK_LIMIT: u64 = 150;
+    const THRESHOLD: u64 = 10;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, INITIAL_BALANCE / 2, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring without limits should succeed");
+
+    let limits = SetRateLimits {
+        limits: RateLimits {
+            account_limit: Some(ACCOUNT_LIMIT),
+            window_blocks: WINDOW_BLOCKS,
+            block_limit: Some(BLOCK_LIMIT),
+        },
+    };
+    session
+        .call_token::<_, ()>(
+            "set_rate_limits",
+            &Signed::new(&session.deploy_sk, limits, 2),
+        )
+        .expect("Setting the rate limits should succeed");
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, 60, 3);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring within the limits should succeed");
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, 50, 4);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring above the account limit should fail");
+
+    let transfer = Transfer::new(&sk, session.deploy_pk(), 100, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring above the block limit should fail");
+
+    let transfer = Transfer::new(&sk, session.deploy_pk(), 90, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring up to the block limit should succeed");
+
+    session.set_block_height(WINDOW_BLOCKS);
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, 50, 4);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring in a new window should succeed");
+
+    session
+        .call_token::<_, ()>(
+            "trip_breaker",
+            &Signed::new(
+                &sk,
+                TripBreaker {
+                    threshold: THRESHOLD,
+                },
+                2,
+            ),
+        )
+        .expect_err("Only the admin should be able to trip the breaker");
+    session
+        .call_token::<_, ()>(
+            "trip_breaker",
+            &Signed::new(
+                &session.deploy_sk,
+                TripBreaker {
+                    threshold: THRESHOLD,
+                },
+                5,
+            ),
+        )
+        .expect("Tripping the breaker should succeed");
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, 2 * THRESHOLD, 6);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring above the threshold of a tripped breaker should fail");
+
+    let locked = TransferLocked {
+        to: Account::External(pk),
+        value: 2 * THRESHOLD,
+        release_height: 2 * WINDOW_BLOCKS,
+    };
+    session
+        .call_token::<_, u64>(
+            "transfer_locked",
+            &Signed::new(&session.deploy_sk, locked, 6),
+        )
+        .expect_err("Locking tokens above the threshold of a tripped breaker should fail");
+
+    let propose = ProposeTransfer {
+        to: Account::External(pk),
+        value: 2 * THRESHOLD,
+        expiry_height: 2 * WINDOW_BLOCKS,
+    };
+    session
+        .call_token::<_, u64>(
+            "propose_transfer",
+            &Signed::new(&session.deploy_sk, propose, 6),
+        )
+        .expect_err("Proposing a transfer above the threshold of a tripped breaker should fail");
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, THRESHOLD, 6);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring up to the threshold of a tripped breaker should succeed");
+
+    session
+        .call_token::<_, ()>(
+            "reset_breaker",
+            &Signed::new(&session.deploy_sk, ResetBreaker, 7),
+        )
+        .expect("Resetting the breaker should succeed");
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, 2 * THRESHOLD, 8);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring after resetting the breaker should succeed");
+
+    let info = session
+        .call_token::<_, BreakerInfo>("breaker", &())
+        .expect("Querying the breaker should succeed")
+        .data;
+    assert_eq!(info.limits, limits.limits);
+    assert_eq!(info.tripped_threshold, None, "The breaker should be reset");
+}
+
+#[test]
+fn min_transfer() {
+    const MIN_TRANSFER: u64 = 10;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let set = SetMinTransfer {
+        min_transfer: MIN_TRANSFER,
+    };
+    session
+        .call_token::<_, ()>("set_min_transfer", &Signed::new(&sk, set, 1))
+        .expect_err("Only the admin should be able to set the minimum transfer");
+    session
+        .call_token::<_, ()>("set_min_transfer", &Signed::new(&session.deploy_sk, set, 1))
+        .expect("Setting the minimum transfer should succeed");
+
+    let min_transfer: u64 = session
+        .call_token("min_transfer", &())
+        .expect("Querying the minimum transfer should succeed")
+        .data;
+    assert_eq!(min_transfer, MIN_TRANSFER);
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, MIN_TRANSFER - 1, 2);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring less than the minimum should fail");
+
+    let approve = Approve::new(&session.deploy_sk, pk, MIN_TRANSFER, 2);
+    session
+        .call_token::<_, ()>("approve", &approve)
+        .expect("Approving should succeed");
+    let transfer = TransferFrom::new(&sk, session.deploy_pk(), pk, MIN_TRANSFER - 1, 1);
+    session
+        .call_token::<_, ()>("transfer_from", &transfer)
+        .expect_err("Transferring less than the minimum from an owner should fail");
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, MIN_TRANSFER + 1, 3);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring the minimum or more should succeed");
+
+    session
+        .call_token::<_, ()>(
+            "transfer",
+            &Transfer::new(&sk, session.deploy_pk(), MIN_TRANSFER + 1, 1),
+        )
+        .expect("Transferring the whole balance below the minimum should succeed");
+    assert_eq!(session.account(pk).balance, 0);
+
+    let set = SetMinTransfer { min_transfer: 0 };
+    session
+        .call_token::<_, ()>("set_min_transfer", &Signed::new(&session.deploy_sk, set, 4))
+        .expect("Resetting the minimum transfer should succeed");
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, 1, 5);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring any value without a minimum should succeed");
+}
+
+#[test]
+fn emission() {
+    const RATE: u64 = 5;
+    const EPOCH_BLOCKS: u64 = 10;
+    const END_HEIGHT: u64 = 35;
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let treasury_sk = SecretKey::random(&mut rng);
+    let treasury_pk = PublicKey::from(&treasury_sk);
+
+    let mut session = ContractSession::new_with(|args| {
+        args.emission = Some(EmissionSchedule {
+            rate: RATE,
+            epoch_blocks: EPOCH_BLOCKS,
+            treasury: Account::External(treasury_pk),
+            end_height: Some(END_HEIGHT),
+        });
+    });
+
+    let minted: u64 = session
+        .call_token("poke_emission", &())
+        .expect("Poking the emission should succeed")
+        .data;
+    assert_eq!(minted, 0, "Nothing should be minted in the first epoch");
+
+    session.set_block_height(2 * EPOCH_BLOCKS + 5);
+
+    let receipt = session
+        .call_token::<_, u64>("poke_emission", &())
+        .expect("Poking the emission should succeed");
+    assert_eq!(receipt.data, 2 * RATE, "Two epochs should be minted");
+
+    let events: Vec<Sequenced<EmissionEvent>> = decode_events(&receipt, TOKEN_ID, "emission");
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events[0].event,
+        EmissionEvent {
+            treasury: Account::External(treasury_pk),
+            value: 2 * RATE,
+            epochs_emitted: 2,
+        }
+    );
+
+    let minted: u64 = session
+        .call_token("poke_emission", &())
+        .expect("Poking the emission should succeed")
+        .data;
+    assert_eq!(minted, 0, "Epochs should only be minted once");
+
+    session.set_block_height(10 * EPOCH_BLOCKS);
+
+    let minted: u64 = session
+        .call_token("poke_emission", &())
+        .expect("Poking the emission should succeed")
+        .data;
+    assert_eq!(minted, RATE, "No epochs should accrue after the end height");
+
+    assert_eq!(session.account(treasury_pk).balance, 3 * RATE);
+    assert_eq!(
+        session.token.total_supply(),
+        INITIAL_BALANCE + INITIAL_HOLDER_BALANCE + 3 * RATE
+    );
+
+    let info: Option<EmissionInfo> = session
+        .call_token("emission", &())
+        .expect("Querying the emission should succeed")
+        .data;
+    let info = info.expect("The emission should be returned");
+    assert_eq!(info.start_height, 0);
+    assert_eq!(info.epochs_emitted, 3);
+}
+
+#[test]
+fn no_emission() {
+    let mut session = ContractSession::new();
+
+    session
+        .call_token::<_, u64>("poke_emission", &())
+        .expect_err("Poking the emission of a token without one should fail");
+
+    let info: Option<EmissionInfo> = session
+        .call_token("emission", &())
+        .expect("Querying the emission should succeed")
+        .data;
+    assert_eq!(info, None);
+}
+
+#[test]
+fn secp256k1_and_ed25519_signers() {
+    const TRANSFERRED_AMOUNT: u64 = INITIAL_BALANCE / 4;
+
+    use ed25519_dalek::Signer as _;
+    use k256::ecdsa::signature::Signer as _;
+
+    let mut session = ContractSession::new();
+
+    let secp_sk = k256::ecdsa::SigningKey::from_slice(&[0x11; 32]).unwrap();
+    let secp_pk = Secp256k1PublicKey(
+        secp_sk
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .try_into()
+            .unwrap(),
+    );
+
+    let ed_sk = ed25519_dalek::SigningKey::from_bytes(&[0x22; 32]);
+    let ed_pk = Ed25519PublicKey(ed_sk.verifying_key().to_bytes());
+
+    for (account, nonce) in [
+        (Account::Secp256k1(secp_pk), 1),
+        (Account::Ed25519(ed_pk), 2),
+    ] {
+        let transfer = Transfer::new(&session.deploy_sk, account, TRANSFERRED_AMOUNT, nonce);
+        session
+            .call_token::<_, ()>("transfer", &transfer)
+            .expect("Transferring to the account should succeed");
+    }
+
+    let op = TransferRaw {
+        to: Account::External(session.deploy_pk()),
+        value: TRANSFERRED_AMOUNT / 2,
+    };
+
+    let mut signed = Signed::from_scheme_parts(secp_pk, op, 1, Secp256k1Signature([0; 64]));
+    let sig: k256::ecdsa::Signature = secp_sk.sign(&signed.signature_message());
+    signed.set_signature(Secp256k1Signature(
+        sig.to_bytes().as_slice().try_into().unwrap(),
+    ));
+    assert!(signed.verify(), "The secp256k1 signature should be valid");
+
+    // a signature of another scheme, or over the message of another key, is rejected
+    let mut forged = signed;
+    forged.set_signature(Ed25519Signature(
+        ed_sk.sign(&signed.signature_message()).to_bytes(),
+    ));
+    assert!(!forged.verify());
+    session
+        .call_token::<_, ()>("transfer_raw", &forged)
+        .expect_err("Transferring with a signature of another scheme should fail");
+
+    session
+        .call_token::<_, ()>("transfer_raw", &signed)
+        .expect("Transferring from a secp256k1 account should succeed");
+
+    let mut signed = Signed::from_scheme_parts(ed_pk, op, 1, Ed25519Signature([0; 64]));
+    let sig = ed_sk.sign(&signed.signature_message());
+    signed.set_signature(Ed25519Signature(sig.to_bytes()));
+    assert!(signed.verify(), "The Ed25519 signature should be valid");
+
+    session
+        .call_token::<_, ()>("transfer_raw", &signed)
+        .expect("Transferring from an Ed25519 account should succeed");
+    session
+        .call_token::<_, ()>("transfer_raw", &signed)
+        .expect_err("Replaying a transfer should fail");
+
+    // transfers and approvals can be signed with the other schemes too
+    let mut transfer = Transfer::from_parts(
+        secp_pk,
+        session.deploy_pk(),
+        1,
+        2,
+        Secp256k1Signature([0; 64]),
+    );
+    let sig: k256::ecdsa::Signature = secp_sk.sign(&transfer.signature_message());
+    transfer.set_signature(Secp256k1Signature(
+        sig.to_bytes().as_slice().try_into().unwrap(),
+    ));
+    assert!(transfer.verify(), "The secp256k1 signature should be valid");
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring from a secp256k1 account should succeed");
+
+    let mut approve =
+        Approve::from_parts(ed_pk, session.deploy_pk(), 1, 2, Ed25519Signature([0; 64]));
+    let sig = ed_sk.sign(&approve.signature_message());
+    approve.set_signature(Ed25519Signature(sig.to_bytes()));
+    assert!(approve.verify(), "The Ed25519 signature should be valid");
+    session
+        .call_token::<_, ()>("approve", &approve)
+        .expect("Approving from an Ed25519 account should succeed");
+
+    let transfer = TransferFrom::new(
+        &session.deploy_sk,
+        Account::Ed25519(ed_pk),
+        session.deploy_pk(),
+        1,
+        3,
+    );
+    session
+        .call_token::<_, ()>("transfer_from", &transfer)
+        .expect("Spending the approved tokens should succeed");
+
+    assert_eq!(
+        session.account(secp_pk).balance,
+        TRANSFERRED_AMOUNT - TRANSFERRED_AMOUNT / 2 - 1
+    );
+    assert_eq!(
+        session.account(ed_pk).balance,
+        TRANSFERRED_AMOUNT - TRANSFERRED_AMOUNT / 2 - 1
+    );
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - TRANSFERRED_AMOUNT + 2
+    );
+}
+
+#[test]
+fn secp256k1_and_ed25519_account_strings() {
+    let secp_sk = k256::ecdsa::SigningKey::from_slice(&[0x11; 32]).unwrap();
+    let secp_pk = Secp256k1PublicKey(
+        secp_sk
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .try_into()
+            .unwrap(),
+    );
+    let ed_pk = Ed25519PublicKey(
+        ed25519_dalek::SigningKey::from_bytes(&[0x22; 32])
+            .verifying_key()
+            .to_bytes(),
+    );
+
+    for account in [Account::Secp256k1(secp_pk), Account::Ed25519(ed_pk)] {
+        let string = account.to_string();
+        assert_eq!(string.parse::<Account>(), Ok(account));
+        assert_eq!(Account::from_bytes(&account.to_bytes()), Ok(account));
+    }
+
+    let mut bytes = Account::Secp256k1(secp_pk).to_bytes();
+    bytes[1] = 0x05;
+    assert_eq!(
+        Account::from_bytes(&bytes),
+        Err(AccountBytesError::InvalidPublicKey),
+        "Keys not on the curve should be rejected"
+    );
+}
+
+#[test]
+fn session_key() {
+    const MAX_VALUE: u64 = 20;
+    const BUDGET: u64 = 30;
+    const EXPIRY_HEIGHT: u64 = 10;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let session_sk = SecretKey::random(&mut rng);
+    let session_pk = PublicKey::from(&session_sk);
+    let other_sk = SecretKey::random(&mut rng);
+    let other_pk = PublicKey::from(&other_sk);
+
+    let owner = Account::External(session.deploy_pk());
+    let consent = Signed::new(&session_sk, SessionKeyConsent { owner }, 1);
+    let register = RegisterSessionKey {
+        key: session_pk,
+        scope: SessionKeyScope {
+            max_value: Some(MAX_VALUE),
+            budget: BUDGET,
+            recipients: Some(vec![Account::Contract(HOLDER_ID)]),
+            expiry_height: EXPIRY_HEIGHT,
+        },
+        consent,
+    };
+
+    let forged = RegisterSessionKey {
+        consent: Signed::new(&other_sk, SessionKeyConsent { owner }, 1),
+        ..register.clone()
+    };
+    session
+        .call_token::<_, ()>(
+            "register_session_key",
+            &Signed::new(&session.deploy_sk, forged, 1),
+        )
+        .expect_err("Registering a session key without its consent should fail");
+
+    let receipt = session
+        .call_token::<_, ()>(
+            "register_session_key",
+            &Signed::new(&session.deploy_sk, register.clone(), 1),
+        )
+        .expect("Registering a session key should succeed");
+
+    let events: Vec<Sequenced<SessionKeyEvent>> = decode_events(&receipt, TOKEN_ID, "session_key");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event.scope, Some(register.scope.clone()));
+
+    session
+        .call_token::<_, ()>(
+            "register_session_key",
+            &Signed::new(&other_sk, register.clone(), 1),
+        )
+        .expect_err("Registering another account's session key should fail");
+
+    let transfer = Transfer::new(&session_sk, HOLDER_ID, MAX_VALUE, 2);
+    let receipt = session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring with a session key should succeed");
+    let event = assert_transfer(
+        &receipt,
+        TOKEN_ID,
+        session.deploy_pk(),
+        HOLDER_ID,
+        MAX_VALUE,
+    );
+    assert_eq!(event.spender, Some(Account::External(session_pk)));
+
+    let transfer = Transfer::new(&session_sk, HOLDER_ID, MAX_VALUE + 1, 3);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring above the maximum of a session key should fail");
+
+    let transfer = Transfer::new(&session_sk, other_pk, 1, 3);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring to a recipient outside of the scope should fail");
+
+    let transfer = Transfer::new(&session_sk, HOLDER_ID, BUDGET - MAX_VALUE + 1, 3);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring above the budget of a session key should fail");
+
+    let transfer = Transfer::new(&session_sk, HOLDER_ID, BUDGET - MAX_VALUE, 3);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring up to the budget should succeed");
+
+    let info: Option<SessionKey> = session
+        .call_token("session_key", &session_pk)
+        .expect("Querying a session key should succeed")
+        .data;
+    let info = info.expect("The session key should be registered");
+    assert_eq!(info.owner, Account::External(session.deploy_pk()));
+    assert_eq!(info.spent, BUDGET);
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - BUDGET
+    );
+    assert_eq!(
+        session.account(HOLDER_ID).balance,
+        INITIAL_HOLDER_BALANCE + BUDGET
+    );
+
+    session
+        .call_token::<_, ()>(
+            "register_session_key",
+            &Signed::new(&session.deploy_sk, register.clone(), 2),
+        )
+        .expect_err("Registering a session key with a used consent should fail");
+
+    // registering the key again starts its budget over
+    let register = RegisterSessionKey {
+        consent: Signed::new(&session_sk, SessionKeyConsent { owner }, 4),
+        ..register
+    };
+    session
+        .call_token::<_, ()>(
+            "register_session_key",
+            &Signed::new(&session.deploy_sk, register, 3),
+        )
+        .expect("Registering a session key again should succeed");
+
+    session.set_block_height(EXPIRY_HEIGHT);
+
+    let transfer = Transfer::new(&session_sk, HOLDER_ID, 1, 5);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring with an expired session key should fail");
+
+    let revoke = RevokeSessionKey { key: session_pk };
+    session
+        .call_token::<_, ()>("revoke_session_key", &Signed::new(&other_sk, revoke, 1))
+        .expect_err("Revoking another account's session key should fail");
+    session
+        .call_token::<_, ()>("revoke_session_key", &Signed::new(&session_sk, revoke, 6))
+        .expect("Revoking a session key by the key itself should succeed");
+
+    let info: Option<SessionKey> = session
+        .call_token("session_key", &session_pk)
+        .expect("Querying a session key should succeed")
+        .data;
+    assert_eq!(info, None);
+}
+
+#[test]
+fn audit() {
+    const LOCKED_AMOUNT: u64 = 100;
+    const PROPOSED_AMOUNT: u64 = 50;
+    const STAKED_AMOUNT: u64 = 200;
+
+    let mut session = ContractSession::new();
+
+    let locked = TransferLocked {
+        to: Account::Contract(HOLDER_ID),
+        value: LOCKED_AMOUNT,
+        release_height: 10,
+    };
+    session
+        .call_token::<_, u64>(
+            "transfer_locked",
+            &Signed::new(&session.deploy_sk, locked, 1),
+        )
+        .expect("Locking a transfer should succeed");
+
+    let propose = ProposeTransfer {
+        to: Account::Contract(HOLDER_ID),
+        value: PROPOSED_AMOUNT,
+        expiry_height: 10,
+    };
+    session
+        .call_token::<_, u64>(
+            "propose_transfer",
+            &Signed::new(&session.deploy_sk, propose, 2),
+        )
+        .expect("Proposing a transfer should succeed");
+
+    let stake = Stake {
+        value: STAKED_AMOUNT,
+        lock_blocks: 10,
+    };
+    session
+        .call_token::<_, ()>("stake", &Signed::new(&session.deploy_sk, stake, 3))
+        .expect("Staking should succeed");
+
+    let (sender, receiver) = mpsc::channel();
+    session
+        .token
+        .session()
+        .feeder_call::<_, ()>(TOKEN_ID, "audit", &(), u64::MAX, sender)
+        .expect("Feeding the audit should succeed");
+
+    let audits: Vec<Audit> = receiver
+        .into_iter()
+        .map(|bytes| rkyv::from_bytes(&bytes).expect("The fed audit should be valid"))
+        .collect();
+    assert_eq!(audits.len(), 1, "A single audit should be fed");
+
+    let audit = audits[0];
+    assert!(audit.is_balanced(), "The books should balance: {audit:?}");
+    assert_eq!(audit.supply, INITIAL_BALANCE + INITIAL_HOLDER_BALANCE);
+    assert_eq!(
+        audit.balances,
+        INITIAL_BALANCE + INITIAL_HOLDER_BALANCE - LOCKED_AMOUNT - PROPOSED_AMOUNT - STAKED_AMOUNT
+    );
+    assert_eq!(audit.locked, LOCKED_AMOUNT);
+    assert_eq!(audit.pending, PROPOSED_AMOUNT);
+    assert_eq!(audit.staked, STAKED_AMOUNT);
+}
+
+#[test]
+fn state_root() {
+    const TRANSFERRED_AMOUNT: u64 = 100;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let root_before = session
+        .call_token::<_, [u8; 32]>("state_root", &())
+        .expect("Querying the state root should succeed")
+        .data;
+    assert_ne!(
+        root_before, EMPTY_STATE_ROOT,
+        "The initial balances should be in the tree"
+    );
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, TRANSFERRED_AMOUNT, 1);
+    session
+        .token
+        .transfer(&transfer)
+        .expect("Transferring should succeed");
+
+    let root = session
+        .call_token::<_, [u8; 32]>("state_root", &())
+        .expect("Querying the state root should succeed")
+        .data;
+    assert_ne!(
+        root, root_before,
+        "The transfer should change the state root"
+    );
+
+    let accounts = [
+        Account::External(session.deploy_pk()),
+        Account::Contract(HOLDER_ID),
+        Account::External(pk),
+    ];
+    for account in accounts {
+        let (sender, receiver) = mpsc::channel();
+        session
+            .token
+            .session()
+            .feeder_call::<_, ()>(TOKEN_ID, "proof_of", &account, u64::MAX, sender)
+            .expect("Feeding the proof should succeed");
+
+        let proofs: Vec<AccountProof> = receiver
+            .into_iter()
+            .map(|bytes| rkyv::from_bytes(&bytes).expect("The fed proof should be valid"))
+            .collect();
+        assert_eq!(proofs.len(), 1, "A single proof should be fed");
+
+        let mut proof = proofs[0].clone();
+        assert!(proof.verify(), "The proof of {account} should be valid");
+        assert_eq!(proof.root, root, "The proof should be for the current root");
+        assert_eq!(proof.info, session.account(account));
+
+        proof.info.balance += 1;
+        assert!(!proof.verify(), "A tampered proof should be invalid");
+    }
+
+    let (sender, _receiver) = mpsc::channel();
+    session
+        .token
+        .session()
+        .feeder_call::<_, ()>(
+            TOKEN_ID,
+            "proof_of",
+            &Account::Contract(MISSING_ID),
+            u64::MAX,
+            sender,
+        )
+        .expect_err("Proving an account not in the tree should fail");
+}
+
+#[test]
+fn transfer_memo() {
+    const TRANSFERRED_AMOUNT: u64 = 100;
+    const MEMO: Memo = [7; 32];
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let transfer = Transfer::new_with_memo(&session.deploy_sk, pk, TRANSFERRED_AMOUNT, 1, MEMO);
+    assert_eq!(transfer.memo(), Some(&MEMO));
+    let receipt = session
+        .token
+        .transfer(&transfer)
+        .expect("Transferring with a memo should succeed");
+
+    let event = assert_transfer(
+        &receipt,
+        TOKEN_ID,
+        session.deploy_pk(),
+        pk,
+        TRANSFERRED_AMOUNT,
+    );
+    assert_eq!(event.memo, Some(MEMO), "The event should carry the memo");
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, TRANSFERRED_AMOUNT, 2);
+    let receipt = session
+        .token
+        .transfer(&transfer)
+        .expect("Transferring without a memo should succeed");
+
+    let event = assert_transfer(
+        &receipt,
+        TOKEN_ID,
+        session.deploy_pk(),
+        pk,
+        TRANSFERRED_AMOUNT,
+    );
+    assert_eq!(event.memo, None, "The event should carry no memo");
+
+    // the memo is covered by the signature, so it can't be swapped for another
+    let transfer = Transfer::builder()
+        .to(pk)
+        .value(TRANSFERRED_AMOUNT)
+        .nonce(3)
+        .memo(MEMO)
+        .sign(&session.deploy_sk);
+    let signature = *transfer.signature();
+    let forged = Transfer::builder()
+        .to(pk)
+        .value(TRANSFERRED_AMOUNT)
+        .nonce(3)
+        .memo([8; 32])
+        .with_signature(session.deploy_pk(), signature);
+    assert!(
+        !forged.verify(),
+        "A transfer with a swapped memo should be invalid"
+    );
+    session
+        .token
+        .transfer(&forged)
+        .expect_err("Transferring with a swapped memo should fail");
+
+    let approve = Approve::new(&session.deploy_sk, pk, TRANSFERRED_AMOUNT, 3);
+    session
+        .token
+        .approve(&approve)
+        .expect("Approving should succeed");
+
+    let transfer =
+        TransferFrom::new_with_memo(&sk, session.deploy_pk(), pk, TRANSFERRED_AMOUNT, 1, MEMO);
+    let receipt = session
+        .token
+        .transfer_from(&transfer)
+        .expect("Transferring from with a memo should succeed");
+
+    let event = assert_transfer(
+        &receipt,
+        TOKEN_ID,
+        session.deploy_pk(),
+        pk,
+        TRANSFERRED_AMOUNT,
+    );
+    assert_eq!(event.memo, Some(MEMO), "The event should carry the memo");
+}
+
+#[test]
+fn pause_scopes() {
+    const TRANSFERRED_AMOUNT: u64 = 100;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let pause = Pause {
+        scope: PauseScope::TRANSFERS | PauseScope::MINT,
+    };
+    let receipt = session
+        .call_token::<_, ()>("pause", &Signed::new(&session.deploy_sk, pause, 1))
+        .expect("Pausing should succeed");
+
+    let events: Vec<PauseEvent> = token_events(&receipt, TOKEN_ID)
+        .into_iter()
+        .map(|event| match event {
+            TokenEvent::Pause(pause) => pause.event,
+            event => panic!("Only pause events should be emitted, but got {event:?}"),
+        })
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            PauseEvent {
+                scope: PauseScope::TRANSFERS,
+                paused: true,
+            },
+            PauseEvent {
+                scope: PauseScope::MINT,
+                paused: true,
+            },
+        ],
+        "An event should be emitted for each scope paused"
+    );
+
+    let paused: PauseScope = session
+        .call_token("paused", &())
+        .expect("Querying the paused scopes should succeed")
+        .data;
+    assert_eq!(paused, PauseScope::TRANSFERS | PauseScope::MINT);
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, TRANSFERRED_AMOUNT, 2);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Transferring while transfers are paused should fail");
+
+    let approve = Approve::new(&session.deploy_sk, pk, TRANSFERRED_AMOUNT, 2);
+    session
+        .call_token::<_, ()>("approve", &approve)
+        .expect("Approving while only transfers are paused should succeed");
+
+    let transfer = TransferFrom::new(&sk, session.deploy_pk(), pk, TRANSFERRED_AMOUNT, 1);
+    session
+        .call_token::<_, ()>("transfer_from", &transfer)
+        .expect_err("Transferring from while transfers are paused should fail");
+
+    let pause = Pause {
+        scope: PauseScope::APPROVALS,
+    };
+    session
+        .call_token::<_, ()>("pause", &Signed::new(&session.deploy_sk, pause, 3))
+        .expect("Pausing approvals should succeed");
+
+    let approve = Approve::new(&session.deploy_sk, pk, TRANSFERRED_AMOUNT * 2, 4);
+    session
+        .call_token::<_, ()>("approve", &approve)
+        .expect_err("Approving while approvals are paused should fail");
+
+    let approve = Approve::new(&session.deploy_sk, pk, 0, 4);
+    session
+        .call_token::<_, ()>("approve", &approve)
+        .expect("Revoking an approval while approvals are paused should succeed");
+
+    let unpause = Unpause {
+        scope: PauseScope::ALL,
+    };
+    let receipt = session
+        .call_token::<_, ()>("unpause", &Signed::new(&session.deploy_sk, unpause, 5))
+        .expect("Unpausing should succeed");
+
+    let events = token_events(&receipt, TOKEN_ID);
+    assert_eq!(events.len(), 3, "Every paused scope should be resumed");
+    assert!(events
+        .iter()
+        .all(|event| matches!(event, TokenEvent::Pause(pause) if !pause.event.paused)));
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, TRANSFERRED_AMOUNT, 6);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring once resumed should succeed");
+
+    let pause = Pause {
+        scope: PauseScope::ALL,
+    };
+    session
+        .call_token::<_, ()>("pause", &Signed::new(&sk, pause, 1))
+        .expect_err("Pausing should only be allowed for the admin");
+}
+
+#[test]
+fn pause_stops_escrows_and_key_rotation() {
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let new_sk = SecretKey::random(&mut rng);
+    let new_pk = PublicKey::from(&new_sk);
+
+    let pause = Pause {
+        scope: PauseScope::TRANSFERS,
+    };
+    session
+        .call_token::<_, ()>("pause", &Signed::new(&session.deploy_sk, pause, 1))
+        .expect("Pausing should succeed");
+
+    let stake = Stake {
+        value: 100,
+        lock_blocks: 0,
+    };
+    session
+        .call_token::<_, ()>("stake", &Signed::new(&session.deploy_sk, stake, 2))
+        .expect_err("Staking while transfers are paused should fail");
+
+    let distribute = Distribute { value: 100 };
+    session
+        .call_token::<_, ()>(
+            "distribute",
+            &Signed::new(&session.deploy_sk, distribute, 2),
+        )
+        .expect_err("Distributing while transfers are paused should fail");
+
+    let rotate = RotateKey {
+        new_key: new_pk.into(),
+    };
+    session
+        .call_token::<_, ()>("rotate_key", &Signed::new(&session.deploy_sk, rotate, 2))
+        .expect_err("Rotating a key while transfers are paused should fail");
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE,
+        "No tokens should have moved while transfers are paused"
+    );
+}
+
+#[test]
+fn burn() {
+    const BURNED_AMOUNT: u64 = 100;
+    const APPROVED_AMOUNT: u64 = 50;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let burn = Burn {
+        value: BURNED_AMOUNT,
+    };
+    let receipt = session
+        .call_token::<_, ()>("burn", &Signed::new(&session.deploy_sk, burn, 1))
+        .expect("Burning should succeed");
+
+    match &token_events(&receipt, TOKEN_ID)[..] {
+        [TokenEvent::Burn(burn)] => assert_eq!(
+            burn.event,
+            BurnEvent {
+                owner: Account::External(session.deploy_pk()),
+                spender: None,
+                value: BURNED_AMOUNT,
+            }
+        ),
+        events => panic!("A single burn event should be emitted, but got {events:?}"),
+    }
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - BURNED_AMOUNT
+    );
+    let supply: u64 = session
+        .call_token("total_supply", &())
+        .expect("Querying the supply should succeed")
+        .data;
+    assert_eq!(
+        supply,
+        INITIAL_BALANCE + INITIAL_HOLDER_BALANCE - BURNED_AMOUNT,
+        "The burned tokens should be removed from the supply"
+    );
+
+    let approve = Approve::new(&session.deploy_sk, pk, APPROVED_AMOUNT, 2);
+    session
+        .token
+        .approve(&approve)
+        .expect("Approving should succeed");
+
+    let burn = BurnFrom {
+        owner: Account::External(session.deploy_pk()),
+        value: APPROVED_AMOUNT + 1,
+    };
+    session
+        .call_token::<_, ()>("burn_from", &Signed::new(&sk, burn, 1))
+        .expect_err("Burning more than the allowance should fail");
+
+    let burn = BurnFrom {
+        owner: Account::External(session.deploy_pk()),
+        value: APPROVED_AMOUNT,
+    };
+    let receipt = session
+        .call_token::<_, ()>("burn_from", &Signed::new(&sk, burn, 1))
+        .expect("Burning within the allowance should succeed");
+
+    match &token_events(&receipt, TOKEN_ID)[..] {
+        [TokenEvent::Burn(burn)] => assert_eq!(burn.event.spender, Some(Account::External(pk))),
+        events => panic!("A single burn event should be emitted, but got {events:?}"),
+    }
+
+    assert_eq!(session.allowance(session.deploy_pk(), pk), 0);
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - BURNED_AMOUNT - APPROVED_AMOUNT
+    );
+    let supply: u64 = session
+        .call_token("total_supply", &())
+        .expect("Querying the supply should succeed")
+        .data;
+    assert_eq!(
+        supply,
+        INITIAL_BALANCE + INITIAL_HOLDER_BALANCE - BURNED_AMOUNT - APPROVED_AMOUNT
+    );
+}
+
+#[test]
+fn rotate_key() {
+    const APPROVED_AMOUNT: u64 = 50;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let new_sk = SecretKey::random(&mut rng);
+    let new_pk = PublicKey::from(&new_sk);
+    let spender_sk = SecretKey::random(&mut rng);
+    let spender_pk = PublicKey::from(&spender_sk);
+
+    let approve = Approve::new(&session.deploy_sk, spender_pk, APPROVED_AMOUNT, 1);
+    session
+        .token
+        .approve(&approve)
+        .expect("Approving should succeed");
+
+    let rotate = RotateKey {
+        new_key: new_pk.into(),
+    };
+    let receipt = session
+        .call_token::<_, ()>("rotate_key", &Signed::new(&session.deploy_sk, rotate, 2))
+        .expect("Rotating the key should succeed");
+
+    match &token_events(&receipt, TOKEN_ID)[..] {
+        [TokenEvent::KeyRotated(rotated)] => assert_eq!(
+            rotated.event,
+            KeyRotated {
+                old: Account::External(session.deploy_pk()),
+                new: Account::External(new_pk),
+                balance: INITIAL_BALANCE,
+                allowances: 1,
+            }
+        ),
+        events => panic!("A single key rotated event should be emitted, but got {events:?}"),
+    }
+
+    assert_eq!(session.account(session.deploy_pk()).balance, 0);
+    assert_eq!(session.account(new_pk).balance, INITIAL_BALANCE);
+    assert_eq!(session.allowance(session.deploy_pk(), spender_pk), 0);
+    assert_eq!(session.allowance(new_pk, spender_pk), APPROVED_AMOUNT);
+
+    let transfer = TransferFrom::new(&spender_sk, new_pk, spender_pk, APPROVED_AMOUNT, 1);
+    session
+        .token
+        .transfer_from(&transfer)
+        .expect("Spending the moved allowance should succeed");
+
+    let transfer = Transfer::new(&new_sk, session.deploy_pk(), 10, 1);
+    session
+        .token
+        .transfer(&transfer)
+        .expect("Transferring with the new key should succeed");
+
+    let transfer = Transfer::new(&session.deploy_sk, new_pk, 10, 1);
+    session
+        .token
+        .transfer(&transfer)
+        .expect_err("Replaying a nonce of the old key should fail");
+
+    let rotate = RotateKey {
+        new_key: new_pk.into(),
+    };
+    session
+        .call_token::<_, ()>("rotate_key", &Signed::new(&new_sk, rotate, 2))
+        .expect_err("Rotating to the same key should fail");
+}
+
+#[test]
+fn rotate_key_moves_account_state() {
+    const LIMIT: u64 = 10;
+    const MOVED_AMOUNT: u64 = 10;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let new_sk = SecretKey::random(&mut rng);
+    let new_pk = PublicKey::from(&new_sk);
+    let spender_pk = PublicKey::from(&SecretKey::random(&mut rng));
+    let operator = Account::External(PublicKey::from(&SecretKey::random(&mut rng)));
+    let session_sk = SecretKey::random(&mut rng);
+    let session_pk = PublicKey::from(&session_sk);
+
+    let old = Account::External(session.deploy_pk());
+    let new = Account::External(new_pk);
+
+    let set = SetSpendingLimit {
+        spender: Account::External(spender_pk),
+        limit: LIMIT,
+        window_blocks: 100,
+    };
+    session
+        .call_token::<_, ()>(
+            "set_spending_limit",
+            &Signed::new(&session.deploy_sk, set, 1),
+        )
+        .expect("Setting a spending limit should succeed");
+
+    let authorize = Signed::new(&session.deploy_sk, AuthorizeOperator { operator }, 2);
+    session
+        .call_token::<_, ()>("authorize_operator", &authorize)
+        .expect("Authorizing an operator should succeed");
+
+    let register = RegisterSessionKey {
+        key: session_pk,
+        scope: SessionKeyScope {
+            max_value: None,
+            budget: LIMIT,
+            recipients: None,
+            expiry_height: 100,
+        },
+        consent: Signed::new(&session_sk, SessionKeyConsent { owner: old }, 1),
+    };
+    session
+        .call_token::<_, ()>(
+            "register_session_key",
+            &Signed::new(&session.deploy_sk, register, 3),
+        )
+        .expect("Registering a session key should succeed");
+
+    let to_sub_account = SubAccountMove {
+        from: 0,
+        to: 1,
+        value: MOVED_AMOUNT,
+    };
+    session
+        .call_token::<_, ()>(
+            "sub_account_move",
+            &Signed::new(&session.deploy_sk, to_sub_account, 4),
+        )
+        .expect("Moving tokens to a sub-account should succeed");
+
+    let rotate = RotateKey {
+        new_key: new_pk.into(),
+    };
+    session
+        .call_token::<_, ()>("rotate_key", &Signed::new(&session.deploy_sk, rotate, 5))
+        .expect_err("Rotating a key with funded sub-accounts should fail");
+
+    let from_sub_account = SubAccountMove {
+        from: 1,
+        to: 0,
+        value: MOVED_AMOUNT,
+    };
+    session
+        .call_token::<_, ()>(
+            "sub_account_move",
+            &Signed::new(&session.deploy_sk, from_sub_account, 5),
+        )
+        .expect("Moving tokens back from the sub-account should succeed");
+
+    let receipt = session
+        .call_token::<_, ()>("rotate_key", &Signed::new(&session.deploy_sk, rotate, 6))
+        .expect("Rotating a key with emptied sub-accounts should succeed");
+
+    let events: Vec<Sequenced<SessionKeyEvent>> = decode_events(&receipt, TOKEN_ID, "session_key");
+    let events: Vec<SessionKeyEvent> = events.into_iter().map(|event| event.event).collect();
+    assert_eq!(
+        events,
+        vec![SessionKeyEvent {
+            owner: old,
+            key: session_pk,
+            scope: None,
+        }],
+        "The session keys of the old account should be revoked"
+    );
+
+    let info: Option<SessionKey> = session
+        .call_token("session_key", &session_pk)
+        .expect("Querying a session key should succeed")
+        .data;
+    assert_eq!(info, None, "The session key should be revoked");
+
+    for (owner, expected) in [(old, false), (new, true)] {
+        let is_operator: bool = session
+            .call_token("is_operator", &IsOperator { owner, operator })
+            .expect("Querying an operator should succeed")
+            .data;
+        assert_eq!(
+            is_operator, expected,
+            "The operator should move to the new account"
+        );
+    }
+
+    for (owner, expected) in [(old, false), (new, true)] {
+        let info = session
+            .call_token::<_, AllowanceInfo>(
+                "allowance_info",
+                &Allowance {
+                    owner,
+                    spender: Account::External(spender_pk),
+                },
+            )
+            .expect("Querying the allowance info should succeed")
+            .data;
+        assert_eq!(
+            info.spending_limit.is_some(),
+            expected,
+            "The spending limit should move to the new account"
+        );
+    }
+}
+
+#[test]
+fn rotate_key_ignores_rate_limits() {
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let new_pk = PublicKey::from(&SecretKey::random(&mut rng));
+
+    let limits = SetRateLimits {
+        limits: RateLimits {
+            account_limit: Some(1),
+            window_blocks: 100,
+            block_limit: Some(1),
+        },
+    };
+    session
+        .call_token::<_, ()>(
+            "set_rate_limits",
+            &Signed::new(&session.deploy_sk, limits, 1),
+        )
+        .expect("Setting the rate limits should succeed");
+    session
+        .call_token::<_, ()>(
+            "trip_breaker",
+            &Signed::new(&session.deploy_sk, TripBreaker { threshold: 1 }, 2),
+        )
+        .expect("Tripping the breaker should succeed");
+
+    let rotate = RotateKey {
+        new_key: new_pk.into(),
+    };
+    session
+        .call_token::<_, ()>("rotate_key", &Signed::new(&session.deploy_sk, rotate, 3))
+        .expect("Rotating a key should succeed regardless of the rate limits");
+
+    assert_eq!(
+        session.account(new_pk).balance,
+        INITIAL_BALANCE,
+        "The whole balance should move to the new key"
+    );
+}
+
+#[test]
+fn decode_token_events() {
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let pk = PublicKey::from(&SecretKey::random(&mut rng));
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, 10, 1);
+    let receipt = session
+        .token
+        .transfer(&transfer)
+        .expect("Transferring should succeed");
+
+    let event = receipt
+        .events
+        .iter()
+        .find(|event| event.source == TOKEN_ID && event.topic == TRANSFER_TOPIC)
+        .expect("A transfer event should be emitted");
+
+    match decode_event(&event.topic, &event.data) {
+        Some(TokenEvent::Transfer(transfer)) => {
+            assert_eq!(transfer.event.owner, Account::External(session.deploy_pk()));
+            assert_eq!(transfer.event.to, Account::External(pk));
+            assert_eq!(transfer.event.value, 10);
+        }
+        event => panic!("The event should decode as a transfer, but got {event:?}"),
+    }
+
+    assert_eq!(
+        decode_event(BURN_TOPIC, &[]),
+        None,
+        "Invalid data should fail to decode"
+    );
+    assert_eq!(
+        decode_event("unknown", &event.data),
+        Some(TokenEvent::Other {
+            topic: String::from("unknown"),
+            data: event.data.clone(),
+        }),
+        "Events with other topics should be left undecoded"
+    );
+}
+
+#[test]
+fn read_archived() {
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let pk = PublicKey::from(&SecretKey::random(&mut rng));
+
+    let transfer = Transfer::new(&session.deploy_sk, pk, 10, 1);
+    let receipt = session
+        .token
+        .transfer(&transfer)
+        .expect("Transferring should succeed");
+
+    let event = receipt
+        .events
+        .iter()
+        .find(|event| event.source == TOKEN_ID && event.topic == TRANSFER_TOPIC)
+        .expect("A transfer event should be emitted");
+
+    // the archived event must be aligned, which the emitted bytes may not be
+    let mut data = AlignedVec::with_capacity(event.data.len());
+    data.extend_from_slice(&event.data);
+
+    let archived = read_event::<TransferEvent>(&data).expect("The event should be valid");
+    assert_eq!(
+        archived.event().owner(),
+        Account::External(session.deploy_pk())
+    );
+    assert_eq!(archived.event().spender(), None);
+    assert_eq!(archived.event().to(), Account::External(pk));
+    assert_eq!(archived.event().value(), 10);
+    assert_eq!(archived.event().burned(), 0);
+    assert_eq!(archived.event().memo(), None);
+
+    assert!(
+        read_event::<TransferEvent>(&data[..data.len() - 1]).is_none(),
+        "Truncated data should fail to validate"
+    );
+
+    let payload = rkyv::to_bytes::<_, 1024>(&transfer).expect("Serializing should succeed");
+    let archived = read_payload::<Transfer>(&payload).expect("The payload should be valid");
+    assert_eq!(archived.from(), *transfer.from());
+    assert_eq!(archived.to(), *transfer.to());
+    assert_eq!(archived.value(), transfer.value());
+    assert_eq!(archived.nonce(), transfer.nonce());
+    assert_eq!(archived.version(), transfer.version());
+}
+
+#[test]
+fn idempotency_id() {
+    const TRANSFERRED_AMOUNT: u64 = 100;
+    const IDEMPOTENCY_ID: u64 = 7;
+    const HEIGHT: u64 = 42;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let id = SubmissionId {
+        account: session.deploy_pk().into(),
+        id: IDEMPOTENCY_ID,
+    };
+
+    let status: SubmissionStatus = session
+        .call_token("submission_status", &id)
+        .expect("Querying the submission status should succeed")
+        .data;
+    assert_eq!(status, SubmissionStatus::Unknown);
+
+    let transfer = Transfer::builder()
+        .to(pk)
+        .value(TRANSFERRED_AMOUNT)
+        .nonce(1)
+        .idempotency_id(IDEMPOTENCY_ID)
+        .sign(&session.deploy_sk);
+    assert_eq!(transfer.idempotency_id(), Some(IDEMPOTENCY_ID));
+    session.set_block_height(HEIGHT);
+    session
+        .token
+        .transfer(&transfer)
+        .expect("Transferring with an idempotency id should succeed");
+
+    let status: SubmissionStatus = session
+        .call_token("submission_status", &id)
+        .expect("Querying the submission status should succeed")
+        .data;
+    assert_eq!(
+        status,
+        SubmissionStatus::Executed(Submission {
+            id: IDEMPOTENCY_ID,
+            nonce: 1,
+            block_height: HEIGHT,
+            value: TRANSFERRED_AMOUNT,
+            received: TRANSFERRED_AMOUNT,
+        })
+    );
+
+    // resubmitting under a new nonce must not move the tokens twice
+    let transfer = Transfer::builder()
+        .to(pk)
+        .value(TRANSFERRED_AMOUNT)
+        .nonce(2)
+        .idempotency_id(IDEMPOTENCY_ID)
+        .sign(&session.deploy_sk);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect_err("Reusing an idempotency id should fail");
+
+    assert_eq!(
+        session.account(pk).balance,
+        TRANSFERRED_AMOUNT,
+        "The tokens should be moved once"
+    );
+
+    // ids are kept per account
+    let transfer = Transfer::builder()
+        .to(session.deploy_pk())
+        .value(TRANSFERRED_AMOUNT)
+        .nonce(1)
+        .idempotency_id(IDEMPOTENCY_ID)
+        .sign(&sk);
+    session
+        .token
+        .transfer(&transfer)
+        .expect("Another account should be able to use the same id");
+}
+
+#[test]
+fn treasury() {
+    const FUNDED_AMOUNT: u64 = 900;
+    const CAP: u64 = 300;
+    const EPOCH_BLOCKS: u64 = 100;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let payout_sk = SecretKey::random(&mut rng);
+    let payout_pk = PublicKey::from(&payout_sk);
+    let payee_pk = PublicKey::from(&SecretKey::random(&mut rng));
+    let other_pk = PublicKey::from(&SecretKey::random(&mut rng));
+
+    let set = SetTreasury {
+        payout_key: Some(payout_pk),
+        epoch_blocks: EPOCH_BLOCKS,
+    };
+    session
+        .call_token::<_, ()>("set_treasury", &Signed::new(&session.deploy_sk, set, 1))
+        .expect("Setting the treasury should succeed");
+
+    for (nonce, payee) in [(2, payee_pk), (3, other_pk)] {
+        let set = SetPayee {
+            payee: payee.into(),
+            cap: CAP,
+        };
+        session
+            .call_token::<_, ()>("set_payee", &Signed::new(&session.deploy_sk, set, nonce))
+            .expect("Registering a payee should succeed");
+    }
+
+    let pause = Pause {
+        scope: PauseScope::TRANSFERS,
+    };
+    session
+        .call_token::<_, ()>("pause", &Signed::new(&session.deploy_sk, pause, 4))
+        .expect("Pausing transfers should succeed");
+
+    let fund = FundTreasury {
+        value: FUNDED_AMOUNT,
+    };
+    session
+        .call_token::<_, ()>("fund_treasury", &Signed::new(&session.deploy_sk, fund, 5))
+        .expect_err("Funding the treasury while transfers are paused should fail");
+
+    let unpause = Unpause {
+        scope: PauseScope::TRANSFERS,
+    };
+    session
+        .call_token::<_, ()>("unpause", &Signed::new(&session.deploy_sk, unpause, 5))
+        .expect("Resuming transfers should succeed");
+
+    session
+        .call_token::<_, ()>("fund_treasury", &Signed::new(&session.deploy_sk, fund, 6))
+        .expect("Funding the treasury should succeed");
+
+    let payout = Payout {
+        payments: vec![(payee_pk.into(), 200), (other_pk.into(), CAP)],
+    };
+    session
+        .call_token::<_, ()>(
+            "payout",
+            &Signed::new(&session.deploy_sk, payout.clone(), 7),
+        )
+        .expect_err("Paying out with another key than the payout key should fail");
+
+    let receipt = session
+        .call_token::<_, ()>("payout", &Signed::new(&payout_sk, payout, 1))
+        .expect("Paying out within the caps should succeed");
+
+    let events: Vec<Sequenced<PayoutEvent>> = decode_events(&receipt, TOKEN_ID, "payout");
+    assert_eq!(
+        events.into_iter().map(|e| e.event).collect::<Vec<_>>(),
+        vec![
+            PayoutEvent {
+                payee: payee_pk.into(),
+                value: 200,
+                epoch: 0,
+            },
+            PayoutEvent {
+                payee: other_pk.into(),
+                value: CAP,
+                epoch: 0,
+            },
+        ],
+        "An event should be emitted for each payment"
+    );
+
+    assert_eq!(session.account(payee_pk).balance, 200);
+    assert_eq!(session.account(other_pk).balance, CAP);
+
+    let payout = Payout {
+        payments: vec![(payee_pk.into(), 50), (payee_pk.into(), 51)],
+    };
+    session
+        .call_token::<_, ()>("payout", &Signed::new(&payout_sk, payout, 2))
+        .expect_err("Paying more than the cap in an epoch should fail");
+
+    let payout = Payout {
+        payments: vec![(session.deploy_pk().into(), 1)],
+    };
+    session
+        .call_token::<_, ()>("payout", &Signed::new(&payout_sk, payout, 2))
+        .expect_err("Paying an account that isn't a payee should fail");
+
+    // the caps apply anew in every epoch
+    session.set_block_height(EPOCH_BLOCKS);
+
+    let payee: Option<Payee> = session
+        .call_token("payee", &Account::from(payee_pk))
+        .expect("Querying the payee should succeed")
+        .data;
+    assert_eq!(
+        payee,
+        Some(Payee {
+            cap: CAP,
+            epoch: 1,
+            paid: 0,
+        })
+    );
+
+    let payout = Payout {
+        payments: vec![(payee_pk.into(), CAP)],
+    };
+    session
+        .call_token::<_, ()>("payout", &Signed::new(&payout_sk, payout, 2))
+        .expect("Paying out in a new epoch should succeed");
+
+    let treasury: TreasuryInfo = session
+        .call_token("treasury", &())
+        .expect("Querying the treasury should succeed")
+        .data;
+    assert_eq!(
+        treasury,
+        TreasuryInfo {
+            balance: FUNDED_AMOUNT - 200 - 2 * CAP,
+            payout_key: Some(payout_pk),
+            epoch_blocks: EPOCH_BLOCKS,
+        }
+    );
+}
+
+#[test]
+fn invoice() {
+    const INVOICED_AMOUNT: u64 = 100;
+    const EXPIRY: u64 = 10;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let pk = PublicKey::from(&SecretKey::random(&mut rng));
+    let other_sk = SecretKey::random(&mut rng);
+    let other_pk = PublicKey::from(&other_sk);
+
+    let invoice = Invoice {
+        id: 1,
+        payee: pk.into(),
+        value: INVOICED_AMOUNT,
+        expiry: EXPIRY,
+    };
+    let id = InvoiceId {
+        payee: pk.into(),
+        payer: session.deploy_pk().into(),
+        id: invoice.id,
+    };
+
+    // another account settling an invoice with the same id doesn't block the payee's invoice
+    let transfer = Transfer::new(&session.deploy_sk, other_pk, 1, 1);
+    session
+        .call_token::<_, ()>("transfer", &transfer)
+        .expect("Transferring should succeed");
+    let squatted = Invoice {
+        value: 1,
+        ..invoice
+    };
+    session
+        .call_token::<_, ()>("settle_invoice", &Signed::new(&other_sk, squatted, 1))
+        .expect("Settling an invoice of another payer should succeed");
+
+    let settled: bool = session
+        .call_token("invoice_settled", &id)
+        .expect("Querying whether the invoice is settled should succeed")
+        .data;
+    assert!(!settled, "The invoice shouldn't be settled yet");
+
+    let receipt = session
+        .call_token::<_, ()>(
+            "settle_invoice",
+            &Signed::new(&session.deploy_sk, invoice, 2),
+        )
+        .expect("Settling the invoice should succeed");
+
+    assert_transfer(&receipt, TOKEN_ID, session.deploy_pk(), pk, INVOICED_AMOUNT);
+    let events: Vec<Sequenced<InvoiceSettledEvent>> =
+        decode_events(&receipt, TOKEN_ID, "invoice_settled");
+    assert_eq!(
+        events.into_iter().map(|e| e.event).collect::<Vec<_>>(),
+        vec![InvoiceSettledEvent {
+            payee: pk.into(),
+            id: invoice.id,
+            payer: session.deploy_pk().into(),
+            value: INVOICED_AMOUNT,
+        }]
+    );
+
+    let settled: bool = session
+        .call_token("invoice_settled", &id)
+        .expect("Querying whether the invoice is settled should succeed")
+        .data;
+    assert!(settled, "The invoice should be settled");
+
+    // accepting the same invoice again, under a new nonce, doesn't pay it twice
+    session
+        .call_token::<_, ()>(
+            "settle_invoice",
+            &Signed::new(&session.deploy_sk, invoice, 3),
+        )
+        .expect_err("Settling an invoice twice should fail");
+    assert_eq!(session.account(pk).balance, INVOICED_AMOUNT + 1);
+
+    let invoice = Invoice { id: 2, ..invoice };
+    session.set_block_height(EXPIRY + 1);
+    session
+        .call_token::<_, ()>(
+            "settle_invoice",
+            &Signed::new(&session.deploy_sk, invoice, 3),
+        )
+        .expect_err("Settling an expired invoice should fail");
+}
+
+#[test]
+fn deployment_spec() {
+    let deploy_pk = PublicKey::from(&SecretKey::random(&mut StdRng::seed_from_u64(0xF0CACC1A)));
+
+    let spec = DeploymentSpec {
+        owner: vec![1; 64],
+        nonce: 0,
+        init_args: InitArgs {
+            balances: vec![(deploy_pk.into(), INITIAL_BALANCE)],
+            ..InitArgs::default()
+        },
+    };
+
+    let contract = spec.contract_id(TOKEN_BYTECODE);
+    assert_eq!(
+        contract,
+        rusk_abi::gen_contract_id(TOKEN_BYTECODE, spec.nonce, &spec.owner),
+        "The id should be derived the same way as the VM does"
+    );
+
+    let other = DeploymentSpec {
+        nonce: 1,
+        ..spec.clone()
+    };
+    assert_ne!(
+        other.contract_id(TOKEN_BYTECODE),
+        contract,
+        "Another nonce should give another id"
+    );
+
+    let mut token = TokenSession::new(TOKEN_BYTECODE, contract, &spec.init_args);
+    assert_eq!(token.token(), contract);
+    assert_eq!(token.balance_of(deploy_pk), INITIAL_BALANCE);
+}
+
+#[test]
+fn sub_accounts() {
+    const TRANSFERRED_AMOUNT: u64 = 100;
+    const TRADING: u32 = 1;
+    const COLD: u32 = 2;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let pk = PublicKey::from(&SecretKey::random(&mut rng));
+
+    let deploy_pk = session.deploy_pk();
+    let trading = Account::sub_account(deploy_pk, TRADING);
+    let cold = Account::sub_account(deploy_pk, COLD);
+    assert_eq!(
+        Account::sub_account(deploy_pk, 0),
+        Account::External(deploy_pk),
+        "Sub-account 0 should be the account of the key"
+    );
+    assert_eq!(trading.root(), Account::External(deploy_pk));
+
+    // sub-accounts receive tokens like any other account
+    let transfer = Transfer::new(&session.deploy_sk, trading, 2 * TRANSFERRED_AMOUNT, 1);
+    session
+        .token
+        .transfer(&transfer)
+        .expect("Transferring to a sub-account should succeed");
+
+    let sub_move = SubAccountMove {
+        from: TRADING,
+        to: COLD,
+        value: TRANSFERRED_AMOUNT,
+    };
+    let receipt = session
+        .call_token::<_, ()>(
+            "sub_account_move",
+            &Signed::new(&session.deploy_sk, sub_move, 2),
+        )
+        .expect("Moving between sub-accounts should succeed");
+
+    assert_transfer(&receipt, TOKEN_ID, trading, cold, TRANSFERRED_AMOUNT);
+    assert_eq!(session.account(trading).balance, TRANSFERRED_AMOUNT);
+    assert_eq!(session.account(cold).balance, TRANSFERRED_AMOUNT);
+
+    let transfer = SubAccountTransfer {
+        from: COLD,
+        to: pk.into(),
+        value: TRANSFERRED_AMOUNT,
+    };
+    let receipt = session
+        .call_token::<_, ()>(
+            "sub_account_transfer",
+            &Signed::new(&session.deploy_sk, transfer, 3),
+        )
+        .expect("Transferring from a sub-account should succeed");
+
+    assert_transfer(&receipt, TOKEN_ID, cold, pk, TRANSFERRED_AMOUNT);
+    assert_eq!(session.account(cold).balance, 0);
+    assert_eq!(session.account(pk).balance, TRANSFERRED_AMOUNT);
+    assert_eq!(
+        session.account(deploy_pk).balance,
+        INITIAL_BALANCE - 2 * TRANSFERRED_AMOUNT,
+        "The account of the key should be separate from its sub-accounts"
+    );
+
+    let sub_move = SubAccountMove {
+        from: COLD,
+        to: 0,
+        value: 1,
+    };
+    session
+        .call_token::<_, ()>(
+            "sub_account_move",
+            &Signed::new(&session.deploy_sk, sub_move, 4),
+        )
+        .expect_err("Moving more than the sub-account holds should fail");
+
+    // the index is covered by the signature, so it can't be swapped for another
+    let signed = Signed::new(
+        &session.deploy_sk,
+        SubAccountMove {
+            from: TRADING,
+            to: 0,
+            value: 1,
+        },
+        4,
+    );
+    let mut forged = Signed::new(
+        &session.deploy_sk,
+        SubAccountMove {
+            from: TRADING,
+            to: COLD,
+            value: 1,
+        },
+        signed.nonce(),
+    );
+    forged.set_signature(*signed.signature());
+    session
+        .call_token::<_, ()>("sub_account_move", &forged)
+        .expect_err("Changing the index should invalidate the signature");
+}
+
+#[test]
+fn stream() {
+    const RATE: u64 = 10;
+    const DEPOSIT: u64 = 100;
+
+    let mut session = ContractSession::new();
+
+    let mut rng = StdRng::seed_from_u64(0xBEEF);
+    let sk = SecretKey::random(&mut rng);
+    let pk = PublicKey::from(&sk);
+
+    let open = OpenStream {
+        to: pk.into(),
+        rate_per_block: RATE,
+        deposit: DEPOSIT,
+    };
+    let id: u64 = session
+        .call_token("open_stream", &Signed::new(&session.deploy_sk, open, 1))
+        .expect("Opening a stream should succeed")
+        .data;
+
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - DEPOSIT,
+        "The deposit should be taken from the sender"
+    );
+
+    session.set_block_height(3);
+
+    let withdraw = WithdrawFromStream { id };
+    let value: u64 = session
+        .call_token("withdraw_from_stream", &Signed::new(&sk, withdraw, 1))
+        .expect("Withdrawing from the stream should succeed")
+        .data;
+    assert_eq!(
+        value,
+        3 * RATE,
+        "The tokens accrued so far should be withdrawn"
+    );
+    assert_eq!(session.account(pk).balance, 3 * RATE);
+
+    session
+        .call_token::<_, u64>(
+            "withdraw_from_stream",
+            &Signed::new(&session.deploy_sk, withdraw, 2),
+        )
+        .expect_err("Only the recipient should be able to withdraw");
+
+    session.set_block_height(5);
+
+    let close = CloseStream { id };
+    let receipt = session
+        .call_token::<_, ()>("close_stream", &Signed::new(&session.deploy_sk, close, 2))
+        .expect("Closing the stream should succeed");
+
+    let events: Vec<Sequenced<StreamClosedEvent>> =
+        decode_events(&receipt, TOKEN_ID, "stream_closed");
+    assert_eq!(
+        events.into_iter().map(|e| e.event).collect::<Vec<_>>(),
+        vec![StreamClosedEvent {
+            id,
+            paid: 2 * RATE,
+            refunded: DEPOSIT - 5 * RATE,
+        }]
+    );
+
+    assert_eq!(session.account(pk).balance, 5 * RATE);
+    assert_eq!(
+        session.account(session.deploy_pk()).balance,
+        INITIAL_BALANCE - 5 * RATE,
+        "The rest of the deposit should be returned to the sender"
+    );
+
+    let stream: Option<Stream> = session
+        .call_token("stream", &id)
+        .expect("Querying the stream should succeed")
+        .data;
+    assert_eq!(stream, None, "The stream should be removed once closed");
+
+    let set = SetMinTransfer { min_transfer: RATE };
+    session
+        .call_token::<_, ()>("set_min_transfer", &Signed::new(&session.deploy_sk, set, 3))
+        .expect("Setting the minimum transfer should succeed");
+
+    let open = OpenStream {
+        deposit: RATE - 1,
+        ..open
+    };
+    session
+        .call_token::<_, u64>("open_stream", &Signed::new(&session.deploy_sk, open, 4))
+        .expect_err("Opening a stream with a deposit below the minimum transfer should fail");
+
+    let trip = TripBreaker { threshold: RATE };
+    session
+        .call_token::<_, ()>("trip_breaker", &Signed::new(&session.deploy_sk, trip, 4))
+        .expect("Tripping the breaker should succeed");
+
+    let open = OpenStream {
+        deposit: DEPOSIT,
+        ..open
+    };
+    session
+        .call_token::<_, u64>("open_stream", &Signed::new(&session.deploy_sk, open, 5))
+        .expect_err("Opening a stream above the threshold of a tripped breaker should fail");
+}
+
 fn main() {
     unreachable!("`main` should never run for this crate");
 }
@@ -0,0 +1,305 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+use dusk_bytes::Serializable;
+
+use execution_core::signatures::bls::PublicKey;
+use execution_core::ContractId;
+
+use crate::{Account, Ed25519PublicKey, Secp256k1PublicKey};
+
+/// The prefix of the string form of an externally owned account.
+pub const EXTERNAL_PREFIX: &str = "duskpk";
+/// The prefix of the string form of a contract account.
+pub const CONTRACT_PREFIX: &str = "duskcontract";
+/// The prefix of the string form of a multisig account.
+pub const MULTISIG_PREFIX: &str = "duskmultisig";
+/// The prefix of the string form of an externally owned account with a secp256k1 key.
+pub const SECP256K1_PREFIX: &str = "dusksecp";
+/// The prefix of the string form of an externally owned account with an Ed25519 key.
+pub const ED25519_PREFIX: &str = "duskedpk";
+/// The prefix of the string form of a sub-account.
+pub const SUB_ACCOUNT_PREFIX: &str = "dusksub";
+
+// The string form of an account follows bech32m (BIP-350), with the prefix as the human-readable
+// part. Unlike bech32m, the length of the string is not limited to 90 characters, since public keys
+// don't fit.
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+const BECH32M_CONST: u32 = 0x2bc830a3;
+const CHECKSUM_LEN: usize = 6;
+
+/// Error returned when parsing an [`Account`] from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseAccountError {
+    /// The string mixes upper and lower case characters.
+    MixedCase,
+    /// The string has no separator between the prefix and the data.
+    MissingSeparator,
+    /// The prefix is not one of the known account prefixes.
+    UnknownPrefix,
+    /// The data contains a character outside of the encoding's alphabet.
+    InvalidCharacter(char),
+    /// The checksum doesn't match the rest of the string.
+    InvalidChecksum,
+    /// The data is too short or too long for the kind of account.
+    InvalidLength,
+    /// The data has non-zero padding bits.
+    InvalidPadding,
+    /// The data of an externally owned account is not a valid public key.
+    InvalidPublicKey,
+    /// The index of a sub-account is zero, which stands for the account of the key itself.
+    ZeroSubAccount,
+}
+
+impl fmt::Display for ParseAccountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MixedCase => f.write_str("mixed case account string"),
+            Self::MissingSeparator => f.write_str("missing separator in account string"),
+            Self::UnknownPrefix => f.write_str("unknown account prefix"),
+            Self::InvalidCharacter(c) => write!(f, "invalid character {c:?} in account string"),
+            Self::InvalidChecksum => f.write_str("invalid account checksum"),
+            Self::InvalidLength => f.write_str("invalid account length"),
+            Self::InvalidPadding => f.write_str("invalid padding in account string"),
+            Self::InvalidPublicKey => f.write_str("invalid public key in account string"),
+            Self::ZeroSubAccount => f.write_str("zero sub-account index in account string"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseAccountError {}
+
+// The checksum of a string, computed as its characters are fed.
+struct Polymod(u32);
+
+impl Polymod {
+    fn new(prefix: &str) -> Self {
+        let mut polymod = Self(1);
+        for c in prefix.bytes() {
+            polymod.feed(c >> 5);
+        }
+        polymod.feed(0);
+        for c in prefix.bytes() {
+            polymod.feed(c & 31);
+        }
+        polymod
+    }
+
+    fn feed(&mut self, value: u8) {
+        let top = self.0 >> 25;
+        self.0 = ((self.0 & 0x1ffffff) << 5) ^ value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                self.0 ^= generator;
+            }
+        }
+    }
+
+    fn checksum(mut self) -> [u8; CHECKSUM_LEN] {
+        for _ in 0..CHECKSUM_LEN {
+            self.feed(0);
+        }
+        let value = self.0 ^ BECH32M_CONST;
+
+        let mut checksum = [0u8; CHECKSUM_LEN];
+        for (i, c) in checksum.iter_mut().enumerate() {
+            *c = ((value >> (5 * (CHECKSUM_LEN - 1 - i))) & 31) as u8;
+        }
+        checksum
+    }
+
+    fn is_valid(&self) -> bool {
+        self.0 == BECH32M_CONST
+    }
+}
+
+// Splits bytes into groups of 5 bits, padding the last group with zeros.
+fn to_groups(bytes: &[u8]) -> Vec<u8> {
+    let mut groups = Vec::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut acc = 0u32;
+    let mut bits = 0;
+
+    for &byte in bytes {
+        acc = (acc << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            groups.push(((acc >> bits) & 31) as u8);
+        }
+    }
+    if bits > 0 {
+        groups.push(((acc << (5 - bits)) & 31) as u8);
+    }
+
+    groups
+}
+
+// Joins groups of 5 bits into bytes, rejecting padding that isn't made of zeros.
+fn from_groups(groups: &[u8]) -> Result<Vec<u8>, ParseAccountError> {
+    let mut bytes = Vec::with_capacity(groups.len() * 5 / 8);
+    let mut acc = 0u32;
+    let mut bits = 0;
+
+    for &group in groups {
+        acc = (acc << 5) | group as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push(((acc >> bits) & 0xff) as u8);
+        }
+    }
+    if bits >= 5 || (acc & ((1 << bits) - 1)) != 0 {
+        return Err(ParseAccountError::InvalidPadding);
+    }
+
+    Ok(bytes)
+}
+
+impl Account {
+    fn prefix(&self) -> &'static str {
+        match self {
+            Account::External(_) => EXTERNAL_PREFIX,
+            Account::Contract(_) => CONTRACT_PREFIX,
+            Account::Multisig(_) => MULTISIG_PREFIX,
+            Account::Secp256k1(_) => SECP256K1_PREFIX,
+            Account::Ed25519(_) => ED25519_PREFIX,
+            Account::SubAccount(..) => SUB_ACCOUNT_PREFIX,
+        }
+    }
+
+    fn payload(&self) -> Vec<u8> {
+        match self {
+            Account::External(pk) => pk.to_bytes().to_vec(),
+            Account::Contract(contract) => contract.to_bytes().to_vec(),
+            Account::Multisig(id) => id.to_le_bytes().to_vec(),
+            Account::Secp256k1(pk) => pk.0.to_vec(),
+            Account::Ed25519(pk) => pk.0.to_vec(),
+            Account::SubAccount(pk, index) => {
+                let mut payload = pk.to_bytes().to_vec();
+                payload.extend_from_slice(&index.to_le_bytes());
+                payload
+            }
+        }
+    }
+}
+
+/// Formats the account as a checksummed string, prefixed by the kind of account, e.g.
+/// `duskcontract1...`.
+impl fmt::Display for Account {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = self.prefix();
+        let mut polymod = Polymod::new(prefix);
+
+        f.write_str(prefix)?;
+        f.write_char('1')?;
+
+        for group in to_groups(&self.payload()) {
+            polymod.feed(group);
+            f.write_char(CHARSET[group as usize] as char)?;
+        }
+        for group in polymod.checksum() {
+            f.write_char(CHARSET[group as usize] as char)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Account {
+    type Err = ParseAccountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let has_lower = s.bytes().any(|c| c.is_ascii_lowercase());
+        let has_upper = s.bytes().any(|c| c.is_ascii_uppercase());
+        if has_lower && has_upper {
+            return Err(ParseAccountError::MixedCase);
+        }
+        let s = s.to_ascii_lowercase();
+
+        let (prefix, data) = s
+            .rsplit_once('1')
+            .ok_or(ParseAccountError::MissingSeparator)?;
+        if data.len() < CHECKSUM_LEN {
+            return Err(ParseAccountError::InvalidLength);
+        }
+
+        let mut polymod = Polymod::new(prefix);
+        let mut groups = Vec::with_capacity(data.len());
+
+        for c in data.chars() {
+            let group = CHARSET
+                .iter()
+                .position(|&x| x as char == c)
+                .ok_or(ParseAccountError::InvalidCharacter(c))?;
+            polymod.feed(group as u8);
+            groups.push(group as u8);
+        }
+
+        if !polymod.is_valid() {
+            return Err(ParseAccountError::InvalidChecksum);
+        }
+        groups.truncate(groups.len() - CHECKSUM_LEN);
+
+        let bytes = from_groups(&groups)?;
+
+        match prefix {
+            EXTERNAL_PREFIX => {
+                let bytes = <[u8; PublicKey::SIZE]>::try_from(bytes.as_slice())
+                    .map_err(|_| ParseAccountError::InvalidLength)?;
+                PublicKey::from_bytes(&bytes)
+                    .map(Account::External)
+                    .map_err(|_| ParseAccountError::InvalidPublicKey)
+            }
+            CONTRACT_PREFIX => {
+                let bytes = <[u8; 32]>::try_from(bytes.as_slice())
+                    .map_err(|_| ParseAccountError::InvalidLength)?;
+                Ok(Account::Contract(ContractId::from_bytes(bytes)))
+            }
+            MULTISIG_PREFIX => {
+                let bytes = <[u8; 8]>::try_from(bytes.as_slice())
+                    .map_err(|_| ParseAccountError::InvalidLength)?;
+                Ok(Account::Multisig(u64::from_le_bytes(bytes)))
+            }
+            SECP256K1_PREFIX => {
+                let bytes = <[u8; Secp256k1PublicKey::SIZE]>::try_from(bytes.as_slice())
+                    .map_err(|_| ParseAccountError::InvalidLength)?;
+                let pk = Secp256k1PublicKey(bytes);
+                if !pk.is_valid() {
+                    return Err(ParseAccountError::InvalidPublicKey);
+                }
+                Ok(Account::Secp256k1(pk))
+            }
+            ED25519_PREFIX => {
+                let bytes = <[u8; Ed25519PublicKey::SIZE]>::try_from(bytes.as_slice())
+                    .map_err(|_| ParseAccountError::InvalidLength)?;
+                let pk = Ed25519PublicKey(bytes);
+                if !pk.is_valid() {
+                    return Err(ParseAccountError::InvalidPublicKey);
+                }
+                Ok(Account::Ed25519(pk))
+            }
+            SUB_ACCOUNT_PREFIX => {
+                let bytes = <[u8; PublicKey::SIZE + 4]>::try_from(bytes.as_slice())
+                    .map_err(|_| ParseAccountError::InvalidLength)?;
+                let (pk_bytes, index_bytes) = bytes.split_at(PublicKey::SIZE);
+
+                let mut pk = [0u8; PublicKey::SIZE];
+                pk.copy_from_slice(pk_bytes);
+                let pk =
+                    PublicKey::from_bytes(&pk).map_err(|_| ParseAccountError::InvalidPublicKey)?;
+
+                let mut index = [0u8; 4];
+                index.copy_from_slice(index_bytes);
+                match u32::from_le_bytes(index) {
+                    0 => Err(ParseAccountError::ZeroSubAccount),
+                    index => Ok(Account::SubAccount(pk, index)),
+                }
+            }
+            _ => Err(ParseAccountError::UnknownPrefix),
+        }
+    }
+}
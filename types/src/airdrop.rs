@@ -0,0 +1,242 @@
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Escrow tokens from the admin's balance for an airdrop, claimable by the accounts included in a
+/// Merkle tree. Only the admin may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateAirdrop {
+    /// The root of the Merkle tree of the accounts and amounts to airdrop, as built by
+    /// [`AirdropTree`].
+    pub merkle_root: [u8; 32],
+    /// The total amount of tokens escrowed for the airdrop.
+    pub total: u64,
+    /// The block height from which the airdrop can no longer be claimed, and the admin may sweep
+    /// the unclaimed tokens.
+    pub expiry_height: u64,
+}
+
+impl Operation for CreateAirdrop {
+    const NAME: &'static str = "create_airdrop";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.merkle_root);
+        msg.field(&self.total.to_le_bytes());
+        msg.field(&self.expiry_height.to_le_bytes());
+    }
+}
+
+/// Sweep the unclaimed tokens of an expired airdrop back into the admin's balance. Only the admin
+/// may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SweepAirdrop {
+    /// The id of the airdrop.
+    pub id: u64,
+}
+
+impl Operation for SweepAirdrop {
+    const NAME: &'static str = "sweep_airdrop";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.id.to_le_bytes());
+    }
+}
+
+/// Claim the tokens airdropped to an account. Anyone may submit a claim, since the tokens are
+/// always credited to the account included in the Merkle tree.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClaimAirdrop {
+    /// The id of the airdrop.
+    pub id: u64,
+    /// The account to credit.
+    pub account: Account,
+    /// The amount airdropped to the account.
+    pub amount: u64,
+    /// The proof of inclusion of the account and amount in the Merkle tree.
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Used to query whether an account has claimed its tokens from an airdrop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AirdropClaimed {
+    /// The id of the airdrop.
+    pub id: u64,
+    /// The account to check.
+    pub account: Account,
+}
+
+/// The state of an airdrop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Airdrop {
+    /// The root of the Merkle tree of the accounts and amounts airdropped.
+    pub merkle_root: [u8; 32],
+    /// The total amount of tokens escrowed.
+    pub total: u64,
+    /// The amount of tokens claimed so far.
+    pub claimed: u64,
+    /// The block height from which the airdrop can no longer be claimed.
+    pub expiry_height: u64,
+}
+
+/// A Merkle tree of the accounts and amounts of an airdrop, used to compute its root and the proofs
+/// of inclusion of each account.
+///
+/// Pairs of nodes are hashed in sorted order, so proofs don't need to include the position of each
+/// node. A node without a sibling is carried up to the next level unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirdropTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl AirdropTree {
+    /// Build the tree of the given accounts and amounts.
+    ///
+    /// # Panics
+    /// When no entries are given.
+    pub fn new(entries: &[(Account, u64)]) -> Self {
+        assert!(
+            !entries.is_empty(),
+            "An airdrop must have at least one entry"
+        );
+
+        let mut levels = Vec::new();
+        let mut level: Vec<[u8; 32]> = entries
+            .iter()
+            .map(|(account, amount)| airdrop_leaf(account, *amount))
+            .collect();
+
+        while level.len() > 1 {
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    [node] => *node,
+                    _ => unreachable!(),
+                })
+                .collect();
+
+            levels.push(level);
+            level = next;
+        }
+        levels.push(level);
+
+        Self { levels }
+    }
+
+    /// The root of the tree.
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.levels.len() - 1][0]
+    }
+
+    /// The proof of inclusion of the entry at the given index.
+    pub fn proof(&self, mut index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        proof
+    }
+}
+
+/// Verify that an account and amount are included in the Merkle tree with the given root.
+pub fn verify_airdrop_proof(
+    merkle_root: &[u8; 32],
+    account: &Account,
+    amount: u64,
+    proof: &[[u8; 32]],
+) -> bool {
+    let node = proof
+        .iter()
+        .fold(airdrop_leaf(account, amount), |node, sibling| {
+            hash_pair(&node, sibling)
+        });
+
+    &node == merkle_root
+}
+
+fn airdrop_leaf(account: &Account, amount: u64) -> [u8; 32] {
+    let mut bytes = Vec::with_capacity(1 + Account::SIZE + 8);
+
+    bytes.push(0);
+    bytes.extend_from_slice(&account.to_bytes());
+    bytes.extend_from_slice(&amount.to_le_bytes());
+
+    hash(&bytes)
+}
+
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (a, b) = if a <= b { (a, b) } else { (b, a) };
+
+    let mut bytes = [0u8; 65];
+
+    bytes[0] = 1;
+    bytes[1..33].copy_from_slice(a);
+    bytes[33..].copy_from_slice(b);
+
+    hash(&bytes)
+}
+
+fn hash(bytes: &[u8]) -> [u8; 32] {
+    let hash = blake2b_simd::Params::new().hash_length(32).hash(bytes);
+
+    let mut out = [0u8; 32];
+    out.copy_from_slice(hash.as_bytes());
+    out
+}
+
+/// Event emitted when an airdrop is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AirdropCreatedEvent {
+    /// The id of the airdrop.
+    pub id: u64,
+    /// The root of the Merkle tree of the accounts and amounts airdropped.
+    pub merkle_root: [u8; 32],
+    /// The total amount of tokens escrowed.
+    pub total: u64,
+    /// The block height from which the airdrop can no longer be claimed.
+    pub expiry_height: u64,
+}
+
+/// Event emitted when tokens are claimed from an airdrop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AirdropClaimedEvent {
+    /// The id of the airdrop.
+    pub id: u64,
+    /// The account credited.
+    pub account: Account,
+    /// The amount claimed.
+    pub amount: u64,
+}
+
+/// Event emitted when the unclaimed tokens of an expired airdrop are swept by the admin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AirdropSweptEvent {
+    /// The id of the airdrop.
+    pub id: u64,
+    /// The amount swept.
+    pub value: u64,
+}
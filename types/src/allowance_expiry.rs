@@ -0,0 +1,45 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Set the block height from which a spender can no longer use its allowance of the signer's
+/// account, or its spending limit. An expiry of `None` removes it, as does revoking the allowance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetAllowanceExpiry {
+    /// The account allowed to spend tokens.
+    pub spender: Account,
+    /// The block height from which the allowance can no longer be used.
+    pub expiry_height: Option<u64>,
+}
+
+impl Operation for SetAllowanceExpiry {
+    const NAME: &'static str = "set_allowance_expiry";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.spender.to_bytes());
+        // an empty field stands for no expiry
+        let expiry_height = self.expiry_height.map(u64::to_le_bytes);
+        msg.field(
+            expiry_height
+                .as_ref()
+                .map(|bytes| &bytes[..])
+                .unwrap_or(&[]),
+        );
+    }
+}
+
+/// Event emitted when the expiry of an allowance is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AllowanceExpiryEvent {
+    /// The account that owns the tokens.
+    pub owner: Account,
+    /// The account allowed to spend them.
+    pub spender: Account,
+    /// The block height from which the allowance can no longer be used, if any.
+    pub expiry_height: Option<u64>,
+}
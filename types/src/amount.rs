@@ -0,0 +1,198 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+
+/// An amount of tokens, in the base units the contract works with.
+///
+/// Tokens are displayed with a number of decimals, with one token being `10^decimals` base units.
+/// The conversion between the two is done by [`from_units`](Self::from_units) and
+/// [`to_display`](Self::to_display). Formatting an amount with a precision, e.g. `{:.18}`, displays
+/// it with that many decimals, while formatting it without one shows the base units.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TokenAmount(u64);
+
+impl TokenAmount {
+    /// No tokens.
+    pub const ZERO: Self = Self(0);
+
+    /// An amount of the given base units.
+    pub const fn from_base_units(base_units: u64) -> Self {
+        Self(base_units)
+    }
+
+    /// Parse an amount given in whole tokens, such as `"12.5"`, for a token with the given number
+    /// of decimals.
+    pub fn from_units(units: &str, decimals: u8) -> Result<Self, AmountError> {
+        let (whole, fraction) = match units.split_once('.') {
+            Some((whole, fraction)) => (whole, fraction),
+            None => (units, ""),
+        };
+
+        if whole.is_empty() && fraction.is_empty() {
+            return Err(AmountError::Invalid);
+        }
+        if !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+        {
+            return Err(AmountError::Invalid);
+        }
+
+        // trailing zeros don't add precision
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.len() > decimals as usize {
+            return Err(AmountError::TooPrecise);
+        }
+
+        let scale = scale(decimals)?;
+
+        let whole = match whole {
+            "" => 0,
+            whole => whole.parse::<u64>().map_err(|_| AmountError::Overflow)?,
+        };
+        let fraction = match fraction {
+            "" => 0,
+            // the fraction has at most `decimals` digits, so it fits once scaled
+            fraction => {
+                fraction.parse::<u64>().map_err(|_| AmountError::Overflow)?
+                    * 10u64.pow((decimals as usize - fraction.len()) as u32)
+            }
+        };
+
+        whole
+            .checked_mul(scale)
+            .and_then(|whole| whole.checked_add(fraction))
+            .map(Self)
+            .ok_or(AmountError::Overflow)
+    }
+
+    /// Convert an amount given in whole tokens as a float, for a token with the given number of
+    /// decimals. The amount is rounded to the nearest base unit.
+    pub fn from_f64(units: f64, decimals: u8) -> Result<Self, AmountError> {
+        if !units.is_finite() || units < 0.0 {
+            return Err(AmountError::Invalid);
+        }
+
+        // formatting rounds to the given number of decimals exactly, unlike scaling the float
+        Self::from_units(&format!("{:.*}", decimals as usize, units), decimals)
+    }
+
+    /// The amount in base units.
+    pub const fn base_units(self) -> u64 {
+        self.0
+    }
+
+    /// The amount in whole tokens, for a token with the given number of decimals, e.g. `"12.5"`.
+    /// Trailing zeros in the fraction are omitted.
+    pub fn to_display(self, decimals: u8) -> String {
+        let mut display = self.format_decimals(decimals);
+
+        if display.contains('.') {
+            let len = display.trim_end_matches('0').trim_end_matches('.').len();
+            display.truncate(len);
+        }
+
+        display
+    }
+
+    /// Add two amounts, returning `None` on overflow.
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        match self.0.checked_add(other.0) {
+            Some(sum) => Some(Self(sum)),
+            None => None,
+        }
+    }
+
+    /// Subtract an amount, returning `None` if it is larger.
+    pub const fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.0.checked_sub(other.0) {
+            Some(difference) => Some(Self(difference)),
+            None => None,
+        }
+    }
+
+    /// Multiply the amount, returning `None` on overflow.
+    pub const fn checked_mul(self, factor: u64) -> Option<Self> {
+        match self.0.checked_mul(factor) {
+            Some(product) => Some(Self(product)),
+            None => None,
+        }
+    }
+
+    /// Divide the amount, rounding down, and returning `None` if the divisor is zero.
+    pub const fn checked_div(self, divisor: u64) -> Option<Self> {
+        match self.0.checked_div(divisor) {
+            Some(quotient) => Some(Self(quotient)),
+            None => None,
+        }
+    }
+
+    /// The amount with exactly the given number of decimals, e.g. `"12.500"`.
+    fn format_decimals(self, decimals: u8) -> String {
+        if decimals == 0 {
+            return format!("{}", self.0);
+        }
+
+        // pad the base units so there is at least one digit before the point
+        let digits = format!("{:0width$}", self.0, width = decimals as usize + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - decimals as usize);
+
+        format!("{whole}.{fraction}")
+    }
+}
+
+impl From<u64> for TokenAmount {
+    fn from(base_units: u64) -> Self {
+        Self(base_units)
+    }
+}
+
+impl From<TokenAmount> for u64 {
+    fn from(amount: TokenAmount) -> Self {
+        amount.0
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(decimals) => {
+                let decimals = u8::try_from(decimals).map_err(|_| fmt::Error)?;
+                f.write_str(&self.format_decimals(decimals))
+            }
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
+/// `10^decimals`, the number of base units in a whole token.
+fn scale(decimals: u8) -> Result<u64, AmountError> {
+    10u64
+        .checked_pow(decimals as u32)
+        .ok_or(AmountError::Overflow)
+}
+
+/// Error returned when converting an amount of whole tokens to a [`TokenAmount`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmountError {
+    /// The amount is not a non-negative decimal number.
+    Invalid,
+    /// The amount has more decimals than the token.
+    TooPrecise,
+    /// The amount doesn't fit in base units.
+    Overflow,
+}
+
+impl fmt::Display for AmountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid => f.write_str("invalid token amount"),
+            Self::TooPrecise => f.write_str("token amount has too many decimals"),
+            Self::Overflow => f.write_str("token amount is too large"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AmountError {}
@@ -0,0 +1,50 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use execution_core::ContractId;
+
+use crate::{Account, Message, Operation};
+
+/// Approve a contract to spend tokens from the signer's account, and immediately call a function on
+/// it. The function is called with an [`ApprovalInfo`], and if it fails the approval is reverted.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApproveAndCall {
+    /// The contract to allow spending tokens.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub spender: ContractId,
+    /// The value to approve the transfer of.
+    pub value: u64,
+    /// The name of the function to call on the spender.
+    pub fn_name: String,
+    /// Data passed on to the spender.
+    pub data: Vec<u8>,
+}
+
+impl Operation for ApproveAndCall {
+    const NAME: &'static str = "approve_and_call";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.spender.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(self.fn_name.as_bytes());
+        msg.field(&self.data);
+    }
+}
+
+/// Used to inform a contract of an allowance it was given using `approve_and_call`.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApprovalInfo {
+    /// The account allowing the contract to spend its tokens.
+    pub owner: Account,
+    /// The value the contract is allowed to spend.
+    pub value: u64,
+    /// Data passed on by the owner.
+    pub data: Vec<u8>,
+}
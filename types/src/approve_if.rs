@@ -0,0 +1,29 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Approve a spender, only if its current allowance is the expected one. This prevents a spender
+/// from spending both the previous and the new allowance, by spending the previous one just before
+/// the change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApproveIf {
+    /// The account to allow spending tokens.
+    pub spender: Account,
+    /// The allowance the spender is expected to currently have.
+    pub expected: u64,
+    /// The value to approve the transfer of.
+    pub value: u64,
+}
+
+impl Operation for ApproveIf {
+    const NAME: &'static str = "approve_if";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.spender.to_bytes());
+        msg.field(&self.expected.to_le_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
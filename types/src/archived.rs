@@ -0,0 +1,190 @@
+//! Reading payloads and events in place, without deserializing them.
+//!
+//! High-throughput consumers, such as indexers, often need a single field of an event or payload.
+//! The archived form can be validated and read directly from its bytes, with the accessors below
+//! returning the fields as native types.
+
+use bytecheck::CheckBytes;
+use rkyv::validation::validators::DefaultValidator;
+use rkyv::{Archive, Deserialize, Infallible};
+
+use crate::{
+    Account, ArchivedAccount, ArchivedApproveEvent, ArchivedSequenced, ArchivedTransfer,
+    ArchivedTransferEvent, ArchivedTransferFrom, Memo, Sequenced, SignerKey,
+};
+
+/// Read a payload of type `T` in place, validating its archived form. Returns `None` if the bytes
+/// are not a valid `T`.
+///
+/// The bytes must be aligned for the archived `T`, as those of an [`AlignedVec`] are, otherwise
+/// they are rejected.
+///
+/// [`AlignedVec`]: rkyv::AlignedVec
+pub fn read_payload<'a, T>(bytes: &'a [u8]) -> Option<&'a T::Archived>
+where
+    T: Archive,
+    T::Archived: CheckBytes<DefaultValidator<'a>>,
+{
+    rkyv::check_archived_root::<T>(bytes).ok()
+}
+
+/// Read the data of an event emitted by the contract in place, as a [`Sequenced`] `T`. Returns
+/// `None` if the data is not valid for `T`.
+///
+/// The topic of the event is not checked, so `T` must be chosen based on it, such as
+/// [`TransferEvent`](crate::TransferEvent) for the [`TRANSFER_TOPIC`](crate::TRANSFER_TOPIC). As
+/// with [`read_payload`], the data must be aligned.
+pub fn read_event<'a, T>(data: &'a [u8]) -> Option<&'a ArchivedSequenced<T>>
+where
+    T: Archive,
+    ArchivedSequenced<T>: CheckBytes<DefaultValidator<'a>>,
+{
+    read_payload::<Sequenced<T>>(data)
+}
+
+fn deserialize<T>(archived: &T::Archived) -> T
+where
+    T: Archive,
+    T::Archived: Deserialize<T, Infallible>,
+{
+    // deserializing with `Infallible` never fails
+    archived.deserialize(&mut Infallible).unwrap()
+}
+
+impl ArchivedAccount {
+    /// The account, deserialized from its archived form.
+    pub fn to_account(&self) -> Account {
+        deserialize(self)
+    }
+}
+
+impl From<&ArchivedAccount> for Account {
+    fn from(account: &ArchivedAccount) -> Self {
+        account.to_account()
+    }
+}
+
+impl<T: Archive> ArchivedSequenced<T> {
+    /// The sequence number of the event.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// The event, in its archived form.
+    pub fn event(&self) -> &T::Archived {
+        &self.event
+    }
+}
+
+impl ArchivedTransferEvent {
+    /// The account tokens are transferred from.
+    pub fn owner(&self) -> Account {
+        self.owner.to_account()
+    }
+
+    /// The account spending the tokens, if any.
+    pub fn spender(&self) -> Option<Account> {
+        self.spender.as_ref().map(ArchivedAccount::to_account)
+    }
+
+    /// The account receiving the tokens.
+    pub fn to(&self) -> Account {
+        self.to.to_account()
+    }
+
+    /// The value transferred, including any burned tokens.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// The part of the value burned.
+    pub fn burned(&self) -> u64 {
+        self.burned
+    }
+
+    /// The memo the transfer was signed with, if any.
+    pub fn memo(&self) -> Option<&Memo> {
+        self.memo.as_ref()
+    }
+}
+
+impl ArchivedApproveEvent {
+    /// The account allowing the transfer.
+    pub fn owner(&self) -> Account {
+        self.owner.to_account()
+    }
+
+    /// The allowed spender.
+    pub fn spender(&self) -> Account {
+        self.spender.to_account()
+    }
+
+    /// The value the spender is allowed to spend.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+impl ArchivedTransfer {
+    /// The key signing the transfer.
+    pub fn from(&self) -> SignerKey {
+        deserialize(&self.from)
+    }
+
+    /// The account to transfer to.
+    pub fn to(&self) -> Account {
+        self.to.to_account()
+    }
+
+    /// The value to transfer.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// The nonce used to sign the transfer.
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// The data passed on to the receiving contract.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The memo surfaced in the transfer event, if any.
+    pub fn memo(&self) -> Option<&Memo> {
+        self.memo.as_ref()
+    }
+
+    /// The version of the message format the payload was signed with.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl ArchivedTransferFrom {
+    /// The key of the spender signing the transfer.
+    pub fn spender(&self) -> SignerKey {
+        deserialize(&self.spender)
+    }
+
+    /// The account that owns the tokens being transferred.
+    pub fn owner(&self) -> Account {
+        self.owner.to_account()
+    }
+
+    /// The account to transfer to.
+    pub fn to(&self) -> Account {
+        self.to.to_account()
+    }
+
+    /// The value to transfer.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// The nonce used to sign the transfer.
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+}
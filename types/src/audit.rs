@@ -0,0 +1,59 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+/// The books of a token, recomputed from its state by the `audit` query. They balance if the
+/// stored supply equals the sum of all balances, and of all tokens held by the contract on their
+/// behalf.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Audit {
+    /// The stored total supply.
+    pub supply: u64,
+    /// The sum of the balances of all accounts.
+    pub balances: u64,
+    /// The tokens held in locked transfers.
+    pub locked: u64,
+    /// The tokens held in proposed transfers not yet claimed.
+    pub pending: u64,
+    /// The tokens still vesting, or vested and not yet claimed.
+    pub vesting: u64,
+    /// The tokens staked.
+    pub staked: u64,
+    /// The tokens of airdrops not yet claimed.
+    pub airdrops: u64,
+    /// The tokens distributed as rewards and not yet claimed.
+    pub rewards: u64,
+    /// The tokens diverted to recovery buckets.
+    pub recoveries: u64,
+    /// The tokens held by the treasury.
+    pub treasury: u64,
+    /// The tokens deposited in streams and not yet withdrawn.
+    pub streams: u64,
+}
+
+impl Audit {
+    /// The sum of all balances and held tokens, which should equal the supply.
+    pub fn total(&self) -> u128 {
+        [
+            self.balances,
+            self.locked,
+            self.pending,
+            self.vesting,
+            self.staked,
+            self.airdrops,
+            self.rewards,
+            self.recoveries,
+            self.treasury,
+            self.streams,
+        ]
+        .iter()
+        .map(|value| *value as u128)
+        .sum()
+    }
+
+    /// Whether the books balance.
+    pub fn is_balanced(&self) -> bool {
+        self.total() == self.supply as u128
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use execution_core::signatures::bls::{MultisigSignature, PublicKey, SecretKey};
+
+use crate::{Account, Message, Operation, MESSAGE_VERSION};
+
+/// A transfer included in a [`BatchTransfer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchTransferEntry {
+    /// The account to transfer from.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub from: PublicKey,
+    /// The account to transfer to.
+    pub to: Account,
+    /// The value to transfer.
+    pub value: u64,
+    /// The nonce of the `from` account used for the transfer.
+    pub nonce: u64,
+}
+
+/// Many transfers, verified using a single aggregated signature.
+///
+/// Every account transferring from signs the whole batch using [`BatchTransfer::sign`], and the
+/// signatures are then combined using [`aggregate_signatures`]. This is much cheaper to verify than
+/// individual signatures over each transfer.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchTransfer {
+    version: u8,
+    transfers: Vec<BatchTransferEntry>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    signature: MultisigSignature,
+}
+
+impl BatchTransfer {
+    const NAME: &'static str = "batch_transfer";
+
+    /// Create a new, unsigned, batch of transfers.
+    pub fn new(transfers: Vec<BatchTransferEntry>) -> Self {
+        Self {
+            version: MESSAGE_VERSION,
+            transfers,
+            signature: MultisigSignature::default(),
+        }
+    }
+
+    /// Sign the batch with the key of one of the accounts transferring from.
+    pub fn sign(&self, sk: &SecretKey) -> MultisigSignature {
+        let pk = PublicKey::from(sk);
+        sk.sign_multisig(&pk, &self.signature_message())
+    }
+
+    /// Set the aggregated signature of all the accounts transferring from.
+    pub fn set_signature(&mut self, signature: MultisigSignature) {
+        self.signature = signature;
+    }
+
+    /// The version of the message format the batch was signed with.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// The transfers in the batch.
+    pub fn transfers(&self) -> &[BatchTransferEntry] {
+        &self.transfers
+    }
+
+    /// The aggregated signature over the batch.
+    pub fn signature(&self) -> &MultisigSignature {
+        &self.signature
+    }
+
+    /// The distinct accounts transferring from, and therefore required to sign the batch.
+    pub fn signers(&self) -> Vec<PublicKey> {
+        let mut signers = Vec::new();
+
+        for transfer in &self.transfers {
+            if !signers.contains(&transfer.from) {
+                signers.push(transfer.from);
+            }
+        }
+
+        signers
+    }
+
+    /// The message to be signed over.
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut msg = Message::new(self.version, Self::NAME);
+
+        msg.field(&(self.transfers.len() as u32).to_le_bytes());
+        for transfer in &self.transfers {
+            msg.field(&transfer.from.to_raw_bytes());
+            msg.field(&transfer.to.to_bytes());
+            msg.field(&transfer.value.to_le_bytes());
+            msg.field(&transfer.nonce.to_le_bytes());
+        }
+
+        msg.into_bytes()
+    }
+}
+
+/// Aggregate the signatures of all the accounts transferring from in a [`BatchTransfer`]. Returns
+/// `None` if no signatures are given.
+pub fn aggregate_signatures(signatures: &[MultisigSignature]) -> Option<MultisigSignature> {
+    let (first, rest) = signatures.split_first()?;
+    Some(first.aggregate(rest))
+}
+
+/// A transfer included in a [`BatchTransferFrom`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchTransferFromEntry {
+    /// The account to transfer from, spending the signer's allowance.
+    pub owner: Account,
+    /// The account to transfer to.
+    pub to: Account,
+    /// The value to transfer.
+    pub value: u64,
+}
+
+/// Many transfers spending the signer's allowances, possibly from different owners, under a single
+/// nonce and signature. Either all transfers succeed, or none do.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchTransferFrom {
+    /// The transfers in the batch.
+    pub transfers: Vec<BatchTransferFromEntry>,
+}
+
+impl Operation for BatchTransferFrom {
+    const NAME: &'static str = "batch_transfer_from";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&(self.transfers.len() as u32).to_le_bytes());
+        for transfer in &self.transfers {
+            msg.field(&transfer.owner.to_bytes());
+            msg.field(&transfer.to.to_bytes());
+            msg.field(&transfer.value.to_le_bytes());
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use alloc::vec::Vec;
+
+use execution_core::signatures::bls::{PublicKey, SecretKey, Signature};
+
+use crate::{
+    Account, Approve, HookPolicy, Memo, SchemeSignature, SignerKey, TokenAmount, Transfer,
+    TransferFrom, MESSAGE_VERSION,
+};
+
+impl Transfer {
+    /// Start building a transfer, setting its fields by name.
+    pub fn builder() -> TransferBuilder {
+        TransferBuilder::default()
+    }
+}
+
+impl TransferFrom {
+    /// Start building a transfer from an owner, setting its fields by name.
+    pub fn builder() -> TransferFromBuilder {
+        TransferFromBuilder::default()
+    }
+}
+
+impl Approve {
+    /// Start building an approval, setting its fields by name.
+    pub fn builder() -> ApproveBuilder {
+        ApproveBuilder::default()
+    }
+}
+
+/// Builds a [`Transfer`], with the fields that aren't set left at their defaults.
+///
+/// The recipient must be set before the transfer is signed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TransferBuilder {
+    to: Option<Account>,
+    value: u64,
+    nonce: u64,
+    hook_policy: Option<HookPolicy>,
+    data: Vec<u8>,
+    memo: Option<Memo>,
+    idempotency_id: Option<u64>,
+}
+
+impl TransferBuilder {
+    /// The account to transfer to.
+    pub fn to(mut self, to: impl Into<Account>) -> Self {
+        self.to = Some(to.into());
+        self
+    }
+
+    /// The value to transfer.
+    pub fn value(mut self, value: u64) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// The amount to transfer, in place of a value in base units.
+    pub fn amount(self, amount: TokenAmount) -> Self {
+        self.value(amount.base_units())
+    }
+
+    /// The nonce to sign the transfer with.
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// What happens should the receiving contract's `token_received` hook fail.
+    pub fn hook_policy(mut self, hook_policy: HookPolicy) -> Self {
+        self.hook_policy = Some(hook_policy);
+        self
+    }
+
+    /// The data passed on to the receiving contract's `token_received` hook.
+    pub fn data(mut self, data: Vec<u8>) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// The memo surfaced in the transfer event, such as a deposit reference.
+    pub fn memo(mut self, memo: Memo) -> Self {
+        self.memo = Some(memo);
+        self
+    }
+
+    /// The id identifying the submission of the transfer, whose status can be queried with
+    /// `submission_status` to tell whether it was executed.
+    pub fn idempotency_id(mut self, idempotency_id: u64) -> Self {
+        self.idempotency_id = Some(idempotency_id);
+        self
+    }
+
+    /// Sign the transfer.
+    ///
+    /// # Panics
+    /// When the recipient hasn't been set.
+    pub fn sign(self, from_sk: &SecretKey) -> Transfer {
+        let mut transfer = self.with_signature(PublicKey::from(from_sk), Signature::default());
+        transfer.signature = SchemeSignature::Bls(from_sk.sign(&transfer.signature_message()));
+        transfer
+    }
+
+    /// Finish the transfer with a signature produced elsewhere, such as by a hardware wallet.
+    /// The key and signature may be of any of the supported schemes.
+    ///
+    /// # Panics
+    /// When the recipient hasn't been set.
+    pub fn with_signature(
+        self,
+        from: impl Into<SignerKey>,
+        signature: impl Into<SchemeSignature>,
+    ) -> Transfer {
+        Transfer {
+            from: from.into(),
+            to: self.to.expect("The recipient of the transfer must be set"),
+            value: self.value,
+            nonce: self.nonce,
+            hook_policy: self.hook_policy,
+            data: self.data,
+            memo: self.memo,
+            idempotency_id: self.idempotency_id,
+            version: MESSAGE_VERSION,
+            signature: signature.into(),
+        }
+    }
+}
+
+/// Builds a [`TransferFrom`], with the fields that aren't set left at their defaults.
+///
+/// The owner and the recipient must be set before the transfer is signed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TransferFromBuilder {
+    owner: Option<Account>,
+    to: Option<Account>,
+    value: u64,
+    nonce: u64,
+    hook_policy: Option<HookPolicy>,
+    data: Vec<u8>,
+    memo: Option<Memo>,
+    idempotency_id: Option<u64>,
+}
+
+impl TransferFromBuilder {
+    /// The account to spend tokens from.
+    pub fn owner(mut self, owner: impl Into<Account>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    /// The account to transfer to.
+    pub fn to(mut self, to: impl Into<Account>) -> Self {
+        self.to = Some(to.into());
+        self
+    }
+
+    /// The value to transfer.
+    pub fn value(mut self, value: u64) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// The amount to transfer, in place of a value in base units.
+    pub fn amount(self, amount: TokenAmount) -> Self {
+        self.value(amount.base_units())
+    }
+
+    /// The nonce to sign the transfer with.
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// What happens should the receiving contract's `token_received` hook fail.
+    pub fn hook_policy(mut self, hook_policy: HookPolicy) -> Self {
+        self.hook_policy = Some(hook_policy);
+        self
+    }
+
+    /// The data passed on to the receiving contract's `token_received` hook.
+    pub fn data(mut self, data: Vec<u8>) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// The memo surfaced in the transfer event, such as a deposit reference.
+    pub fn memo(mut self, memo: Memo) -> Self {
+        self.memo = Some(memo);
+        self
+    }
+
+    /// The id identifying the submission of the transfer, whose status can be queried with
+    /// `submission_status` to tell whether it was executed.
+    pub fn idempotency_id(mut self, idempotency_id: u64) -> Self {
+        self.idempotency_id = Some(idempotency_id);
+        self
+    }
+
+    /// Sign the transfer.
+    ///
+    /// # Panics
+    /// When the owner or the recipient haven't been set.
+    pub fn sign(self, spender_sk: &SecretKey) -> TransferFrom {
+        let mut transfer = self.with_signature(PublicKey::from(spender_sk), Signature::default());
+        transfer.signature = SchemeSignature::Bls(spender_sk.sign(&transfer.signature_message()));
+        transfer
+    }
+
+    /// Finish the transfer with a signature produced elsewhere, such as by a hardware wallet.
+    /// The key and signature may be of any of the supported schemes.
+    ///
+    /// # Panics
+    /// When the owner or the recipient haven't been set.
+    pub fn with_signature(
+        self,
+        spender: impl Into<SignerKey>,
+        signature: impl Into<SchemeSignature>,
+    ) -> TransferFrom {
+        TransferFrom {
+            spender: spender.into(),
+            owner: self.owner.expect("The owner of the tokens must be set"),
+            to: self.to.expect("The recipient of the transfer must be set"),
+            value: self.value,
+            nonce: self.nonce,
+            hook_policy: self.hook_policy,
+            data: self.data,
+            memo: self.memo,
+            idempotency_id: self.idempotency_id,
+            version: MESSAGE_VERSION,
+            signature: signature.into(),
+        }
+    }
+}
+
+/// Builds an [`Approve`], with the fields that aren't set left at their defaults.
+///
+/// The spender must be set before the approval is signed.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ApproveBuilder {
+    spender: Option<Account>,
+    value: u64,
+    nonce: u64,
+}
+
+impl ApproveBuilder {
+    /// The account to allow spending tokens.
+    pub fn spender(mut self, spender: impl Into<Account>) -> Self {
+        self.spender = Some(spender.into());
+        self
+    }
+
+    /// The value to approve the transfer of.
+    pub fn value(mut self, value: u64) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// The amount to approve the transfer of, in place of a value in base units.
+    pub fn amount(self, amount: TokenAmount) -> Self {
+        self.value(amount.base_units())
+    }
+
+    /// The nonce to sign the approval with.
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// Sign the approval.
+    ///
+    /// # Panics
+    /// When the spender hasn't been set.
+    pub fn sign(self, owner_sk: &SecretKey) -> Approve {
+        let spender = self.spender.expect("The spender must be set");
+        Approve::new(owner_sk, spender, self.value, self.nonce)
+    }
+
+    /// Finish the approval with a signature produced elsewhere, such as by a hardware wallet.
+    /// The key and signature may be of any of the supported schemes.
+    ///
+    /// # Panics
+    /// When the spender hasn't been set.
+    pub fn with_signature(
+        self,
+        owner: impl Into<SignerKey>,
+        signature: impl Into<SchemeSignature>,
+    ) -> Approve {
+        let spender = self.spender.expect("The spender must be set");
+        Approve::from_parts(owner, spender, self.value, self.nonce, signature)
+    }
+}
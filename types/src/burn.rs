@@ -0,0 +1,60 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Burn tokens from the signer's balance, removing them from the supply.
+///
+/// Not supported by contracts deployed with `wrapped` set in the [`InitArgs`](crate::InitArgs),
+/// whose tokens are burned by withdrawing them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Burn {
+    /// The value to burn.
+    pub value: u64,
+}
+
+impl Operation for Burn {
+    const NAME: &'static str = "burn";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Burn tokens from an owner's balance, spending the signer's allowance.
+///
+/// Not supported by contracts deployed with `wrapped` set in the [`InitArgs`](crate::InitArgs),
+/// whose tokens are burned by withdrawing them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BurnFrom {
+    /// The account to burn from.
+    pub owner: Account,
+    /// The value to burn.
+    pub value: u64,
+}
+
+impl Operation for BurnFrom {
+    const NAME: &'static str = "burn_from";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.owner.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Event emitted when tokens are burned by their owner or a spender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BurnEvent {
+    /// The account the tokens are burned from.
+    pub owner: Account,
+    /// The account spending the tokens, set if `burn_from` is used.
+    pub spender: Option<Account>,
+    /// The value burned.
+    pub value: u64,
+}
@@ -0,0 +1,98 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Message, Operation};
+
+/// Limits on the value transferred, bounding the damage done by a compromised key.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateLimits {
+    /// The value each account may transfer in every window of `window_blocks` blocks. If `None`,
+    /// accounts are not limited.
+    pub account_limit: Option<u64>,
+    /// The number of blocks in each window. Windows start at multiples of this number.
+    pub window_blocks: u64,
+    /// The total value that may be transferred in each block. If `None`, blocks are not limited.
+    pub block_limit: Option<u64>,
+}
+
+/// Set the limits on the value transferred. Only the admin may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetRateLimits {
+    /// The new limits.
+    pub limits: RateLimits,
+}
+
+impl Operation for SetRateLimits {
+    const NAME: &'static str = "set_rate_limits";
+
+    fn write_message(&self, msg: &mut Message) {
+        // an empty field stands for no limit
+        let account_limit = self.limits.account_limit.map(u64::to_le_bytes);
+        let block_limit = self.limits.block_limit.map(u64::to_le_bytes);
+
+        msg.field(
+            account_limit
+                .as_ref()
+                .map(|bytes| &bytes[..])
+                .unwrap_or(&[]),
+        );
+        msg.field(&self.limits.window_blocks.to_le_bytes());
+        msg.field(block_limit.as_ref().map(|bytes| &bytes[..]).unwrap_or(&[]));
+    }
+}
+
+/// Trip the circuit breaker, halting all transfers above a threshold until it is reset. Only the
+/// admin may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TripBreaker {
+    /// The largest value that may still be transferred.
+    pub threshold: u64,
+}
+
+impl Operation for TripBreaker {
+    const NAME: &'static str = "trip_breaker";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.threshold.to_le_bytes());
+    }
+}
+
+/// Reset a tripped circuit breaker, resuming transfers of any value. Only the admin may sign this
+/// operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResetBreaker;
+
+impl Operation for ResetBreaker {
+    const NAME: &'static str = "reset_breaker";
+
+    fn write_message(&self, _msg: &mut Message) {}
+}
+
+/// The state of the circuit breaker.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BreakerInfo {
+    /// The limits on the value transferred.
+    pub limits: RateLimits,
+    /// The largest value that may be transferred while the breaker is tripped, or `None` if it
+    /// isn't.
+    pub tripped_threshold: Option<u64>,
+}
+
+/// Event emitted when the rate limits are set, or the circuit breaker is tripped or reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BreakerEvent {
+    /// The new state of the circuit breaker.
+    pub info: BreakerInfo,
+}
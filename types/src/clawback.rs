@@ -0,0 +1,107 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Announce the clawback of tokens from an account, which can be executed once the clawback delay
+/// chosen at `init` has passed. Only the admin may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnnounceClawback {
+    /// The account to claw tokens back from.
+    pub account: Account,
+    /// The account to move the tokens to.
+    pub to: Account,
+    /// The value to claw back.
+    pub value: u64,
+}
+
+impl Operation for AnnounceClawback {
+    const NAME: &'static str = "announce_clawback";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.account.to_bytes());
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Execute an announced clawback, once its delay has passed. Only the admin may sign this
+/// operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecuteClawback {
+    /// The id of the announced clawback.
+    pub id: u64,
+}
+
+impl Operation for ExecuteClawback {
+    const NAME: &'static str = "execute_clawback";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.id.to_le_bytes());
+    }
+}
+
+/// Cancel an announced clawback, e.g. after the holder successfully contested it. Only the admin
+/// may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CancelClawback {
+    /// The id of the announced clawback.
+    pub id: u64,
+}
+
+impl Operation for CancelClawback {
+    const NAME: &'static str = "cancel_clawback";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.id.to_le_bytes());
+    }
+}
+
+/// An announced clawback, waiting for its delay to pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clawback {
+    /// The account to claw tokens back from.
+    pub account: Account,
+    /// The account to move the tokens to.
+    pub to: Account,
+    /// The value to claw back.
+    pub value: u64,
+    /// The block height from which the clawback can be executed.
+    pub executable_height: u64,
+}
+
+/// Event emitted when a clawback is announced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClawbackAnnouncedEvent {
+    /// The id of the clawback.
+    pub id: u64,
+    /// The account to claw tokens back from.
+    pub account: Account,
+    /// The account to move the tokens to.
+    pub to: Account,
+    /// The value to claw back.
+    pub value: u64,
+    /// The block height from which the clawback can be executed.
+    pub executable_height: u64,
+}
+
+/// Event emitted when an announced clawback is executed or cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClawbackEvent {
+    /// The id of the clawback.
+    pub id: u64,
+    /// Whether the clawback was executed, as opposed to cancelled.
+    pub executed: bool,
+}
@@ -0,0 +1,42 @@
+use alloc::vec::Vec;
+
+use execution_core::ContractId;
+
+use crate::InitArgs;
+
+/// Derive the id of a contract deployed with the given bytecode, nonce, and owner, the same way the
+/// VM does when no id is given explicitly.
+///
+/// The id is the 32-byte BLAKE2b hash of the bytecode, followed by the little-endian bytes of the
+/// nonce, and the owner. The constructor argument doesn't take part, so deploying the same bytecode
+/// with the same owner more than once requires a different nonce for each deployment.
+pub fn gen_contract_id(bytecode: &[u8], nonce: u64, owner: &[u8]) -> ContractId {
+    let mut state = blake2b_simd::Params::new().hash_length(32).to_state();
+    state.update(bytecode);
+    state.update(&nonce.to_le_bytes());
+    state.update(owner);
+
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(state.finalize().as_bytes());
+    ContractId::from_bytes(bytes)
+}
+
+/// Everything needed to deploy a token besides its bytecode, shared by the tooling that deploys it
+/// and by the tooling that needs to know its id beforehand.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeploymentSpec {
+    /// The owner of the deployed contract.
+    pub owner: Vec<u8>,
+    /// The nonce distinguishing deployments of the same bytecode by the same owner.
+    pub nonce: u64,
+    /// The arguments the contract is initialized with.
+    pub init_args: InitArgs,
+}
+
+impl DeploymentSpec {
+    /// The id the token gets when deployed with the given bytecode.
+    pub fn contract_id(&self, bytecode: &[u8]) -> ContractId {
+        gen_contract_id(bytecode, self.nonce, &self.owner)
+    }
+}
@@ -0,0 +1,46 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::Account;
+
+/// A schedule of tokens minted to a treasury at a fixed rate, starting when the contract is
+/// deployed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmissionSchedule {
+    /// The value minted for every epoch.
+    pub rate: u64,
+    /// The number of blocks in each epoch.
+    pub epoch_blocks: u64,
+    /// The account the tokens are minted to.
+    pub treasury: Account,
+    /// The height after which no more tokens accrue. If `None`, the emission never ends.
+    pub end_height: Option<u64>,
+}
+
+/// The state of the emission of a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmissionInfo {
+    /// The schedule of the emission.
+    pub schedule: EmissionSchedule,
+    /// The height the first epoch starts at.
+    pub start_height: u64,
+    /// The number of epochs whose tokens have been minted.
+    pub epochs_emitted: u64,
+}
+
+/// Event emitted when the tokens accrued by the emission are minted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EmissionEvent {
+    /// The treasury credited.
+    pub treasury: Account,
+    /// The value minted.
+    pub value: u64,
+    /// The number of epochs whose tokens have been minted, including these.
+    pub epochs_emitted: u64,
+}
@@ -0,0 +1,125 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::validation::validators::DefaultValidator;
+use rkyv::{AlignedVec, Archive, Deserialize, Infallible, Serialize};
+
+use crate::{
+    ApproveEvent, BurnEvent, DepositEvent, EmissionEvent, KeyRotated, PauseEvent, TokenMintedEvent,
+    TransferEvent, WithdrawEvent,
+};
+
+/// The topic of a [`TransferEvent`].
+pub const TRANSFER_TOPIC: &str = "transfer";
+/// The topic of an [`ApproveEvent`].
+pub const APPROVE_TOPIC: &str = "approve";
+/// The topic of an [`EmissionEvent`].
+pub const EMISSION_TOPIC: &str = "emission";
+/// The topic of a [`DepositEvent`].
+pub const DEPOSIT_TOPIC: &str = "deposit";
+/// The topic of a [`TokenMintedEvent`].
+pub const TOKEN_MINTED_TOPIC: &str = "token_minted";
+/// The topic of a [`BurnEvent`].
+pub const BURN_TOPIC: &str = "burn";
+/// The topic of a [`WithdrawEvent`].
+pub const WITHDRAW_TOPIC: &str = "withdraw";
+/// The topic of a [`PauseEvent`].
+pub const PAUSE_TOPIC: &str = "pause";
+/// The topic of a [`KeyRotated`] event.
+pub const KEY_ROTATED_TOPIC: &str = "key_rotated";
+
+/// The data of every event emitted by the contract, tagging the event with its sequence number.
+/// Sequence numbers start at zero and increase by one with each event, allowing indexers to detect
+/// missed events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sequenced<T> {
+    /// The sequence number of the event.
+    pub seq: u64,
+    /// The event.
+    pub event: T,
+}
+
+/// A recent event, as kept by the contract and returned by the `events_since` feeder query.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EventRecord {
+    /// The sequence number of the event.
+    pub seq: u64,
+    /// The topic of the event.
+    pub topic: String,
+    /// The data of the event, i.e. the serialized [`Sequenced`] event.
+    pub data: Vec<u8>,
+}
+
+/// The number of recent events kept by the contract.
+pub const EVENT_BUFFER_LEN: usize = 1024;
+
+/// An event emitted by the contract, decoded from its topic and data by [`decode_event`].
+///
+/// Only the events moving tokens, minting or burning them, pausing operations, or changing the
+/// ownership of an account are decoded, with any other left as [`Other`](Self::Other).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenEvent {
+    /// Tokens were transferred.
+    Transfer(Sequenced<TransferEvent>),
+    /// An allowance was set.
+    Approve(Sequenced<ApproveEvent>),
+    /// Tokens were minted by the emission.
+    Emission(Sequenced<EmissionEvent>),
+    /// Wrapped tokens were minted for deposited Dusk.
+    Deposit(Sequenced<DepositEvent>),
+    /// Tokens of a created token were minted.
+    TokenMinted(Sequenced<TokenMintedEvent>),
+    /// Tokens were burned.
+    Burn(Sequenced<BurnEvent>),
+    /// Wrapped tokens were burned for withdrawn Dusk.
+    Withdraw(Sequenced<WithdrawEvent>),
+    /// Operations were paused or resumed.
+    Pause(Sequenced<PauseEvent>),
+    /// An account moved to a new key.
+    KeyRotated(Sequenced<KeyRotated>),
+    /// Any other event, left undecoded.
+    Other {
+        /// The topic of the event.
+        topic: String,
+        /// The data of the event.
+        data: Vec<u8>,
+    },
+}
+
+/// Decode an event emitted by the contract, based on its topic. Returns `None` if the data is not
+/// valid for the topic.
+pub fn decode_event(topic: &str, data: &[u8]) -> Option<TokenEvent> {
+    let event = match topic {
+        TRANSFER_TOPIC => TokenEvent::Transfer(decode(data)?),
+        APPROVE_TOPIC => TokenEvent::Approve(decode(data)?),
+        EMISSION_TOPIC => TokenEvent::Emission(decode(data)?),
+        DEPOSIT_TOPIC => TokenEvent::Deposit(decode(data)?),
+        TOKEN_MINTED_TOPIC => TokenEvent::TokenMinted(decode(data)?),
+        BURN_TOPIC => TokenEvent::Burn(decode(data)?),
+        WITHDRAW_TOPIC => TokenEvent::Withdraw(decode(data)?),
+        PAUSE_TOPIC => TokenEvent::Pause(decode(data)?),
+        KEY_ROTATED_TOPIC => TokenEvent::KeyRotated(decode(data)?),
+        _ => TokenEvent::Other {
+            topic: String::from(topic),
+            data: Vec::from(data),
+        },
+    };
+    Some(event)
+}
+
+fn decode<T>(data: &[u8]) -> Option<T>
+where
+    T: Archive,
+    T::Archived: for<'a> CheckBytes<DefaultValidator<'a>> + Deserialize<T, Infallible>,
+{
+    // the archived event must be aligned, which the emitted bytes may not be
+    let mut bytes = AlignedVec::with_capacity(data.len());
+    bytes.extend_from_slice(data);
+
+    rkyv::from_bytes(&bytes).ok()
+}
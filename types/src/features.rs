@@ -0,0 +1,42 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+/// The version of the token interface implemented by the contract. It is bumped whenever entry
+/// points are changed in ways that integrations must account for.
+pub const SPEC_VERSION: u32 = 5;
+
+/// An optional extension of the token interface, whose support by a deployment can be queried
+/// using `supports`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeatureId {
+    /// A burn rate and relayed transfers paying a fee, from the `fees` feature.
+    Fees,
+    /// The whitelist mode, from the `compliance` feature.
+    Compliance,
+    /// Wrapping native Dusk, if the deployment was initialized as wrapped.
+    Wrapped,
+    /// Clawbacks by the admin, if the deployment was initialized with a clawback delay.
+    Clawback,
+    /// Snapshots of balances and the total supply.
+    Snapshots,
+    /// Accounts controlled by multiple keys.
+    Multisig,
+    /// Off-chain approvals, submitted with `permit_transfer_from`.
+    Permit,
+    /// Tokens issued beyond the default one, keyed by a token id.
+    MultiToken,
+    /// Flash loans repaid within the same call.
+    FlashLoans,
+    /// Rate limits and the circuit breaker.
+    CircuitBreaker,
+    /// Staking the Dusk backing wrapped tokens, if the deployment was initialized as wrapped.
+    NativeStaking,
+    /// Tokens emitted to a treasury, if the deployment was initialized with an emission.
+    Emission,
+    /// Pausing transfers, approvals, and minting separately.
+    Pause,
+    /// A treasury paying registered payees up to a cap in each epoch.
+    Treasury,
+}
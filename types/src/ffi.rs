@@ -0,0 +1,442 @@
+//! A C ABI to construct, sign, serialize, and verify payloads.
+//!
+//! Every function returns a [`Status`], with [`Status::Ok`] signaling success. Inputs are passed as
+//! pointers to fixed-size byte arrays:
+//!
+//! - secret keys as 32 bytes
+//! - public keys as 96 bytes, in their compressed form
+//! - signatures as 48 bytes
+//! - accounts as 194 bytes, as given by [`Account::to_bytes`]
+//!
+//! Outputs are written to buffers owned by the caller, so no memory allocated by this library is
+//! ever handed out. Functions writing variable-length output take a buffer, its length, and a
+//! pointer where the number of bytes written is stored. If the buffer is too small, nothing is
+//! written to it, the required length is stored instead, and [`Status::BufferTooSmall`] is
+//! returned.
+//!
+//! Payloads are serialized using `rkyv`, as expected by the contract.
+//!
+//! To link against these functions, build the crate as a static or dynamic library, e.g.:
+//!
+//! ```sh
+//! cargo rustc -p ttoken-types --release --features ffi --crate-type staticlib
+//! ```
+
+// Every field of a payload is passed as a separate argument.
+#![allow(clippy::too_many_arguments)]
+
+use core::{ptr, slice};
+
+use dusk_bytes::Serializable;
+use rkyv::ser::serializers::AllocSerializer;
+use rkyv::validation::validators::DefaultValidator;
+use rkyv::{AlignedVec, Archive, Deserialize, Infallible};
+
+use bytecheck::CheckBytes;
+use execution_core::signatures::bls::{PublicKey, SecretKey, Signature};
+
+use crate::{Account, Approve, Transfer, TransferFrom};
+
+/// The size of the scratch space used when serializing payloads.
+const SCRATCH_SIZE: usize = 1024;
+
+/// The result of a call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer was null.
+    NullPointer = 1,
+    /// A secret or public key is invalid.
+    InvalidKey = 2,
+    /// An account is invalid.
+    InvalidAccount = 3,
+    /// A signature is invalid, or doesn't match the payload.
+    InvalidSignature = 4,
+    /// The output buffer is too small. The required length was stored.
+    BufferTooSmall = 5,
+    /// A payload could not be serialized.
+    SerializationFailed = 6,
+    /// The bytes given are not a valid serialized payload.
+    InvalidPayload = 7,
+}
+
+/// Computes the public key of a secret key.
+///
+/// # Safety
+/// `sk` must point to 32 readable bytes, and `pk_out` to 96 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_public_key(sk: *const u8, pk_out: *mut u8) -> Status {
+    status(|| {
+        let sk = read_secret_key(sk)?;
+        let pk = PublicKey::from(&sk);
+        write_array(&pk.to_bytes(), pk_out)
+    })
+}
+
+/// Writes the message to sign for a transfer.
+///
+/// # Safety
+/// `from` and `to` must point to a public key and an account respectively, and `out` to `out_len`
+/// writable bytes. `written` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_transfer_message(
+    from: *const u8,
+    to: *const u8,
+    value: u64,
+    nonce: u64,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Status {
+    status(|| {
+        let from = read_public_key(from)?;
+        let to = read_account(to)?;
+        let transfer = Transfer::from_parts(from, to, value, nonce, Signature::default());
+        write_buf(&transfer.signature_message(), out, out_len, written)
+    })
+}
+
+/// Signs a transfer, writing it serialized.
+///
+/// # Safety
+/// `sk` and `to` must point to a secret key and an account respectively, and `out` to `out_len`
+/// writable bytes. `written` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_transfer_sign(
+    sk: *const u8,
+    to: *const u8,
+    value: u64,
+    nonce: u64,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Status {
+    status(|| {
+        let sk = read_secret_key(sk)?;
+        let to = read_account(to)?;
+        let transfer = Transfer::new(&sk, to, value, nonce);
+        write_payload(&transfer, out, out_len, written)
+    })
+}
+
+/// Assembles a transfer signed elsewhere, writing it serialized. The signature is verified before
+/// the transfer is written.
+///
+/// # Safety
+/// `from`, `to`, and `signature` must point to a public key, an account, and a signature
+/// respectively, and `out` to `out_len` writable bytes. `written` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_transfer_with_signature(
+    from: *const u8,
+    to: *const u8,
+    value: u64,
+    nonce: u64,
+    signature: *const u8,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Status {
+    status(|| {
+        let from = read_public_key(from)?;
+        let to = read_account(to)?;
+        let signature = read_signature(signature)?;
+        let transfer = Transfer::from_parts(from, to, value, nonce, signature);
+        verified(transfer.verify())?;
+        write_payload(&transfer, out, out_len, written)
+    })
+}
+
+/// Verifies the signature of a serialized transfer.
+///
+/// # Safety
+/// `payload` must point to `payload_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_transfer_verify(payload: *const u8, payload_len: usize) -> Status {
+    status(|| {
+        let transfer: Transfer = read_payload(payload, payload_len)?;
+        verified(transfer.verify())
+    })
+}
+
+/// Writes the message to sign for a transfer spending tokens from the `owner`.
+///
+/// # Safety
+/// `spender` must point to a public key, `owner` and `to` to accounts, and `out` to `out_len`
+/// writable bytes. `written` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_transfer_from_message(
+    spender: *const u8,
+    owner: *const u8,
+    to: *const u8,
+    value: u64,
+    nonce: u64,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Status {
+    status(|| {
+        let spender = read_public_key(spender)?;
+        let owner = read_account(owner)?;
+        let to = read_account(to)?;
+        let transfer =
+            TransferFrom::from_parts(spender, owner, to, value, nonce, Signature::default());
+        write_buf(&transfer.signature_message(), out, out_len, written)
+    })
+}
+
+/// Signs a transfer spending tokens from the `owner`, writing it serialized.
+///
+/// # Safety
+/// `sk` must point to a secret key, `owner` and `to` to accounts, and `out` to `out_len` writable
+/// bytes. `written` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_transfer_from_sign(
+    sk: *const u8,
+    owner: *const u8,
+    to: *const u8,
+    value: u64,
+    nonce: u64,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Status {
+    status(|| {
+        let sk = read_secret_key(sk)?;
+        let owner = read_account(owner)?;
+        let to = read_account(to)?;
+        let transfer = TransferFrom::new(&sk, owner, to, value, nonce);
+        write_payload(&transfer, out, out_len, written)
+    })
+}
+
+/// Assembles a transfer spending tokens from the `owner` signed elsewhere, writing it serialized.
+/// The signature is verified before the transfer is written.
+///
+/// # Safety
+/// `spender` must point to a public key, `owner` and `to` to accounts, `signature` to a signature,
+/// and `out` to `out_len` writable bytes. `written` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_transfer_from_with_signature(
+    spender: *const u8,
+    owner: *const u8,
+    to: *const u8,
+    value: u64,
+    nonce: u64,
+    signature: *const u8,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Status {
+    status(|| {
+        let spender = read_public_key(spender)?;
+        let owner = read_account(owner)?;
+        let to = read_account(to)?;
+        let signature = read_signature(signature)?;
+        let transfer = TransferFrom::from_parts(spender, owner, to, value, nonce, signature);
+        verified(transfer.verify())?;
+        write_payload(&transfer, out, out_len, written)
+    })
+}
+
+/// Verifies the signature of a serialized transfer spending tokens from an owner.
+///
+/// # Safety
+/// `payload` must point to `payload_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_transfer_from_verify(
+    payload: *const u8,
+    payload_len: usize,
+) -> Status {
+    status(|| {
+        let transfer: TransferFrom = read_payload(payload, payload_len)?;
+        verified(transfer.verify())
+    })
+}
+
+/// Writes the message to sign for an approval.
+///
+/// # Safety
+/// `owner` and `spender` must point to a public key and an account respectively, and `out` to
+/// `out_len` writable bytes. `written` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_approve_message(
+    owner: *const u8,
+    spender: *const u8,
+    value: u64,
+    nonce: u64,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Status {
+    status(|| {
+        let owner = read_public_key(owner)?;
+        let spender = read_account(spender)?;
+        let approve = Approve::from_parts(owner, spender, value, nonce, Signature::default());
+        write_buf(&approve.signature_message(), out, out_len, written)
+    })
+}
+
+/// Signs an approval, writing it serialized.
+///
+/// # Safety
+/// `sk` and `spender` must point to a secret key and an account respectively, and `out` to
+/// `out_len` writable bytes. `written` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_approve_sign(
+    sk: *const u8,
+    spender: *const u8,
+    value: u64,
+    nonce: u64,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Status {
+    status(|| {
+        let sk = read_secret_key(sk)?;
+        let spender = read_account(spender)?;
+        let approve = Approve::new(&sk, spender, value, nonce);
+        write_payload(&approve, out, out_len, written)
+    })
+}
+
+/// Assembles an approval signed elsewhere, writing it serialized. The signature is verified before
+/// the approval is written.
+///
+/// # Safety
+/// `owner`, `spender`, and `signature` must point to a public key, an account, and a signature
+/// respectively, and `out` to `out_len` writable bytes. `written` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_approve_with_signature(
+    owner: *const u8,
+    spender: *const u8,
+    value: u64,
+    nonce: u64,
+    signature: *const u8,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Status {
+    status(|| {
+        let owner = read_public_key(owner)?;
+        let spender = read_account(spender)?;
+        let signature = read_signature(signature)?;
+        let approve = Approve::from_parts(owner, spender, value, nonce, signature);
+        verified(approve.verify())?;
+        write_payload(&approve, out, out_len, written)
+    })
+}
+
+/// Verifies the signature of a serialized approval.
+///
+/// # Safety
+/// `payload` must point to `payload_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ttoken_approve_verify(payload: *const u8, payload_len: usize) -> Status {
+    status(|| {
+        let approve: Approve = read_payload(payload, payload_len)?;
+        verified(approve.verify())
+    })
+}
+
+fn status(f: impl FnOnce() -> Result<(), Status>) -> Status {
+    match f() {
+        Ok(()) => Status::Ok,
+        Err(status) => status,
+    }
+}
+
+fn verified(valid: bool) -> Result<(), Status> {
+    if !valid {
+        return Err(Status::InvalidSignature);
+    }
+    Ok(())
+}
+
+unsafe fn read_array<const N: usize>(ptr: *const u8) -> Result<[u8; N], Status> {
+    if ptr.is_null() {
+        return Err(Status::NullPointer);
+    }
+
+    let mut bytes = [0u8; N];
+    ptr::copy_nonoverlapping(ptr, bytes.as_mut_ptr(), N);
+    Ok(bytes)
+}
+
+unsafe fn read_secret_key(ptr: *const u8) -> Result<SecretKey, Status> {
+    let bytes = read_array(ptr)?;
+    SecretKey::from_bytes(&bytes).map_err(|_| Status::InvalidKey)
+}
+
+unsafe fn read_public_key(ptr: *const u8) -> Result<PublicKey, Status> {
+    let bytes = read_array(ptr)?;
+    PublicKey::from_bytes(&bytes).map_err(|_| Status::InvalidKey)
+}
+
+unsafe fn read_signature(ptr: *const u8) -> Result<Signature, Status> {
+    let bytes = read_array(ptr)?;
+    Signature::from_bytes(&bytes).map_err(|_| Status::InvalidSignature)
+}
+
+unsafe fn read_account(ptr: *const u8) -> Result<Account, Status> {
+    let bytes = read_array(ptr)?;
+    Account::from_bytes(&bytes).map_err(|_| Status::InvalidAccount)
+}
+
+unsafe fn read_payload<T>(ptr: *const u8, len: usize) -> Result<T, Status>
+where
+    T: Archive,
+    T::Archived: for<'a> CheckBytes<DefaultValidator<'a>> + Deserialize<T, Infallible>,
+{
+    if ptr.is_null() {
+        return Err(Status::NullPointer);
+    }
+
+    // the archived payload must be aligned, which the caller's buffer may not be
+    let mut bytes = AlignedVec::with_capacity(len);
+    bytes.extend_from_slice(slice::from_raw_parts(ptr, len));
+
+    rkyv::from_bytes(&bytes).map_err(|_| Status::InvalidPayload)
+}
+
+unsafe fn write_array(bytes: &[u8], out: *mut u8) -> Result<(), Status> {
+    if out.is_null() {
+        return Err(Status::NullPointer);
+    }
+
+    ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+    Ok(())
+}
+
+unsafe fn write_buf(
+    bytes: &[u8],
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Result<(), Status> {
+    if written.is_null() {
+        return Err(Status::NullPointer);
+    }
+
+    *written = bytes.len();
+    if bytes.len() > out_len {
+        return Err(Status::BufferTooSmall);
+    }
+
+    write_array(bytes, out)
+}
+
+unsafe fn write_payload<T>(
+    payload: &T,
+    out: *mut u8,
+    out_len: usize,
+    written: *mut usize,
+) -> Result<(), Status>
+where
+    T: rkyv::Serialize<AllocSerializer<SCRATCH_SIZE>>,
+{
+    let bytes =
+        rkyv::to_bytes::<_, SCRATCH_SIZE>(payload).map_err(|_| Status::SerializationFailed)?;
+    write_buf(&bytes, out, out_len, written)
+}
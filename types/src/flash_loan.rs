@@ -0,0 +1,58 @@
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use execution_core::ContractId;
+
+/// The flash loan fee denoting that the fee is the whole loan, i.e. a flash loan fee is expressed in
+/// basis points.
+pub const FLASH_LOAN_FEE_SCALE: u16 = 10_000;
+
+/// Mint tokens to a contract for the duration of a call to its `on_flash_loan` function, which must
+/// leave it with enough tokens to repay the loan plus the fee.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlashLoan {
+    /// The contract borrowing the tokens.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub borrower: ContractId,
+    /// The value lent.
+    pub value: u64,
+    /// Data passed on to the borrower.
+    pub data: Vec<u8>,
+}
+
+/// Used to inform a contract of a flash loan it was given, when calling its `on_flash_loan`
+/// function. The function must return `true` to accept the loan.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlashLoanInfo {
+    /// The contract that requested the loan, or `None` if it was requested directly by a
+    /// transaction. Borrowers should only accept loans they requested, since the fee is taken from
+    /// their balance.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+    pub initiator: Option<ContractId>,
+    /// The value lent.
+    pub value: u64,
+    /// The fee taken on top of the value when the loan is repaid.
+    pub fee: u64,
+    /// Data passed on by the initiator.
+    pub data: Vec<u8>,
+}
+
+/// Event emitted when a flash loan is repaid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlashLoanEvent {
+    /// The contract that borrowed the tokens.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub borrower: ContractId,
+    /// The value lent.
+    pub value: u64,
+    /// The fee paid to the token contract.
+    pub fee: u64,
+}
@@ -0,0 +1,23 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, TokenId};
+
+/// A transfer kept in the history of the accounts involved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferRecord {
+    /// The token transferred.
+    pub token: TokenId,
+    /// The account tokens were transferred from.
+    pub owner: Account,
+    /// The account spending the tokens, if not the owner.
+    pub spender: Option<Account>,
+    /// The account receiving the tokens.
+    pub to: Account,
+    /// The value transferred, including any burned tokens.
+    pub value: u64,
+    /// The block height the transfer happened at.
+    pub block_height: u64,
+}
@@ -0,0 +1,59 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// A request to pay, created by the payee and accepted by the payer signing it off-chain. The
+/// signed invoice is then submitted with `settle_invoice` by anyone - usually the payee itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Invoice {
+    /// The id of the invoice, chosen by the payee, and unique among its invoices to the payer.
+    pub id: u64,
+    /// The account to pay.
+    pub payee: Account,
+    /// The value to pay.
+    pub value: u64,
+    /// The last block height at which the invoice may be settled.
+    pub expiry: u64,
+}
+
+impl Operation for Invoice {
+    const NAME: &'static str = "invoice";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.id.to_le_bytes());
+        msg.field(&self.payee.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.expiry.to_le_bytes());
+    }
+}
+
+/// Used to query whether an invoice of a payee has been settled by a payer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InvoiceId {
+    /// The payee of the invoice.
+    pub payee: Account,
+    /// The payer of the invoice.
+    pub payer: Account,
+    /// The id of the invoice.
+    pub id: u64,
+}
+
+/// Event emitted when an invoice is settled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InvoiceSettledEvent {
+    /// The payee of the invoice.
+    pub payee: Account,
+    /// The id of the invoice.
+    pub id: u64,
+    /// The account that accepted and paid the invoice.
+    pub payer: Account,
+    /// The value paid.
+    pub value: u64,
+}
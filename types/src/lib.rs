@@ -1,57 +1,366 @@
 //! Types used to inteact with the `ttoken-contract`.
 
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 
+extern crate alloc;
+
+use alloc::vec::Vec;
 use core::cmp::Ordering;
+use core::fmt;
 
 use bytecheck::CheckBytes;
+use dusk_bytes::Serializable;
 use rkyv::{Archive, Deserialize, Serialize};
 
 use execution_core::signatures::bls::{PublicKey, SecretKey, Signature};
 use execution_core::ContractId;
 
+mod address;
+mod airdrop;
+mod allowance_expiry;
+mod amount;
+mod approve_and_call;
+mod approve_if;
+mod archived;
+mod audit;
+mod batch;
+mod builder;
+mod burn;
+mod circuit_breaker;
+mod clawback;
+#[cfg(feature = "std")]
+mod deployment;
+mod emission;
+mod events;
+mod features;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod flash_loan;
+mod history;
+mod invoice;
+mod locked;
+mod message;
+mod migration;
+mod min_transfer;
+mod multi_token;
+mod multisig;
+mod native_staking;
+mod nonce;
+mod operator;
+mod pause;
+mod permit;
+mod prune;
+mod raw;
+mod relayed;
+mod rescue;
+mod rewards;
+mod rotate_key;
+mod scheme;
+#[cfg(feature = "serde")]
+mod serde_hex;
+mod session_key;
+mod snapshot;
+mod spending_limit;
+mod staking;
+mod state_root;
+mod stream;
+mod sub_account;
+mod submission;
+mod treasury;
+mod two_phase;
+mod verifier;
+mod vesting;
+mod whitelist;
+mod wrapped;
+
+pub use address::*;
+pub use airdrop::*;
+pub use allowance_expiry::*;
+pub use amount::*;
+pub use approve_and_call::*;
+pub use approve_if::*;
+pub use archived::*;
+pub use audit::*;
+pub use batch::*;
+pub use builder::*;
+pub use burn::*;
+pub use circuit_breaker::*;
+pub use clawback::*;
+#[cfg(feature = "std")]
+pub use deployment::*;
+pub use emission::*;
+pub use events::*;
+pub use features::*;
+pub use flash_loan::*;
+pub use history::*;
+pub use invoice::*;
+pub use locked::*;
+pub use message::*;
+pub use migration::*;
+pub use min_transfer::*;
+pub use multi_token::*;
+pub use multisig::*;
+pub use native_staking::*;
+pub use nonce::*;
+pub use operator::*;
+pub use pause::*;
+pub use permit::*;
+pub use prune::*;
+pub use raw::*;
+pub use relayed::*;
+pub use rescue::*;
+pub use rewards::*;
+pub use rotate_key::*;
+pub use scheme::*;
+pub use session_key::*;
+pub use snapshot::*;
+pub use spending_limit::*;
+pub use staking::*;
+pub use state_root::*;
+pub use stream::*;
+pub use sub_account::*;
+pub use submission::*;
+pub use treasury::*;
+pub use two_phase::*;
+pub use verifier::*;
+pub use vesting::*;
+pub use whitelist::*;
+pub use wrapped::*;
+
 /// The label for an account.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Account {
-    /// An externally owned account.
-    External(PublicKey),
+    /// An externally owned account, signing with a BLS key.
+    External(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] PublicKey),
     /// A contract account.
-    Contract(ContractId),
+    Contract(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] ContractId),
+    /// An account controlled by a threshold of keys, identified by the id assigned on registration.
+    Multisig(u64),
+    /// An externally owned account signing with a secp256k1 key, such as those of hardware
+    /// wallets.
+    Secp256k1(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Secp256k1PublicKey),
+    /// An externally owned account signing with an Ed25519 key.
+    Ed25519(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Ed25519PublicKey),
+    /// A sub-account of an externally owned account signing with a BLS key, segregating part of
+    /// the tokens controlled by the key under a non-zero index. Sub-account `0` is the account of
+    /// the key itself, so use [`Account::sub_account`] to address sub-accounts.
+    SubAccount(
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] PublicKey,
+        u32,
+    ),
 }
 
 impl Account {
-    fn to_bytes(&self) -> [u8; 194] {
+    /// The size of the byte representation of an account.
+    pub const SIZE: usize = 194;
+
+    const EXTERNAL_TAG: u8 = 0;
+    const CONTRACT_TAG: u8 = 1;
+    const MULTISIG_TAG: u8 = 2;
+    const SECP256K1_TAG: u8 = 3;
+    const ED25519_TAG: u8 = 4;
+    const SUB_ACCOUNT_TAG: u8 = 5;
+
+    /// The sub-account of a BLS key with the given index, which is the account of the key itself
+    /// for index `0`.
+    pub fn sub_account(pk: PublicKey, index: u32) -> Self {
+        match index {
+            0 => Account::External(pk),
+            index => Account::SubAccount(pk, index),
+        }
+    }
+
+    /// The account a sub-account belongs to, or the account itself for any other kind.
+    pub fn root(&self) -> Self {
+        match self {
+            Account::SubAccount(pk, _) => Account::External(*pk),
+            account => *account,
+        }
+    }
+
+    /// The byte representation of the account, as used in signature messages.
+    ///
+    /// The first byte is a tag for the kind of account - `0` for externally owned accounts, `1`
+    /// for contracts, `2` for multisig accounts, `3` and `4` for externally owned accounts with
+    /// secp256k1 and Ed25519 keys, and `5` for sub-accounts. It is followed by the raw bytes of the
+    /// public key, the bytes of the contract id, the id of the multisig account in little-endian,
+    /// the encoded secp256k1 or Ed25519 key, or the compressed public key followed by the index in
+    /// little-endian, respectively, with any remaining bytes set to zero.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+
         match self {
             Account::External(pk) => {
-                let mut bytes = [0u8; 194];
                 let pk_bytes = pk.to_raw_bytes();
 
-                bytes[0] = 0;
+                bytes[0] = Self::EXTERNAL_TAG;
                 bytes[1..].copy_from_slice(&pk_bytes);
-
-                bytes
             }
             Account::Contract(contract) => {
-                let mut bytes = [0u8; 194];
                 let contract_bytes = contract.to_bytes();
 
-                bytes[0] = 1;
+                bytes[0] = Self::CONTRACT_TAG;
                 bytes[1..1 + contract_bytes.len()].copy_from_slice(&contract_bytes);
+            }
+            Account::Multisig(id) => {
+                let id_bytes = id.to_le_bytes();
+
+                bytes[0] = Self::MULTISIG_TAG;
+                bytes[1..1 + id_bytes.len()].copy_from_slice(&id_bytes);
+            }
+            Account::Secp256k1(pk) => {
+                bytes[0] = Self::SECP256K1_TAG;
+                bytes[1..1 + pk.0.len()].copy_from_slice(&pk.0);
+            }
+            Account::Ed25519(pk) => {
+                bytes[0] = Self::ED25519_TAG;
+                bytes[1..1 + pk.0.len()].copy_from_slice(&pk.0);
+            }
+            Account::SubAccount(pk, index) => {
+                let pk_bytes = pk.to_bytes();
+                let index_bytes = index.to_le_bytes();
+
+                bytes[0] = Self::SUB_ACCOUNT_TAG;
+                bytes[1..1 + pk_bytes.len()].copy_from_slice(&pk_bytes);
+                bytes[1 + pk_bytes.len()..1 + pk_bytes.len() + index_bytes.len()]
+                    .copy_from_slice(&index_bytes);
+            }
+        }
+
+        bytes
+    }
+
+    /// Parse an account from its byte representation, as produced by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, AccountBytesError> {
+        let (tag, rest) = (bytes[0], &bytes[1..]);
+
+        match tag {
+            Self::EXTERNAL_TAG => {
+                // SAFETY: the validity of the key is checked right after
+                let pk = unsafe { PublicKey::from_slice_unchecked(rest) };
+                if !pk.is_valid() {
+                    return Err(AccountBytesError::InvalidPublicKey);
+                }
+                Ok(Account::External(pk))
+            }
+            Self::CONTRACT_TAG => {
+                let (contract_bytes, padding) = rest.split_at(32);
+                check_padding(padding)?;
+
+                let mut id = [0u8; 32];
+                id.copy_from_slice(contract_bytes);
+                Ok(Account::Contract(ContractId::from_bytes(id)))
+            }
+            Self::MULTISIG_TAG => {
+                let (id_bytes, padding) = rest.split_at(8);
+                check_padding(padding)?;
 
-                bytes
+                let mut id = [0u8; 8];
+                id.copy_from_slice(id_bytes);
+                Ok(Account::Multisig(u64::from_le_bytes(id)))
             }
+            Self::SECP256K1_TAG => {
+                let (key_bytes, padding) = rest.split_at(Secp256k1PublicKey::SIZE);
+                check_padding(padding)?;
+
+                let mut key = [0u8; Secp256k1PublicKey::SIZE];
+                key.copy_from_slice(key_bytes);
+                let pk = Secp256k1PublicKey(key);
+                if !pk.is_valid() {
+                    return Err(AccountBytesError::InvalidPublicKey);
+                }
+                Ok(Account::Secp256k1(pk))
+            }
+            Self::ED25519_TAG => {
+                let (key_bytes, padding) = rest.split_at(Ed25519PublicKey::SIZE);
+                check_padding(padding)?;
+
+                let mut key = [0u8; Ed25519PublicKey::SIZE];
+                key.copy_from_slice(key_bytes);
+                let pk = Ed25519PublicKey(key);
+                if !pk.is_valid() {
+                    return Err(AccountBytesError::InvalidPublicKey);
+                }
+                Ok(Account::Ed25519(pk))
+            }
+            Self::SUB_ACCOUNT_TAG => {
+                let (pk_bytes, rest) = rest.split_at(PublicKey::SIZE);
+                let (index_bytes, padding) = rest.split_at(4);
+                check_padding(padding)?;
+
+                let mut pk = [0u8; PublicKey::SIZE];
+                pk.copy_from_slice(pk_bytes);
+                let pk =
+                    PublicKey::from_bytes(&pk).map_err(|_| AccountBytesError::InvalidPublicKey)?;
+
+                let mut index = [0u8; 4];
+                index.copy_from_slice(index_bytes);
+                match u32::from_le_bytes(index) {
+                    0 => Err(AccountBytesError::ZeroSubAccount),
+                    index => Ok(Account::SubAccount(pk, index)),
+                }
+            }
+            tag => Err(AccountBytesError::InvalidTag(tag)),
         }
     }
 }
 
+fn check_padding(padding: &[u8]) -> Result<(), AccountBytesError> {
+    if padding.iter().any(|b| *b != 0) {
+        return Err(AccountBytesError::InvalidPadding);
+    }
+    Ok(())
+}
+
+/// Error returned when parsing an [`Account`] from its byte representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountBytesError {
+    /// The tag is not one of a known kind of account.
+    InvalidTag(u8),
+    /// The bytes of an externally owned account are not a valid public key.
+    InvalidPublicKey,
+    /// The bytes following the id of a contract or multisig account, a secp256k1 or Ed25519 key,
+    /// or the index of a sub-account, are not all zero.
+    InvalidPadding,
+    /// The index of a sub-account is zero, which stands for the account of the key itself.
+    ZeroSubAccount,
+}
+
+impl fmt::Display for AccountBytesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTag(tag) => write!(f, "invalid account tag {tag}"),
+            Self::InvalidPublicKey => f.write_str("invalid public key in account bytes"),
+            Self::InvalidPadding => f.write_str("non-zero padding in account bytes"),
+            Self::ZeroSubAccount => f.write_str("zero sub-account index in account bytes"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AccountBytesError {}
+
 impl From<PublicKey> for Account {
     fn from(pk: PublicKey) -> Self {
         Self::External(pk)
     }
 }
 
+impl From<Secp256k1PublicKey> for Account {
+    fn from(pk: Secp256k1PublicKey) -> Self {
+        Self::Secp256k1(pk)
+    }
+}
+
+impl From<Ed25519PublicKey> for Account {
+    fn from(pk: Ed25519PublicKey) -> Self {
+        Self::Ed25519(pk)
+    }
+}
+
 impl From<ContractId> for Account {
     fn from(contract: ContractId) -> Self {
         Self::Contract(contract)
@@ -78,10 +387,31 @@ impl Ord for Account {
                 lhs.cmp(&rhs)
             }
             (Contract(lhs), Contract(rhs)) => lhs.cmp(rhs),
-            // An externally owned account is defined as always "smaller" than a contract account.
-            // This ensures they are never mixed when ordering.
-            (External(_lhs), Contract(_rhs)) => Ordering::Greater,
-            (Contract(_lhs), External(_rhs)) => Ordering::Less,
+            (Multisig(lhs), Multisig(rhs)) => lhs.cmp(rhs),
+            (Secp256k1(lhs), Secp256k1(rhs)) => lhs.cmp(rhs),
+            (Ed25519(lhs), Ed25519(rhs)) => lhs.cmp(rhs),
+            (SubAccount(lhs, lhs_index), SubAccount(rhs, rhs_index)) => {
+                let lhs = lhs.to_raw_bytes();
+                let rhs = rhs.to_raw_bytes();
+                lhs.cmp(&rhs).then(lhs_index.cmp(rhs_index))
+            }
+            // Accounts of different kinds are ordered by kind - contract accounts first, then
+            // multisig accounts, then externally owned accounts by the scheme of their key, and
+            // sub-accounts last. This ensures they are never mixed when ordering.
+            (lhs, rhs) => lhs.kind().cmp(&rhs.kind()),
+        }
+    }
+}
+
+impl Account {
+    fn kind(&self) -> u8 {
+        match self {
+            Account::Contract(_) => 0,
+            Account::Multisig(_) => 1,
+            Account::External(_) => 2,
+            Account::Secp256k1(_) => 3,
+            Account::Ed25519(_) => 4,
+            Account::SubAccount(..) => 5,
         }
     }
 }
@@ -89,11 +419,12 @@ impl Ord for Account {
 /// The data an account has in the contract.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AccountInfo {
     /// The balance of the account.
     pub balance: u64,
-    /// The current nonce of the account. Use the current value +1 to perform an interaction with
-    /// the account.
+    /// The number of nonces the account has used since its entry was last pruned, after it held no
+    /// tokens. Use the `nonce` query for the lowest nonce the account hasn't used yet.
     pub nonce: u64,
 }
 
@@ -105,9 +436,78 @@ impl AccountInfo {
     };
 }
 
+/// Arguments used to initialize the contract.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InitArgs {
+    /// The initial balances of accounts.
+    pub balances: Vec<(Account, u64)>,
+    /// The key allowed to perform administrative operations. If `None`, no such operations can be
+    /// performed.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+    pub admin: Option<PublicKey>,
+    /// The policy followed when the `token_received` hook of a receiving contract fails, and the
+    /// sender didn't choose one.
+    pub hook_policy: HookPolicy,
+    /// The accounts initially allowed to hold tokens. If `Some`, the whitelist mode is active, and
+    /// only whitelisted accounts may send and receive tokens. Requires the `compliance` feature.
+    pub whitelist: Option<Vec<Account>>,
+    /// The fraction of every transfer that is burned, in units of [`BURN_RATE_SCALE`]. Requires the
+    /// `fees` feature if non-zero.
+    pub burn_rate: u16,
+    /// Whether the token wraps native Dusk, only minting tokens on deposits of Dusk and burning
+    /// them on withdrawals. If `true`, there can be no initial balances, burn rate, or emission.
+    pub wrapped: bool,
+    /// The number of transfers kept in the history of each account. If zero, no history is kept.
+    pub history_len: u32,
+    /// The value above which transfers must be proposed and claimed in two phases. If `None`, any
+    /// transfer can be made directly.
+    pub two_phase_threshold: Option<u64>,
+    /// The fee taken on flash loans, in units of [`FLASH_LOAN_FEE_SCALE`]. Fees are paid to the
+    /// token contract's own account.
+    pub flash_loan_fee: u16,
+    /// The number of blocks between announcing a clawback and being able to execute it. If `None`,
+    /// tokens can't be clawed back.
+    pub clawback_delay: Option<u64>,
+    /// The schedule of tokens minted to a treasury, starting at deployment. If `None`, no tokens
+    /// are emitted.
+    pub emission: Option<EmissionSchedule>,
+}
+
+/// The burn rate denoting that all transferred tokens are burned, i.e. a burn rate is expressed in
+/// basis points.
+pub const BURN_RATE_SCALE: u16 = 10_000;
+
+/// What happens to a transfer when the `token_received` hook of the receiving contract fails.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HookPolicy {
+    /// Revert the whole transfer.
+    #[default]
+    Revert,
+    /// Credit the tokens to the receiving contract anyway, and emit a [`HookFailedEvent`].
+    Credit,
+    /// Divert the tokens to a recovery bucket, from which the sender can claim them back, and emit
+    /// a [`HookFailedEvent`].
+    Recover,
+}
+
+// The byte used to represent an optional policy in the signature messages.
+fn hook_policy_byte(policy: Option<HookPolicy>) -> u8 {
+    match policy {
+        None => 0,
+        Some(HookPolicy::Revert) => 1,
+        Some(HookPolicy::Credit) => 2,
+        Some(HookPolicy::Recover) => 3,
+    }
+}
+
 /// Arguments to query for how much of an allowance a spender has of the `owner` account.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Allowance {
     /// The account that owns the tokens.
     pub owner: Account,
@@ -115,41 +515,165 @@ pub struct Allowance {
     pub spender: Account,
 }
 
-/// Data used to transfer tokens from one account to another.
+/// The state of the allowance a spender has of the `owner` account.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AllowanceInfo {
+    /// The value the spender may still spend, as returned by `allowance`.
+    pub value: u64,
+    /// The block height from which the allowance can no longer be used, as set by
+    /// `set_allowance_expiry`, if any.
+    pub expiry_height: Option<u64>,
+    /// The spending limit of the spender, used instead of the allowance if set, with the value
+    /// spent in the current window.
+    pub spending_limit: Option<SpendingLimit>,
+}
+
+/// A reference attached to a transfer and surfaced in its [`TransferEvent`], such as the id of a
+/// user depositing to an exchange.
+pub type Memo = [u8; 32];
+
+// The bytes used to represent an optional memo in the signature messages, with an empty field
+// standing for no memo.
+fn memo_bytes(memo: &Option<Memo>) -> &[u8] {
+    memo.as_ref().map(|memo| &memo[..]).unwrap_or(&[])
+}
+
+/// Data used to transfer tokens from one account to another.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transfer {
-    from: PublicKey,
+    version: u8,
+    from: SignerKey,
     to: Account,
     value: u64,
     nonce: u64,
-    signature: Signature,
+    hook_policy: Option<HookPolicy>,
+    data: Vec<u8>,
+    memo: Option<Memo>,
+    idempotency_id: Option<u64>,
+    signature: SchemeSignature,
 }
 
 impl Transfer {
-    const SIGNATURE_MSG_SIZE: usize = 193 + 194 + 8 + 8;
+    const NAME: &'static str = "transfer";
 
     /// Create a new transfer.
     pub fn new(from_sk: &SecretKey, to: impl Into<Account>, value: u64, nonce: u64) -> Self {
-        let from = PublicKey::from(from_sk);
+        Self::new_inner(from_sk, to.into(), value, nonce, None, Vec::new(), None)
+    }
+
+    /// Create a new transfer, choosing what happens should the receiving contract's
+    /// `token_received` hook fail. If `None`, the policy chosen by the deployment is used.
+    pub fn new_with_hook_policy(
+        from_sk: &SecretKey,
+        to: impl Into<Account>,
+        value: u64,
+        nonce: u64,
+        hook_policy: Option<HookPolicy>,
+    ) -> Self {
+        Self::new_inner(
+            from_sk,
+            to.into(),
+            value,
+            nonce,
+            hook_policy,
+            Vec::new(),
+            None,
+        )
+    }
+
+    /// Create a new transfer, carrying data passed on to the receiving contract's `token_received`
+    /// hook.
+    pub fn new_with_data(
+        from_sk: &SecretKey,
+        to: impl Into<Account>,
+        value: u64,
+        nonce: u64,
+        data: Vec<u8>,
+    ) -> Self {
+        Self::new_inner(from_sk, to.into(), value, nonce, None, data, None)
+    }
+
+    /// Create a new transfer, carrying a memo surfaced in its [`TransferEvent`].
+    pub fn new_with_memo(
+        from_sk: &SecretKey,
+        to: impl Into<Account>,
+        value: u64,
+        nonce: u64,
+        memo: Memo,
+    ) -> Self {
+        Self::new_inner(
+            from_sk,
+            to.into(),
+            value,
+            nonce,
+            None,
+            Vec::new(),
+            Some(memo),
+        )
+    }
+
+    /// Create a transfer from its parts, with a signature produced elsewhere - such as by a
+    /// hardware wallet or a remote signer. The signature should be over the
+    /// [`signature_message`](Self::signature_message) of a transfer with the same parts, and can be
+    /// checked using [`verify`](Self::verify).
+    pub fn from_parts(
+        from: impl Into<SignerKey>,
+        to: impl Into<Account>,
+        value: u64,
+        nonce: u64,
+        signature: impl Into<SchemeSignature>,
+    ) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+            value,
+            nonce,
+            hook_policy: None,
+            data: Vec::new(),
+            memo: None,
+            idempotency_id: None,
+            version: MESSAGE_VERSION,
+            signature: signature.into(),
+        }
+    }
+
+    fn new_inner(
+        from_sk: &SecretKey,
+        to: Account,
+        value: u64,
+        nonce: u64,
+        hook_policy: Option<HookPolicy>,
+        data: Vec<u8>,
+        memo: Option<Memo>,
+    ) -> Self {
+        let from = SignerKey::Bls(PublicKey::from(from_sk));
 
         let mut transfer = Self {
             from,
-            to: to.into(),
+            to,
             value,
             nonce,
-            signature: Signature::default(),
+            hook_policy,
+            data,
+            memo,
+            idempotency_id: None,
+            version: MESSAGE_VERSION,
+            signature: SchemeSignature::Bls(Signature::default()),
         };
 
         let sig_msg = transfer.signature_message();
         let sig = from_sk.sign(&sig_msg);
-        transfer.signature = sig;
+        transfer.signature = SchemeSignature::Bls(sig);
 
         transfer
     }
 
-    /// The account to transfer from.
-    pub fn from(&self) -> &PublicKey {
+    /// The key of the account to transfer from.
+    pub fn from(&self) -> &SignerKey {
         &self.from
     }
 
@@ -168,51 +692,91 @@ impl Transfer {
         self.nonce
     }
 
-    /// The signature used for the transfer.
-    pub fn signature(&self) -> &Signature {
-        &self.signature
+    /// The policy chosen for when the receiving contract's hook fails, if any.
+    pub fn hook_policy(&self) -> Option<HookPolicy> {
+        self.hook_policy
     }
 
-    /// The message to be signed over.
-    pub fn signature_message(&self) -> [u8; Self::SIGNATURE_MSG_SIZE] {
-        let mut msg = [0u8; Self::SIGNATURE_MSG_SIZE];
+    /// The data passed on to the receiving contract.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
 
-        let mut offset = 0;
+    /// The memo surfaced in the transfer event, if any.
+    pub fn memo(&self) -> Option<&Memo> {
+        self.memo.as_ref()
+    }
 
-        let bytes = self.from.to_raw_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        offset += bytes.len();
+    /// The id identifying the submission of the transfer, if any.
+    pub fn idempotency_id(&self) -> Option<u64> {
+        self.idempotency_id
+    }
 
-        let bytes = self.to.to_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        offset += bytes.len();
+    /// The version of the message format the payload was signed with.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// The signature used for the transfer.
+    pub fn signature(&self) -> &SchemeSignature {
+        &self.signature
+    }
 
-        let bytes = self.value.to_le_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        offset += bytes.len();
+    /// Set the signature, produced elsewhere.
+    pub fn set_signature(&mut self, signature: impl Into<SchemeSignature>) {
+        self.signature = signature.into();
+    }
 
-        let bytes = self.nonce.to_le_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        // offset += bytes.len();
+    /// Whether the signature is valid for the account transferring.
+    pub fn verify(&self) -> bool {
+        let msg = self.signature_message();
+        self.from.verify(&self.signature, &msg)
+    }
 
-        msg
+    /// The message to be signed over.
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut msg = Message::new(self.version, Self::NAME);
+
+        msg.field(&self.from.to_message_bytes());
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.nonce.to_le_bytes());
+        msg.field(&[hook_policy_byte(self.hook_policy)]);
+        msg.field(&self.data);
+        msg.field(memo_bytes(&self.memo));
+        // an empty field stands for no idempotency id
+        let idempotency_id = self.idempotency_id.map(u64::to_le_bytes);
+        msg.field(
+            idempotency_id
+                .as_ref()
+                .map(|bytes| &bytes[..])
+                .unwrap_or(&[]),
+        );
+
+        msg.into_bytes()
     }
 }
 
 /// Data used to transfer tokens from an owner to a recipient, by an allowed party.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransferFrom {
-    spender: PublicKey,
+    version: u8,
+    spender: SignerKey,
     owner: Account,
     to: Account,
     value: u64,
     nonce: u64,
-    signature: Signature,
+    hook_policy: Option<HookPolicy>,
+    data: Vec<u8>,
+    memo: Option<Memo>,
+    idempotency_id: Option<u64>,
+    signature: SchemeSignature,
 }
 
 impl TransferFrom {
-    const SIGNATURE_MSG_SIZE: usize = 193 + 194 + 194 + 8 + 8;
+    const NAME: &'static str = "transfer_from";
 
     /// Create a new transfer, spending tokens from the `owner`.
     pub fn new(
@@ -222,26 +786,148 @@ impl TransferFrom {
         value: u64,
         nonce: u64,
     ) -> Self {
-        let spender = PublicKey::from(spender_sk);
+        Self::new_inner(
+            spender_sk,
+            owner.into(),
+            to.into(),
+            value,
+            nonce,
+            None,
+            Vec::new(),
+            None,
+        )
+    }
 
-        let mut transfer_from = Self {
-            spender,
+    /// Create a new transfer, spending tokens from the `owner`, and choosing what happens should
+    /// the receiving contract's `token_received` hook fail. If `None`, the policy chosen by the
+    /// deployment is used.
+    pub fn new_with_hook_policy(
+        spender_sk: &SecretKey,
+        owner: impl Into<Account>,
+        to: impl Into<Account>,
+        value: u64,
+        nonce: u64,
+        hook_policy: Option<HookPolicy>,
+    ) -> Self {
+        Self::new_inner(
+            spender_sk,
+            owner.into(),
+            to.into(),
+            value,
+            nonce,
+            hook_policy,
+            Vec::new(),
+            None,
+        )
+    }
+
+    /// Create a new transfer, spending tokens from the `owner`, and carrying data passed on to the
+    /// receiving contract's `token_received` hook.
+    pub fn new_with_data(
+        spender_sk: &SecretKey,
+        owner: impl Into<Account>,
+        to: impl Into<Account>,
+        value: u64,
+        nonce: u64,
+        data: Vec<u8>,
+    ) -> Self {
+        Self::new_inner(
+            spender_sk,
+            owner.into(),
+            to.into(),
+            value,
+            nonce,
+            None,
+            data,
+            None,
+        )
+    }
+
+    /// Create a new transfer, spending tokens from the `owner`, and carrying a memo surfaced in its
+    /// [`TransferEvent`].
+    pub fn new_with_memo(
+        spender_sk: &SecretKey,
+        owner: impl Into<Account>,
+        to: impl Into<Account>,
+        value: u64,
+        nonce: u64,
+        memo: Memo,
+    ) -> Self {
+        Self::new_inner(
+            spender_sk,
+            owner.into(),
+            to.into(),
+            value,
+            nonce,
+            None,
+            Vec::new(),
+            Some(memo),
+        )
+    }
+
+    /// Create a transfer from its parts, with a signature produced elsewhere - such as by a
+    /// hardware wallet or a remote signer. The signature should be over the
+    /// [`signature_message`](Self::signature_message) of a transfer with the same parts, and can be
+    /// checked using [`verify`](Self::verify).
+    pub fn from_parts(
+        spender: impl Into<SignerKey>,
+        owner: impl Into<Account>,
+        to: impl Into<Account>,
+        value: u64,
+        nonce: u64,
+        signature: impl Into<SchemeSignature>,
+    ) -> Self {
+        Self {
+            spender: spender.into(),
             owner: owner.into(),
             to: to.into(),
             value,
             nonce,
-            signature: Signature::default(),
+            hook_policy: None,
+            data: Vec::new(),
+            memo: None,
+            idempotency_id: None,
+            version: MESSAGE_VERSION,
+            signature: signature.into(),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_inner(
+        spender_sk: &SecretKey,
+        owner: Account,
+        to: Account,
+        value: u64,
+        nonce: u64,
+        hook_policy: Option<HookPolicy>,
+        data: Vec<u8>,
+        memo: Option<Memo>,
+    ) -> Self {
+        let spender = SignerKey::Bls(PublicKey::from(spender_sk));
+
+        let mut transfer_from = Self {
+            spender,
+            owner,
+            to,
+            value,
+            nonce,
+            hook_policy,
+            data,
+            memo,
+            idempotency_id: None,
+            version: MESSAGE_VERSION,
+            signature: SchemeSignature::Bls(Signature::default()),
         };
 
         let sig_msg = transfer_from.signature_message();
         let sig = spender_sk.sign(&sig_msg);
-        transfer_from.signature = sig;
+        transfer_from.signature = SchemeSignature::Bls(sig);
 
         transfer_from
     }
 
-    /// The account spending the tokens.
-    pub fn spender(&self) -> &PublicKey {
+    /// The key of the account spending the tokens.
+    pub fn spender(&self) -> &SignerKey {
         &self.spender
     }
 
@@ -265,38 +951,69 @@ impl TransferFrom {
         self.nonce
     }
 
-    /// The signature used for the transfer.
-    pub fn signature(&self) -> &Signature {
-        &self.signature
+    /// The policy chosen for when the receiving contract's hook fails, if any.
+    pub fn hook_policy(&self) -> Option<HookPolicy> {
+        self.hook_policy
     }
 
-    /// The message to be signed over.
-    pub fn signature_message(&self) -> [u8; Self::SIGNATURE_MSG_SIZE] {
-        let mut msg = [0u8; Self::SIGNATURE_MSG_SIZE];
+    /// The data passed on to the receiving contract.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
 
-        let mut offset = 0;
+    /// The memo surfaced in the transfer event, if any.
+    pub fn memo(&self) -> Option<&Memo> {
+        self.memo.as_ref()
+    }
 
-        let bytes = self.spender.to_raw_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        offset += bytes.len();
+    /// The id identifying the submission of the transfer, if any.
+    pub fn idempotency_id(&self) -> Option<u64> {
+        self.idempotency_id
+    }
 
-        let bytes = self.owner.to_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        offset += bytes.len();
+    /// The version of the message format the payload was signed with.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
 
-        let bytes = self.to.to_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        offset += bytes.len();
+    /// The signature used for the transfer.
+    pub fn signature(&self) -> &SchemeSignature {
+        &self.signature
+    }
 
-        let bytes = self.value.to_le_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        offset += bytes.len();
+    /// Set the signature, produced elsewhere.
+    pub fn set_signature(&mut self, signature: impl Into<SchemeSignature>) {
+        self.signature = signature.into();
+    }
 
-        let bytes = self.nonce.to_le_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        // offset += bytes.len();
+    /// Whether the signature is valid for the spender.
+    pub fn verify(&self) -> bool {
+        let msg = self.signature_message();
+        self.spender.verify(&self.signature, &msg)
+    }
 
-        msg
+    /// The message to be signed over.
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut msg = Message::new(self.version, Self::NAME);
+
+        msg.field(&self.spender.to_message_bytes());
+        msg.field(&self.owner.to_bytes());
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.nonce.to_le_bytes());
+        msg.field(&[hook_policy_byte(self.hook_policy)]);
+        msg.field(&self.data);
+        msg.field(memo_bytes(&self.memo));
+        // an empty field stands for no idempotency id
+        let idempotency_id = self.idempotency_id.map(u64::to_le_bytes);
+        msg.field(
+            idempotency_id
+                .as_ref()
+                .map(|bytes| &bytes[..])
+                .unwrap_or(&[]),
+        );
+
+        msg.into_bytes()
     }
 }
 
@@ -304,8 +1021,9 @@ impl TransferFrom {
 ///
 /// Note that there is no need for a signature, since contracts are essentially asserting via their
 /// code that they wish the transaction to be made.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransferFromContract {
     /// The account to transfer to.
     pub to: Account,
@@ -313,43 +1031,112 @@ pub struct TransferFromContract {
     pub from: Option<Account>,
     /// The value to transfer.
     pub value: u64,
+    /// The policy followed should the receiving contract's hook fail. If `None`, the policy chosen
+    /// by the deployment is used.
+    pub hook_policy: Option<HookPolicy>,
+    /// A key identifying the transfer, chosen by the calling contract. If set, the transfer is
+    /// rejected with [`TransferFromContractError::DuplicateIdempotencyKey`] if the calling contract
+    /// already used the key, making it safe to retry.
+    pub idempotency_key: Option<u64>,
+    /// Data passed on to the receiving contract's `token_received` hook.
+    pub data: Vec<u8>,
+}
+
+/// Errors returned by `transfer_from_contract`.
+///
+/// Other failures cause the call to panic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransferFromContractError {
+    /// The idempotency key was already used by the calling contract.
+    DuplicateIdempotencyKey(u64),
+}
+
+/// Arguments to query whether a contract has used an idempotency key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdempotencyKey {
+    /// The contract using the key.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub contract: ContractId,
+    /// The key.
+    pub key: u64,
+}
+
+/// Data used by a contract to approve spending tokens from its account.
+///
+/// As with [`TransferFromContract`], there is no need for a signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApproveFromContract {
+    /// The account to allow spending tokens from the contract.
+    pub spender: Account,
+    /// The value to approve the transfer of.
+    pub value: u64,
 }
 
 /// Data used to approve spending tokens from a user's account.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Approve {
-    owner: PublicKey,
+    version: u8,
+    owner: SignerKey,
     spender: Account,
     value: u64,
     nonce: u64,
-    signature: Signature,
+    signature: SchemeSignature,
 }
 
 impl Approve {
-    const SIGNATURE_MSG_SIZE: usize = 193 + 194 + 8 + 8;
+    const NAME: &'static str = "approve";
 
     /// Create a new approval.
     pub fn new(owner_sk: &SecretKey, spender: impl Into<Account>, value: u64, nonce: u64) -> Self {
-        let owner = PublicKey::from(owner_sk);
+        let owner = SignerKey::Bls(PublicKey::from(owner_sk));
 
         let mut approve = Self {
             owner,
             spender: spender.into(),
             value,
             nonce,
-            signature: Signature::default(),
+            version: MESSAGE_VERSION,
+            signature: SchemeSignature::Bls(Signature::default()),
         };
 
         let sig_msg = approve.signature_message();
         let sig = owner_sk.sign(&sig_msg);
-        approve.signature = sig;
+        approve.signature = SchemeSignature::Bls(sig);
 
         approve
     }
 
-    /// The account to allow the transfer of tokens.
-    pub fn owner(&self) -> &PublicKey {
+    /// Create an approval from its parts, with a signature produced elsewhere - such as by a
+    /// hardware wallet or a remote signer. The signature should be over the
+    /// [`signature_message`](Self::signature_message) of an approval with the same parts, and can
+    /// be checked using [`verify`](Self::verify).
+    pub fn from_parts(
+        owner: impl Into<SignerKey>,
+        spender: impl Into<Account>,
+        value: u64,
+        nonce: u64,
+        signature: impl Into<SchemeSignature>,
+    ) -> Self {
+        Self {
+            owner: owner.into(),
+            spender: spender.into(),
+            value,
+            nonce,
+            version: MESSAGE_VERSION,
+            signature: signature.into(),
+        }
+    }
+
+    /// The key of the account to allow the transfer of tokens.
+    pub fn owner(&self) -> &SignerKey {
         &self.owner
     }
 
@@ -368,54 +1155,222 @@ impl Approve {
         self.nonce
     }
 
+    /// The version of the message format the payload was signed with.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
     /// The signature used for the allowance.
-    pub fn signature(&self) -> &Signature {
+    pub fn signature(&self) -> &SchemeSignature {
         &self.signature
     }
 
+    /// Set the signature, produced elsewhere.
+    pub fn set_signature(&mut self, signature: impl Into<SchemeSignature>) {
+        self.signature = signature.into();
+    }
+
+    /// Whether the signature is valid for the account approving.
+    pub fn verify(&self) -> bool {
+        let msg = self.signature_message();
+        self.owner.verify(&self.signature, &msg)
+    }
+
     /// The message to be signed over.
-    pub fn signature_message(&self) -> [u8; Self::SIGNATURE_MSG_SIZE] {
-        let mut msg = [0u8; Self::SIGNATURE_MSG_SIZE];
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut msg = Message::new(self.version, Self::NAME);
 
-        let mut offset = 0;
+        msg.field(&self.owner.to_message_bytes());
+        msg.field(&self.spender.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.nonce.to_le_bytes());
 
-        let bytes = self.owner.to_raw_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        offset += bytes.len();
+        msg.into_bytes()
+    }
+}
+
+/// An operation an externally owned account can sign over.
+pub trait Operation {
+    /// The name of the operation. It is included in the signature message, ensuring a signature
+    /// over one operation can't be used for another.
+    const NAME: &'static str;
+
+    /// Write the fields of the operation to the signature message.
+    fn write_message(&self, msg: &mut Message);
+}
+
+/// An operation signed by an externally owned account, using any of the supported
+/// [`SignatureScheme`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Signed<T> {
+    version: u8,
+    signer: SignerKey,
+    op: T,
+    nonce: u64,
+    signature: SchemeSignature,
+}
+
+impl<T: Operation> Signed<T> {
+    /// Sign a new operation.
+    pub fn new(signer_sk: &SecretKey, op: T, nonce: u64) -> Self {
+        let signer = PublicKey::from(signer_sk);
+
+        let mut signed = Self {
+            signer: SignerKey::Bls(signer),
+            op,
+            nonce,
+            version: MESSAGE_VERSION,
+            signature: SchemeSignature::Bls(Signature::default()),
+        };
+
+        let sig_msg = signed.signature_message();
+        let sig = signer_sk.sign(&sig_msg);
+        signed.signature = SchemeSignature::Bls(sig);
+
+        signed
+    }
+
+    /// Create a signed operation from its parts, with a signature produced elsewhere - such as by
+    /// a hardware wallet or a remote signer. The signature can be checked using
+    /// [`verify`](Self::verify).
+    pub fn from_parts(signer: PublicKey, op: T, nonce: u64, signature: Signature) -> Self {
+        Self::from_scheme_parts(signer, op, nonce, signature)
+    }
+
+    /// Create a signed operation from its parts, with a key and signature of any of the supported
+    /// schemes, such as a secp256k1 key of a hardware wallet. The signature should be over the
+    /// [`signature_message`](Self::signature_message) of an operation with the same parts, which
+    /// doesn't depend on the signature, so it can be set afterwards using
+    /// [`set_signature`](Self::set_signature).
+    pub fn from_scheme_parts(
+        signer: impl Into<SignerKey>,
+        op: T,
+        nonce: u64,
+        signature: impl Into<SchemeSignature>,
+    ) -> Self {
+        Self {
+            signer: signer.into(),
+            op,
+            nonce,
+            version: MESSAGE_VERSION,
+            signature: signature.into(),
+        }
+    }
 
-        let bytes = self.spender.to_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        offset += bytes.len();
+    /// Set the signature over the operation, produced elsewhere.
+    pub fn set_signature(&mut self, signature: impl Into<SchemeSignature>) {
+        self.signature = signature.into();
+    }
 
-        let bytes = self.value.to_le_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        offset += bytes.len();
+    /// The key signing the operation.
+    pub fn signer(&self) -> &SignerKey {
+        &self.signer
+    }
 
-        let bytes = self.nonce.to_le_bytes();
-        msg[offset..][..bytes.len()].copy_from_slice(&bytes);
-        // offset += bytes.len();
+    /// The account signing the operation.
+    pub fn signer_account(&self) -> Account {
+        self.signer.account()
+    }
+
+    /// The operation signed over.
+    pub fn op(&self) -> &T {
+        &self.op
+    }
+
+    /// The nonce used to sign the operation.
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// The version of the message format the payload was signed with.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// The signature over the operation.
+    pub fn signature(&self) -> &SchemeSignature {
+        &self.signature
+    }
 
-        msg
+    /// Whether the signature is valid for the signer, and made with the scheme of its key.
+    pub fn verify(&self) -> bool {
+        let msg = self.signature_message();
+        self.signer.verify(&self.signature, &msg)
     }
+
+    /// The message to be signed over. The signer's key is encoded as given by
+    /// [`SignerKey::to_message_bytes`], so the scheme is part of the message.
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut msg = Message::new(self.version, T::NAME);
+
+        msg.field(&self.signer.to_message_bytes());
+        self.op.write_message(&mut msg);
+        msg.field(&self.nonce.to_le_bytes());
+
+        msg.into_bytes()
+    }
+}
+
+/// Claim tokens diverted to the recovery bucket of the signer, due to the failure of a receiving
+/// contract's hook. The claimed tokens are credited back to the signer's balance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClaimRecovery {
+    /// The value to claim.
+    pub value: u64,
+}
+
+impl Operation for ClaimRecovery {
+    const NAME: &'static str = "claim_recovery";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Used to inform a contract that tokens were transferred from its account by an approved spender.
+///
+/// Only sent to contracts that opted in using `register_token_sent_hook`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferSentInfo {
+    /// The account that spent the tokens.
+    pub spender: Account,
+    /// The account the tokens were transferred to.
+    pub to: Account,
+    /// The number of tokens transferred.
+    pub value: u64,
 }
 
 /// Event emitted when tokens are transferred from one account to another.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransferEvent {
     /// The account tokens are transferred from.
     pub owner: Account,
-    /// The account spending the tokens, set if `transfer_from` is used.
+    /// The account spending the tokens, set if `transfer_from` is used, or the transfer is signed
+    /// by a session key of the owner.
     pub spender: Option<Account>,
     /// The account receiving the tokens.
     pub to: Account,
-    /// The value transferred.
+    /// The value transferred, including any burned tokens.
     pub value: u64,
+    /// The part of the value burned, and therefore not received. The value received is `value -
+    /// burned`.
+    pub burned: u64,
+    /// The memo the transfer was signed with, if any.
+    pub memo: Option<Memo>,
 }
 
 /// Event emitted when a spender is approved on an account.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ApproveEvent {
     /// The account allowing the transfer.
     pub owner: Account,
@@ -426,11 +1381,42 @@ pub struct ApproveEvent {
 }
 
 /// Used to inform a contract of the source of funds they're receiving.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
 #[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransferInfo {
     /// The originating account of the funds transferred to the contract.
     pub from: Account,
     /// The number of tokens transferred.
     pub value: u64,
+    /// Data passed on by the sender.
+    pub data: Vec<u8>,
+}
+
+/// Event emitted when the `token_received` hook of a receiving contract fails, and the transfer is
+/// not reverted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HookFailedEvent {
+    /// The account the tokens were transferred from.
+    pub from: Account,
+    /// The contract whose hook failed.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub to: ContractId,
+    /// The value transferred.
+    pub value: u64,
+    /// The policy applied to the transfer.
+    pub policy: HookPolicy,
+}
+
+/// Event emitted when tokens are claimed from a recovery bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecoveryClaimedEvent {
+    /// The account claiming the tokens.
+    pub owner: Account,
+    /// The value claimed.
+    pub value: u64,
 }
@@ -0,0 +1,107 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Transfer tokens to an account, holding them in the contract until a given block height. Before
+/// then, the signer can cancel the transfer, and from then on the recipient can claim it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferLocked {
+    /// The account to transfer to.
+    pub to: Account,
+    /// The value to transfer.
+    pub value: u64,
+    /// The block height from which the recipient can claim the tokens.
+    pub release_height: u64,
+}
+
+impl Operation for TransferLocked {
+    const NAME: &'static str = "transfer_locked";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.release_height.to_le_bytes());
+    }
+}
+
+/// Claim a locked transfer made to the signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClaimLocked {
+    /// The id of the locked transfer.
+    pub id: u64,
+}
+
+impl Operation for ClaimLocked {
+    const NAME: &'static str = "claim_locked";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.id.to_le_bytes());
+    }
+}
+
+/// Cancel a locked transfer made by the signer, returning the tokens to them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CancelLocked {
+    /// The id of the locked transfer.
+    pub id: u64,
+}
+
+impl Operation for CancelLocked {
+    const NAME: &'static str = "cancel_locked";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.id.to_le_bytes());
+    }
+}
+
+/// A transfer whose tokens are held by the contract until its release height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LockedTransfer {
+    /// The account the tokens are transferred from.
+    pub from: Account,
+    /// The account the tokens are transferred to.
+    pub to: Account,
+    /// The value transferred.
+    pub value: u64,
+    /// The block height from which the recipient can claim the tokens.
+    pub release_height: u64,
+}
+
+/// Event emitted when a locked transfer is made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferLockedEvent {
+    /// The id of the locked transfer.
+    pub id: u64,
+    /// The account the tokens are transferred from.
+    pub from: Account,
+    /// The account the tokens are transferred to.
+    pub to: Account,
+    /// The value transferred.
+    pub value: u64,
+    /// The block height from which the recipient can claim the tokens.
+    pub release_height: u64,
+}
+
+/// Event emitted when a locked transfer is claimed by its recipient, or cancelled by its sender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LockedTransferSettledEvent {
+    /// The id of the locked transfer.
+    pub id: u64,
+    /// The account the tokens were credited to.
+    pub to: Account,
+    /// The value credited.
+    pub value: u64,
+}
@@ -0,0 +1,48 @@
+use alloc::vec::Vec;
+
+/// The version of the signature message format produced by this crate. Payloads carry the version
+/// they were signed with, and contracts reject any version they don't support.
+///
+/// - `1`: the initial format.
+/// - `2`: transfers sign over their memo.
+/// - `3`: transfers sign over their idempotency id.
+pub const MESSAGE_VERSION: u8 = 3;
+
+/// A signature message, as signed over by accounts.
+///
+/// A message starts with a single byte holding the version of the format, followed by a series of
+/// fields. The first field is the name of the payload, ensuring a signature over one kind of
+/// payload can't be used for another, and the rest are the fields of the payload in order. Every
+/// field is prefixed by its length, as a 4-byte little-endian integer, so a message can be
+/// reproduced without knowing the size of each field in advance.
+///
+/// Integers are encoded in little-endian, accounts using their 194-byte representation, and public
+/// keys using their 193-byte raw representation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    bytes: Vec<u8>,
+}
+
+impl Message {
+    /// Start a message for the payload with the given name.
+    pub fn new(version: u8, name: &str) -> Self {
+        let mut msg = Self {
+            bytes: Vec::from([version]),
+        };
+        msg.field(name.as_bytes());
+        msg
+    }
+
+    /// Append a field to the message.
+    pub fn field(&mut self, bytes: &[u8]) -> &mut Self {
+        self.bytes
+            .extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        self.bytes.extend_from_slice(bytes);
+        self
+    }
+
+    /// The bytes of the message.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
@@ -0,0 +1,63 @@
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Message, Operation};
+
+/// The maximum size of a chunk of the serialized state, keeping it well within the size of the
+/// argument buffer of a contract call.
+pub const STATE_CHUNK_SIZE: usize = 32 * 1024;
+
+/// Import a chunk of the state exported by a previous deployment of the contract, using
+/// `state_chunk`. Chunks must be imported in order, starting from index zero, and once the last
+/// chunk is imported the state is transformed from the given version, and replaces the current
+/// state. A deployment can only be migrated to once. Only the admin may sign this operation.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Migrate {
+    /// The version of the state being imported, as returned by `state_version`.
+    pub version: u32,
+    /// The index of the chunk, as passed to `state_chunk`.
+    pub index: u32,
+    /// The chunk of the serialized state.
+    pub chunk: Vec<u8>,
+    /// Whether this is the last chunk.
+    pub last: bool,
+}
+
+impl Operation for Migrate {
+    const NAME: &'static str = "migrate";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.version.to_le_bytes());
+        msg.field(&self.index.to_le_bytes());
+        msg.field(&self.chunk);
+        msg.field(&[self.last as u8]);
+    }
+}
+
+/// Discard the chunks imported so far by an unfinished migration, so that it can be started over.
+/// Only the admin may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbortMigration;
+
+impl Operation for AbortMigration {
+    const NAME: &'static str = "abort_migration";
+
+    fn write_message(&self, _msg: &mut Message) {}
+}
+
+/// Event emitted when a migration is completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MigratedEvent {
+    /// The version of the state imported.
+    pub from_version: u32,
+    /// The version of the state after the migration.
+    pub to_version: u32,
+}
@@ -0,0 +1,31 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Message, Operation};
+
+/// Set the minimum value of a transfer, protecting the state against dust. Transfers emptying the
+/// sending account are exempt. Only the admin may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetMinTransfer {
+    /// The new minimum value, with zero allowing transfers of any value.
+    pub min_transfer: u64,
+}
+
+impl Operation for SetMinTransfer {
+    const NAME: &'static str = "set_min_transfer";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.min_transfer.to_le_bytes());
+    }
+}
+
+/// Event emitted when the minimum value of a transfer is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinTransferEvent {
+    /// The new minimum value.
+    pub min_transfer: u64,
+}
@@ -0,0 +1,319 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, HookPolicy, Message, Operation};
+
+/// The id of one of the fungible tokens issued by a deployment.
+pub type TokenId = u64;
+
+/// The id of the token every deployment issues, whose balances are the ones queried and moved by
+/// the single-token functions - `account`, `transfer`, `approve`, and so on.
+pub const DEFAULT_TOKEN_ID: TokenId = 0;
+
+/// The descriptive metadata of a token.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenMetadata {
+    /// The name of the token.
+    pub name: String,
+    /// The symbol of the token.
+    pub symbol: String,
+    /// The number of decimals used to display amounts of the token.
+    pub decimals: u8,
+}
+
+/// The state of a token issued by a deployment.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenInfo {
+    /// The metadata of the token.
+    pub metadata: TokenMetadata,
+    /// The maximum supply of the token, if any.
+    pub cap: Option<u64>,
+    /// The current supply of the token.
+    pub supply: u64,
+}
+
+/// Create a new token, with no supply. Only the admin may sign this operation.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateToken {
+    /// The id of the token, which must not be in use.
+    pub id: TokenId,
+    /// The metadata of the token.
+    pub metadata: TokenMetadata,
+    /// The maximum supply of the token, if any.
+    pub cap: Option<u64>,
+}
+
+impl Operation for CreateToken {
+    const NAME: &'static str = "create_token";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.id.to_le_bytes());
+        msg.field(self.metadata.name.as_bytes());
+        msg.field(self.metadata.symbol.as_bytes());
+        msg.field(&[self.metadata.decimals]);
+        // an empty field stands for an uncapped token
+        let cap = self.cap.map(u64::to_le_bytes);
+        msg.field(cap.as_ref().map(|bytes| &bytes[..]).unwrap_or(&[]));
+    }
+}
+
+/// Mint tokens of a created token to an account, up to its cap. Only the admin may sign this
+/// operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MintToken {
+    /// The token to mint.
+    pub token: TokenId,
+    /// The account to credit.
+    pub to: Account,
+    /// The value to mint.
+    pub value: u64,
+}
+
+impl Operation for MintToken {
+    const NAME: &'static str = "mint_token";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.token.to_le_bytes());
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Transfer tokens of the given token from the signer's account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenTransfer {
+    /// The token to transfer.
+    pub token: TokenId,
+    /// The account to transfer to.
+    pub to: Account,
+    /// The value to transfer.
+    pub value: u64,
+}
+
+impl Operation for TokenTransfer {
+    const NAME: &'static str = "token_transfer";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.token.to_le_bytes());
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Transfer tokens of the given token from an owner, spending the signer's allowance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenTransferFrom {
+    /// The token to transfer.
+    pub token: TokenId,
+    /// The account to transfer from.
+    pub owner: Account,
+    /// The account to transfer to.
+    pub to: Account,
+    /// The value to transfer.
+    pub value: u64,
+}
+
+impl Operation for TokenTransferFrom {
+    const NAME: &'static str = "token_transfer_from";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.token.to_le_bytes());
+        msg.field(&self.owner.to_bytes());
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Allow a spender to use tokens of the given token from the signer's account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenApprove {
+    /// The token to approve.
+    pub token: TokenId,
+    /// The account allowed to spend the tokens.
+    pub spender: Account,
+    /// The value the spender is allowed to spend.
+    pub value: u64,
+}
+
+impl Operation for TokenApprove {
+    const NAME: &'static str = "token_approve";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.token.to_le_bytes());
+        msg.field(&self.spender.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Transfer tokens of the given token from a contract, or from an owner that allowed the contract
+/// to spend its tokens.
+///
+/// As with [`TransferFromContract`](crate::TransferFromContract), no signature is needed, since the
+/// calling contract is asserting via its code that it wishes the transfer to be made.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenTransferFromContract {
+    /// The token to transfer.
+    pub token: TokenId,
+    /// The account to transfer to.
+    pub to: Account,
+    /// The owner of the tokens to transfer. If `None` it is assumed to be the contract itself.
+    pub from: Option<Account>,
+    /// The value to transfer.
+    pub value: u64,
+    /// The policy followed should the receiving contract's hook fail. If `None`, the policy chosen
+    /// by the deployment is used.
+    pub hook_policy: Option<HookPolicy>,
+    /// Data passed on to the receiving contract's hook.
+    pub data: Vec<u8>,
+}
+
+/// Burn tokens of the given token from the signer's balance, removing them from its supply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenBurn {
+    /// The token to burn.
+    pub token: TokenId,
+    /// The value to burn.
+    pub value: u64,
+}
+
+impl Operation for TokenBurn {
+    const NAME: &'static str = "token_burn";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.token.to_le_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// The argument of the `multi_token_received` hook, called on a contract receiving tokens of a
+/// created token. Tokens of the default token are announced by `token_received` instead.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenTransferInfo {
+    /// The token transferred.
+    pub token: TokenId,
+    /// The originating account of the tokens transferred to the contract.
+    pub from: Account,
+    /// The value transferred.
+    pub value: u64,
+    /// Data passed on by the sender.
+    pub data: Vec<u8>,
+}
+
+/// Used to query the balance of an account in a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenBalance {
+    /// The token.
+    pub token: TokenId,
+    /// The account.
+    pub account: Account,
+}
+
+/// Used to query the allowance a spender has of an owner's tokens of a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenAllowance {
+    /// The token.
+    pub token: TokenId,
+    /// The account that owns the tokens.
+    pub owner: Account,
+    /// The account allowed to spend the `owner`s tokens.
+    pub spender: Account,
+}
+
+/// Event emitted when a token is created.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenCreatedEvent {
+    /// The id of the token.
+    pub id: TokenId,
+    /// The metadata of the token.
+    pub metadata: TokenMetadata,
+    /// The maximum supply of the token, if any.
+    pub cap: Option<u64>,
+}
+
+/// Event emitted when tokens of a created token are minted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenMintedEvent {
+    /// The token minted.
+    pub token: TokenId,
+    /// The account credited.
+    pub to: Account,
+    /// The value minted.
+    pub value: u64,
+}
+
+/// Event emitted when tokens of a created token are transferred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenTransferEvent {
+    /// The token transferred.
+    pub token: TokenId,
+    /// The account tokens are transferred from.
+    pub owner: Account,
+    /// The account spending the tokens, set if `token_transfer_from` is used, or if a contract
+    /// transfers from an owner with `token_transfer_from_contract`.
+    pub spender: Option<Account>,
+    /// The account receiving the tokens.
+    pub to: Account,
+    /// The value transferred.
+    pub value: u64,
+}
+
+/// Event emitted when tokens of a created token are burned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenBurnEvent {
+    /// The token burned.
+    pub token: TokenId,
+    /// The account the tokens are burned from.
+    pub owner: Account,
+    /// The value burned.
+    pub value: u64,
+}
+
+/// Event emitted when a spender is approved on an account for a created token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenApproveEvent {
+    /// The token approved.
+    pub token: TokenId,
+    /// The account allowing the transfer.
+    pub owner: Account,
+    /// The allowed spender.
+    pub spender: Account,
+    /// The value `spender` is allowed to spend.
+    pub value: u64,
+}
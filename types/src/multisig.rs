@@ -0,0 +1,116 @@
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use execution_core::signatures::bls::{PublicKey, SecretKey, Signature};
+
+use crate::{Account, Message, MESSAGE_VERSION};
+
+/// The keys controlling a multisig account, and how many of them must sign to move its tokens.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Multisig {
+    /// The keys controlling the account, which must be distinct.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
+    pub keys: Vec<PublicKey>,
+    /// The number of distinct keys that must sign.
+    pub threshold: u32,
+}
+
+/// Data used to transfer tokens from a multisig account.
+///
+/// The transfer is created unsigned, and each key holder then adds their signature using
+/// [`MultisigTransfer::sign`].
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultisigTransfer {
+    version: u8,
+    multisig: u64,
+    to: Account,
+    value: u64,
+    nonce: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::indexed"))]
+    signatures: Vec<(u32, Signature)>,
+}
+
+impl MultisigTransfer {
+    const NAME: &'static str = "multisig_transfer";
+
+    /// Create a new, unsigned, transfer from a multisig account.
+    pub fn new(multisig: u64, to: impl Into<Account>, value: u64, nonce: u64) -> Self {
+        Self {
+            version: MESSAGE_VERSION,
+            multisig,
+            to: to.into(),
+            value,
+            nonce,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Sign the transfer with the key at the given index of the multisig account's keys.
+    pub fn sign(&mut self, key_index: u32, sk: &SecretKey) {
+        let sig_msg = self.signature_message();
+        let sig = sk.sign(&sig_msg);
+        self.signatures.push((key_index, sig));
+    }
+
+    /// The version of the message format the transfer was signed with.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// The id of the multisig account to transfer from.
+    pub fn multisig(&self) -> u64 {
+        self.multisig
+    }
+
+    /// The account to transfer to.
+    pub fn to(&self) -> &Account {
+        &self.to
+    }
+
+    /// The value to transfer.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// The nonce used to sign the transfer.
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// The signatures over the transfer, together with the index of the key that produced each.
+    pub fn signatures(&self) -> &[(u32, Signature)] {
+        &self.signatures
+    }
+
+    /// The message to be signed over.
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut msg = Message::new(self.version, Self::NAME);
+
+        msg.field(&self.multisig.to_le_bytes());
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.nonce.to_le_bytes());
+
+        msg.into_bytes()
+    }
+}
+
+/// Event emitted when a multisig account is registered.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultisigRegisteredEvent {
+    /// The id of the multisig account.
+    pub id: u64,
+    /// The keys controlling the account.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::vec"))]
+    pub keys: Vec<PublicKey>,
+    /// The number of distinct keys that must sign.
+    pub threshold: u32,
+}
@@ -0,0 +1,97 @@
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use execution_core::signatures::bls::PublicKey;
+
+use crate::{Account, Message, Operation};
+
+/// Stake native Dusk backing the signer's tokens with the Dusk stake contract, burning the tokens.
+/// The stake is owned by the token contract, so it can only be unstaked through it.
+///
+/// Only supported by contracts deployed with `wrapped` set in the [`InitArgs`](crate::InitArgs).
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StakeNative {
+    /// The key the stake is held under.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub stake_key: PublicKey,
+    /// The value staked.
+    pub value: u64,
+    /// The arguments of the stake, as passed on to the stake contract. They must be signed by the
+    /// stake key, and name the token contract as the owner of the stake.
+    pub stake_args: Vec<u8>,
+}
+
+impl Operation for StakeNative {
+    const NAME: &'static str = "stake_native";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.stake_key.to_raw_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.stake_args);
+    }
+}
+
+/// Unstake native Dusk staked by the signer with [`StakeNative`], minting tokens for the Dusk
+/// returned by the stake contract.
+///
+/// Only supported by contracts deployed with `wrapped` set in the [`InitArgs`](crate::InitArgs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnstakeNative {
+    /// The value to unstake.
+    pub value: u64,
+}
+
+impl Operation for UnstakeNative {
+    const NAME: &'static str = "unstake_native";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// The native Dusk an account staked through the token contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NativeStakeInfo {
+    /// The key the stake is held under.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub stake_key: PublicKey,
+    /// The value staked.
+    pub staked: u64,
+    /// The value unstaked, but not yet returned by the stake contract.
+    pub pending_unstake: u64,
+}
+
+/// Event emitted when native Dusk is staked through the token contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NativeStakedEvent {
+    /// The account the tokens are burned from.
+    pub account: Account,
+    /// The key the stake is held under.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub stake_key: PublicKey,
+    /// The value staked.
+    pub value: u64,
+}
+
+/// Event emitted when native Dusk staked through the token contract is unstaked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NativeUnstakedEvent {
+    /// The account credited the tokens.
+    pub account: Account,
+    /// The value unstaked.
+    pub value: u64,
+    /// The value returned by the stake contract so far, and minted as tokens.
+    pub returned: u64,
+}
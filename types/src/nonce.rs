@@ -0,0 +1,76 @@
+use alloc::collections::BTreeMap;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Use a nonce without doing anything else, invalidating any other operation signed with the same
+/// nonce that has not been executed yet. The nonce to cancel is the one this operation is signed
+/// with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CancelNonce;
+
+impl Operation for CancelNonce {
+    const NAME: &'static str = "cancel_nonce";
+
+    fn write_message(&self, _msg: &mut Message) {}
+}
+
+/// Event emitted when a nonce is cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NonceCancelledEvent {
+    /// The account cancelling the nonce.
+    pub account: Account,
+    /// The nonce cancelled.
+    pub nonce: u64,
+}
+
+/// Used to query whether an account has used a nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NonceUsed {
+    /// The account to check.
+    pub account: Account,
+    /// The nonce to check.
+    pub nonce: u64,
+}
+
+/// Keeps track of the nonces of accounts signing operations, allowing for signing multiple
+/// operations in sequence without querying the contract for each of them.
+///
+/// The lowest unused nonce of an account is fetched from the contract - using the `nonce` query - the
+/// first time it is needed, and incremented locally from then on.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NonceManager {
+    nonces: BTreeMap<Account, u64>,
+}
+
+impl NonceManager {
+    /// Create a new manager, with no nonces cached.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The nonce to use in the next operation signed by the account. If not cached, it is fetched
+    /// using the given closure, which should return the result of the `nonce` query.
+    pub fn next(&mut self, account: Account, fetch: impl FnOnce(Account) -> u64) -> u64 {
+        let nonce = self.nonces.entry(account).or_insert_with(|| fetch(account));
+
+        let next = *nonce;
+        *nonce += 1;
+
+        next
+    }
+
+    /// Forget the cached nonce of an account, such that it is fetched again when next needed. This
+    /// should be done when an operation signed by the account fails to be executed.
+    pub fn reset(&mut self, account: &Account) {
+        self.nonces.remove(account);
+    }
+}
@@ -0,0 +1,85 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Authorize an account to move any amount of tokens from the signer's account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuthorizeOperator {
+    /// The account to authorize as an operator.
+    pub operator: Account,
+}
+
+impl Operation for AuthorizeOperator {
+    const NAME: &'static str = "authorize_operator";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.operator.to_bytes());
+    }
+}
+
+/// Revoke an account's authorization to move tokens from the signer's account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RevokeOperator {
+    /// The account to revoke as an operator.
+    pub operator: Account,
+}
+
+impl Operation for RevokeOperator {
+    const NAME: &'static str = "revoke_operator";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.operator.to_bytes());
+    }
+}
+
+/// Transfer tokens from an account the signer is an operator of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatorTransfer {
+    /// The account to transfer from.
+    pub from: Account,
+    /// The account to transfer to.
+    pub to: Account,
+    /// The value to transfer.
+    pub value: u64,
+}
+
+impl Operation for OperatorTransfer {
+    const NAME: &'static str = "operator_transfer";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.from.to_bytes());
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Used to query whether an account is an operator of another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IsOperator {
+    /// The account holding the tokens.
+    pub owner: Account,
+    /// The account possibly authorized to move them.
+    pub operator: Account,
+}
+
+/// Event emitted when an operator is authorized or revoked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatorEvent {
+    /// The account holding the tokens.
+    pub owner: Account,
+    /// The operator authorized or revoked.
+    pub operator: Account,
+    /// Whether the operator is now authorized.
+    pub authorized: bool,
+}
@@ -0,0 +1,116 @@
+use core::ops::{BitOr, BitOrAssign};
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Message, Operation};
+
+/// A set of operations that can be paused by the admin, combined using `|`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PauseScope(u8);
+
+impl PauseScope {
+    /// No operations.
+    pub const NONE: Self = Self(0);
+    /// Transferring tokens, including locked and proposed transfers.
+    pub const TRANSFERS: Self = Self(1);
+    /// Approving spenders. Revoking an approval, by setting it to zero, is never paused.
+    pub const APPROVALS: Self = Self(1 << 1);
+    /// Minting tokens, by emission, deposits of wrapped Dusk, or of created tokens.
+    pub const MINT: Self = Self(1 << 2);
+    /// All operations that can be paused.
+    pub const ALL: Self = Self(Self::TRANSFERS.0 | Self::APPROVALS.0 | Self::MINT.0);
+
+    /// The scopes in the order they are tested in, each with a single operation.
+    pub const SCOPES: [Self; 3] = [Self::TRANSFERS, Self::APPROVALS, Self::MINT];
+
+    /// The set given by its bits, or `None` if any of them doesn't stand for an operation.
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        if bits & !Self::ALL.0 != 0 {
+            return None;
+        }
+        Some(Self(bits))
+    }
+
+    /// The bits of the set.
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Whether the set holds no operations.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether the set holds all the operations in `other`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// The operations in the set that aren't in `other`.
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+}
+
+impl BitOr for PauseScope {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for PauseScope {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+/// Pause the given operations, on top of those already paused. Only the admin may sign this
+/// operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pause {
+    /// The operations to pause.
+    pub scope: PauseScope,
+}
+
+impl Operation for Pause {
+    const NAME: &'static str = "pause";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&[self.scope.bits()]);
+    }
+}
+
+/// Resume the given operations, leaving any others paused. Only the admin may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Unpause {
+    /// The operations to resume.
+    pub scope: PauseScope,
+}
+
+impl Operation for Unpause {
+    const NAME: &'static str = "unpause";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&[self.scope.bits()]);
+    }
+}
+
+/// Event emitted for each operation paused or resumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PauseEvent {
+    /// The operation, a single one of [`PauseScope::SCOPES`].
+    pub scope: PauseScope,
+    /// Whether the operation was paused, or resumed.
+    pub paused: bool,
+}
@@ -0,0 +1,54 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation, Signed};
+
+/// Allow a spender to use tokens from the signer's account, signed off-chain by the owner and
+/// submitted by anyone - usually the spender itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Permit {
+    /// The account to allow spending tokens.
+    pub spender: Account,
+    /// The value to approve the transfer of.
+    pub value: u64,
+    /// The last block height at which the permit may be used.
+    pub deadline: u64,
+}
+
+impl Operation for Permit {
+    const NAME: &'static str = "permit";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.spender.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.deadline.to_le_bytes());
+    }
+}
+
+/// Use a permit signed by the owner, and immediately transfer tokens from the owner's account. The
+/// signer must be the spender of the permit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PermitTransferFrom {
+    /// The permit signed by the owner.
+    pub permit: Signed<Permit>,
+    /// The account to transfer to.
+    pub to: Account,
+    /// The value to transfer.
+    pub value: u64,
+}
+
+impl Operation for PermitTransferFrom {
+    const NAME: &'static str = "permit_transfer_from";
+
+    fn write_message(&self, msg: &mut Message) {
+        let permit_msg = self.permit.signature_message();
+
+        msg.field(&permit_msg);
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
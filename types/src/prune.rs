@@ -0,0 +1,37 @@
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Remove the entries of the given accounts, if they hold no tokens. The nonces the accounts used
+/// are kept apart, so operations they signed still can't be replayed, but the number of nonces
+/// used returned by `account` starts over from zero. Only the admin may sign this operation.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PruneEmptyAccounts {
+    /// The accounts to prune.
+    pub accounts: Vec<Account>,
+}
+
+impl Operation for PruneEmptyAccounts {
+    const NAME: &'static str = "prune_empty_accounts";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&(self.accounts.len() as u32).to_le_bytes());
+        for account in &self.accounts {
+            msg.field(&account.to_bytes());
+        }
+    }
+}
+
+/// Event emitted when empty accounts are pruned by the admin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountsPrunedEvent {
+    /// The number of entries removed.
+    pub count: u32,
+}
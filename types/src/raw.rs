@@ -0,0 +1,25 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Transfer tokens without calling the `token_received` hook of the receiving contract. This allows
+/// sending tokens to contracts that don't implement the hook, at the risk of them being stuck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferRaw {
+    /// The account to transfer to.
+    pub to: Account,
+    /// The value to transfer.
+    pub value: u64,
+}
+
+impl Operation for TransferRaw {
+    const NAME: &'static str = "transfer_raw";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
@@ -0,0 +1,33 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Transfer tokens, paying a fee in tokens to the relayer submitting the transfer on behalf of the
+/// signer. This allows accounts without native funds to pay for gas to move their tokens.
+///
+/// Only supported by contracts compiled with the `fees` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelayedTransfer {
+    /// The account to transfer to.
+    pub to: Account,
+    /// The value to transfer.
+    pub value: u64,
+    /// The fee paid to the relayer.
+    pub fee: u64,
+    /// The account of the relayer.
+    pub relayer: Account,
+}
+
+impl Operation for RelayedTransfer {
+    const NAME: &'static str = "relayed_transfer";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.fee.to_le_bytes());
+        msg.field(&self.relayer.to_bytes());
+    }
+}
@@ -0,0 +1,48 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use execution_core::ContractId;
+
+use crate::{Account, Message, Operation};
+
+/// Move tokens held by the token contract itself to another account. Only the admin may sign this
+/// operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rescue {
+    /// The token contract whose tokens are rescued, if not this one. The other contract must
+    /// implement `transfer_from_contract` as this one does.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+    pub token: Option<ContractId>,
+    /// The account to move the tokens to.
+    pub to: Account,
+    /// The value to move.
+    pub value: u64,
+}
+
+impl Operation for Rescue {
+    const NAME: &'static str = "rescue";
+
+    fn write_message(&self, msg: &mut Message) {
+        // an empty field stands for this token contract
+        let token = self.token.map(|token| token.to_bytes());
+        msg.field(token.as_ref().map(|bytes| &bytes[..]).unwrap_or(&[]));
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Event emitted when tokens held by the token contract are rescued by the admin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RescuedEvent {
+    /// The token contract whose tokens were rescued.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub token: ContractId,
+    /// The account the tokens were moved to.
+    pub to: Account,
+    /// The value moved.
+    pub value: u64,
+}
@@ -0,0 +1,81 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Distribute tokens from the signer's balance to all holders, pro-rata by their balance at a
+/// snapshot taken at the time of distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Distribute {
+    /// The value to distribute.
+    pub value: u64,
+}
+
+impl Operation for Distribute {
+    const NAME: &'static str = "distribute";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Claim all rewards distributed to the signer so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClaimRewards;
+
+impl Operation for ClaimRewards {
+    const NAME: &'static str = "claim_rewards";
+
+    fn write_message(&self, _msg: &mut Message) {}
+}
+
+/// A distribution of rewards to holders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Distribution {
+    /// The snapshot balances are taken at.
+    pub snapshot_id: u64,
+    /// The value distributed.
+    pub value: u64,
+    /// The sum of all balances at the snapshot.
+    pub total_balance: u64,
+}
+
+impl Distribution {
+    /// The share of the distribution of an account with the given balance at the snapshot.
+    pub fn share_of(&self, balance: u64) -> u64 {
+        if self.total_balance == 0 {
+            return 0;
+        }
+        (balance as u128 * self.value as u128 / self.total_balance as u128) as u64
+    }
+}
+
+/// Event emitted when rewards are distributed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DistributedEvent {
+    /// The account distributing the rewards.
+    pub distributor: Account,
+    /// The value distributed.
+    pub value: u64,
+    /// The snapshot balances are taken at.
+    pub snapshot_id: u64,
+}
+
+/// Event emitted when rewards are claimed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RewardsClaimedEvent {
+    /// The account claiming.
+    pub account: Account,
+    /// The value claimed.
+    pub value: u64,
+}
@@ -0,0 +1,43 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation, SignerKey};
+
+/// Move the balance and allowances of the signer's account to the account of a new key, such as
+/// when the signing key is rotated.
+///
+/// The allowances moved are those granted by the account. Allowances granted to it by others are
+/// left in place, to be approved again for the new account by their owners. The expiries, spending
+/// limits and operators set by the account move with its allowances, and the session keys
+/// registered for it are revoked. Rotation is rejected while any sub-account of the old key holds
+/// tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RotateKey {
+    /// The key taking over the account.
+    pub new_key: SignerKey,
+}
+
+impl Operation for RotateKey {
+    const NAME: &'static str = "rotate_key";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.new_key.to_message_bytes());
+    }
+}
+
+/// Event emitted when the key of an account is rotated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyRotated {
+    /// The account of the old key.
+    pub old: Account,
+    /// The account of the new key.
+    pub new: Account,
+    /// The balance moved.
+    pub balance: u64,
+    /// The number of allowances moved.
+    pub allowances: u32,
+}
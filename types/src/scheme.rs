@@ -0,0 +1,232 @@
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use execution_core::signatures::bls::{PublicKey, Signature};
+
+use crate::Account;
+
+/// A signature scheme externally owned accounts may sign operations with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignatureScheme {
+    /// BLS signatures over BLS12-381, as used by Dusk keys.
+    Bls,
+    /// ECDSA signatures over secp256k1, as produced by most hardware wallets.
+    Secp256k1,
+    /// EdDSA signatures over Curve25519.
+    Ed25519,
+}
+
+impl SignatureScheme {
+    /// The byte identifying the scheme in signature messages.
+    pub const fn tag(self) -> u8 {
+        match self {
+            Self::Bls => 0,
+            Self::Secp256k1 => 1,
+            Self::Ed25519 => 2,
+        }
+    }
+}
+
+/// A secp256k1 public key, in its 33-byte compressed SEC1 encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Secp256k1PublicKey(pub [u8; 33]);
+
+impl Secp256k1PublicKey {
+    /// The size of the encoding of a key.
+    pub const SIZE: usize = 33;
+
+    /// Whether the bytes encode a point on the curve.
+    pub fn is_valid(&self) -> bool {
+        k256::ecdsa::VerifyingKey::from_sec1_bytes(&self.0).is_ok()
+    }
+
+    /// Whether the signature over the message is valid for the key. Signatures are over the
+    /// SHA-256 digest of the message, and must be normalized to a low `s`.
+    pub fn verify(&self, signature: &Secp256k1Signature, msg: &[u8]) -> bool {
+        use k256::ecdsa::signature::Verifier;
+
+        let Ok(key) = k256::ecdsa::VerifyingKey::from_sec1_bytes(&self.0) else {
+            return false;
+        };
+        let Ok(signature) = k256::ecdsa::Signature::from_slice(&signature.0) else {
+            return false;
+        };
+
+        key.verify(msg, &signature).is_ok()
+    }
+}
+
+/// A secp256k1 ECDSA signature, as the 64-byte concatenation of `r` and `s`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Secp256k1Signature(pub [u8; 64]);
+
+impl Secp256k1Signature {
+    /// The size of a signature.
+    pub const SIZE: usize = 64;
+}
+
+/// An Ed25519 public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Ed25519PublicKey(pub [u8; 32]);
+
+impl Ed25519PublicKey {
+    /// The size of a key.
+    pub const SIZE: usize = 32;
+
+    /// Whether the bytes encode a point on the curve.
+    pub fn is_valid(&self) -> bool {
+        ed25519_dalek::VerifyingKey::from_bytes(&self.0).is_ok()
+    }
+
+    /// Whether the signature over the message is valid for the key. Verification is strict,
+    /// rejecting weak keys and malleable signatures.
+    pub fn verify(&self, signature: &Ed25519Signature, msg: &[u8]) -> bool {
+        let Ok(key) = ed25519_dalek::VerifyingKey::from_bytes(&self.0) else {
+            return false;
+        };
+        let signature = ed25519_dalek::Signature::from_bytes(&signature.0);
+
+        key.verify_strict(msg, &signature).is_ok()
+    }
+}
+
+/// An Ed25519 signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+pub struct Ed25519Signature(pub [u8; 64]);
+
+impl Ed25519Signature {
+    /// The size of a signature.
+    pub const SIZE: usize = 64;
+}
+
+/// The key of an externally owned account signing an operation, in any of the supported schemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignerKey {
+    /// A BLS key, signing for [`Account::External`].
+    Bls(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] PublicKey),
+    /// A secp256k1 key, signing for [`Account::Secp256k1`].
+    Secp256k1(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Secp256k1PublicKey),
+    /// An Ed25519 key, signing for [`Account::Ed25519`].
+    Ed25519(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Ed25519PublicKey),
+}
+
+impl SignerKey {
+    /// The scheme the key signs with.
+    pub fn scheme(&self) -> SignatureScheme {
+        match self {
+            Self::Bls(_) => SignatureScheme::Bls,
+            Self::Secp256k1(_) => SignatureScheme::Secp256k1,
+            Self::Ed25519(_) => SignatureScheme::Ed25519,
+        }
+    }
+
+    /// The account the key signs for.
+    pub fn account(&self) -> Account {
+        match *self {
+            Self::Bls(pk) => Account::External(pk),
+            Self::Secp256k1(pk) => Account::Secp256k1(pk),
+            Self::Ed25519(pk) => Account::Ed25519(pk),
+        }
+    }
+
+    /// The bytes of the key, as used in signature messages.
+    ///
+    /// BLS keys are given by their 193-byte raw representation. The keys of other schemes are
+    /// prefixed by the [tag](SignatureScheme::tag) of their scheme, so a signature made with one
+    /// scheme can't be passed off as made with another.
+    pub fn to_message_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Bls(pk) => pk.to_raw_bytes().to_vec(),
+            Self::Secp256k1(pk) => tagged(SignatureScheme::Secp256k1, &pk.0),
+            Self::Ed25519(pk) => tagged(SignatureScheme::Ed25519, &pk.0),
+        }
+    }
+
+    /// Whether the signature over the message is valid for the key. Signatures made with a
+    /// different scheme than the key are never valid.
+    pub fn verify(&self, signature: &SchemeSignature, msg: &[u8]) -> bool {
+        match (self, signature) {
+            (Self::Bls(pk), SchemeSignature::Bls(sig)) => pk.verify(sig, msg).is_ok(),
+            (Self::Secp256k1(pk), SchemeSignature::Secp256k1(sig)) => pk.verify(sig, msg),
+            (Self::Ed25519(pk), SchemeSignature::Ed25519(sig)) => pk.verify(sig, msg),
+            _ => false,
+        }
+    }
+}
+
+fn tagged(scheme: SignatureScheme, key: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1 + key.len());
+    bytes.push(scheme.tag());
+    bytes.extend_from_slice(key);
+    bytes
+}
+
+impl From<PublicKey> for SignerKey {
+    fn from(pk: PublicKey) -> Self {
+        Self::Bls(pk)
+    }
+}
+
+impl From<Secp256k1PublicKey> for SignerKey {
+    fn from(pk: Secp256k1PublicKey) -> Self {
+        Self::Secp256k1(pk)
+    }
+}
+
+impl From<Ed25519PublicKey> for SignerKey {
+    fn from(pk: Ed25519PublicKey) -> Self {
+        Self::Ed25519(pk)
+    }
+}
+
+/// A signature over an operation, in any of the supported schemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SchemeSignature {
+    /// A BLS signature.
+    Bls(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Signature),
+    /// A secp256k1 ECDSA signature.
+    Secp256k1(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Secp256k1Signature),
+    /// An Ed25519 signature.
+    Ed25519(#[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))] Ed25519Signature),
+}
+
+impl SchemeSignature {
+    /// The scheme of the signature.
+    pub fn scheme(&self) -> SignatureScheme {
+        match self {
+            Self::Bls(_) => SignatureScheme::Bls,
+            Self::Secp256k1(_) => SignatureScheme::Secp256k1,
+            Self::Ed25519(_) => SignatureScheme::Ed25519,
+        }
+    }
+}
+
+impl From<Signature> for SchemeSignature {
+    fn from(sig: Signature) -> Self {
+        Self::Bls(sig)
+    }
+}
+
+impl From<Secp256k1Signature> for SchemeSignature {
+    fn from(sig: Secp256k1Signature) -> Self {
+        Self::Secp256k1(sig)
+    }
+}
+
+impl From<Ed25519Signature> for SchemeSignature {
+    fn from(sig: Ed25519Signature) -> Self {
+        Self::Ed25519(sig)
+    }
+}
@@ -0,0 +1,197 @@
+//! Human-readable encoding of keys, signatures, and contract ids as hex strings, used through
+//! `#[serde(with = "...")]` on the fields holding them.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use dusk_bytes::Serializable;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use execution_core::signatures::bls::{MultisigSignature, PublicKey, Signature};
+use execution_core::ContractId;
+
+use crate::{Ed25519PublicKey, Ed25519Signature, Secp256k1PublicKey, Secp256k1Signature};
+
+/// A type encoded as the hex string of its bytes.
+pub(crate) trait Hex: Sized {
+    fn encode(&self) -> String;
+    fn decode(s: &str) -> Option<Self>;
+}
+
+fn decode_array<const N: usize>(s: &str) -> Option<[u8; N]> {
+    let mut bytes = [0u8; N];
+    hex::decode_to_slice(s, &mut bytes).ok()?;
+    Some(bytes)
+}
+
+impl Hex for PublicKey {
+    fn encode(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    fn decode(s: &str) -> Option<Self> {
+        PublicKey::from_bytes(&decode_array(s)?).ok()
+    }
+}
+
+impl Hex for Signature {
+    fn encode(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    fn decode(s: &str) -> Option<Self> {
+        Signature::from_bytes(&decode_array(s)?).ok()
+    }
+}
+
+impl Hex for MultisigSignature {
+    fn encode(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    fn decode(s: &str) -> Option<Self> {
+        MultisigSignature::from_bytes(&decode_array(s)?).ok()
+    }
+}
+
+impl Hex for ContractId {
+    fn encode(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    fn decode(s: &str) -> Option<Self> {
+        Some(ContractId::from_bytes(decode_array(s)?))
+    }
+}
+
+impl Hex for Secp256k1PublicKey {
+    fn encode(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    fn decode(s: &str) -> Option<Self> {
+        decode_array(s).map(Self)
+    }
+}
+
+impl Hex for Secp256k1Signature {
+    fn encode(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    fn decode(s: &str) -> Option<Self> {
+        decode_array(s).map(Self)
+    }
+}
+
+impl Hex for Ed25519PublicKey {
+    fn encode(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    fn decode(s: &str) -> Option<Self> {
+        decode_array(s).map(Self)
+    }
+}
+
+impl Hex for Ed25519Signature {
+    fn encode(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    fn decode(s: &str) -> Option<Self> {
+        decode_array(s).map(Self)
+    }
+}
+
+// Wraps a value to serialize it as hex, so it can be used within options, vectors, and tuples.
+struct Encoded<T>(T);
+
+impl<T: Hex> Serialize for Encoded<&T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.encode())
+    }
+}
+
+impl<'de, T: Hex> Deserialize<'de> for Encoded<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        T::decode(&s)
+            .map(Encoded)
+            .ok_or_else(|| D::Error::custom("invalid hex encoding"))
+    }
+}
+
+pub(crate) fn serialize<T: Hex, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    Encoded(value).serialize(serializer)
+}
+
+pub(crate) fn deserialize<'de, T: Hex, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    Encoded::deserialize(deserializer).map(|encoded| encoded.0)
+}
+
+/// Encodes an optional value.
+pub(crate) mod option {
+    use super::*;
+
+    pub(crate) fn serialize<T: Hex, S: Serializer>(
+        value: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.as_ref().map(Encoded).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, T: Hex, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error> {
+        let value = Option::<Encoded<T>>::deserialize(deserializer)?;
+        Ok(value.map(|encoded| encoded.0))
+    }
+}
+
+/// Encodes a vector of values.
+pub(crate) mod vec {
+    use super::*;
+
+    pub(crate) fn serialize<T: Hex, S: Serializer>(
+        values: &[T],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(Encoded))
+    }
+
+    pub(crate) fn deserialize<'de, T: Hex, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<T>, D::Error> {
+        let values = Vec::<Encoded<T>>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|encoded| encoded.0).collect())
+    }
+}
+
+/// Encodes a vector of values, each paired with an index.
+pub(crate) mod indexed {
+    use super::*;
+
+    pub(crate) fn serialize<T: Hex, S: Serializer>(
+        values: &[(u32, T)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(|(i, value)| (i, Encoded(value))))
+    }
+
+    pub(crate) fn deserialize<'de, T: Hex, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(u32, T)>, D::Error> {
+        let values = Vec::<(u32, Encoded<T>)>::deserialize(deserializer)?;
+        Ok(values
+            .into_iter()
+            .map(|(i, encoded)| (i, encoded.0))
+            .collect())
+    }
+}
@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use execution_core::signatures::bls::PublicKey;
+
+use crate::{Account, Message, Operation, Signed};
+
+/// The transfers a session key may sign on behalf of the account that registered it.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionKeyScope {
+    /// The largest value of a single transfer. If `None`, only the budget limits transfers.
+    pub max_value: Option<u64>,
+    /// The total value the key may transfer.
+    pub budget: u64,
+    /// The accounts the key may transfer to. If `None`, it may transfer to any account.
+    pub recipients: Option<Vec<Account>>,
+    /// The block height from which the key can no longer be used.
+    pub expiry_height: u64,
+}
+
+/// The consent of a key to be registered as a session key of an account, signed by the key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionKeyConsent {
+    /// The account the key agrees to sign transfers from.
+    pub owner: Account,
+}
+
+impl Operation for SessionKeyConsent {
+    const NAME: &'static str = "session_key_consent";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.owner.to_bytes());
+    }
+}
+
+/// Register a session key, allowing it to sign `Transfer`s from the signer's account within the
+/// given scope. Registering a key again replaces its scope, and starts its budget over.
+///
+/// Since every `Transfer` signed by the key is then made from the signer's account, the key must
+/// consent to the registration.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisterSessionKey {
+    /// The session key.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub key: PublicKey,
+    /// The scope of the key.
+    pub scope: SessionKeyScope,
+    /// The consent of the key, signed by it with a nonce of its own.
+    pub consent: Signed<SessionKeyConsent>,
+}
+
+impl Operation for RegisterSessionKey {
+    const NAME: &'static str = "register_session_key";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.key.to_raw_bytes());
+
+        // an empty field stands for no maximum, or no restriction on the recipients
+        let max_value = self.scope.max_value.map(u64::to_le_bytes);
+        msg.field(max_value.as_ref().map(|bytes| &bytes[..]).unwrap_or(&[]));
+        msg.field(&self.scope.budget.to_le_bytes());
+        match &self.scope.recipients {
+            Some(recipients) => {
+                msg.field(&(recipients.len() as u32).to_le_bytes());
+                for recipient in recipients {
+                    msg.field(&recipient.to_bytes());
+                }
+            }
+            None => {
+                msg.field(&[]);
+            }
+        }
+        msg.field(&self.scope.expiry_height.to_le_bytes());
+        msg.field(&self.consent.signature_message());
+    }
+}
+
+/// Revoke a session key, signed either by the account that registered it or by the key itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RevokeSessionKey {
+    /// The session key.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub key: PublicKey,
+}
+
+impl Operation for RevokeSessionKey {
+    const NAME: &'static str = "revoke_session_key";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.key.to_raw_bytes());
+    }
+}
+
+/// A registered session key.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionKey {
+    /// The account the key transfers from.
+    pub owner: Account,
+    /// The scope of the key.
+    pub scope: SessionKeyScope,
+    /// The value the key has transferred, counted against the budget.
+    pub spent: u64,
+}
+
+/// Event emitted when a session key is registered or revoked.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionKeyEvent {
+    /// The account the key transfers from.
+    pub owner: Account,
+    /// The session key.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub key: PublicKey,
+    /// The scope of the key, or `None` if it was revoked.
+    pub scope: Option<SessionKeyScope>,
+}
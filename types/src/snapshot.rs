@@ -0,0 +1,36 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Take a snapshot of all balances and the total supply. Only the admin may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot;
+
+impl Operation for Snapshot {
+    const NAME: &'static str = "snapshot";
+
+    fn write_message(&self, _msg: &mut Message) {}
+}
+
+/// Arguments to query for the balance of an account at a given snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BalanceAt {
+    /// The account to query the balance of.
+    pub account: Account,
+    /// The id of the snapshot.
+    pub snapshot_id: u64,
+}
+
+/// Event emitted when a snapshot is taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotEvent {
+    /// The id of the snapshot.
+    pub id: u64,
+}
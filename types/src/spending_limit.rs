@@ -0,0 +1,69 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Allow a spender to use at most a given value of tokens from the signer's account in every window
+/// of a number of blocks. A spender with a spending limit uses it instead of its allowance. Setting
+/// a limit of zero removes it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetSpendingLimit {
+    /// The account to allow spending tokens.
+    pub spender: Account,
+    /// The value the spender may spend in each window.
+    pub limit: u64,
+    /// The number of blocks in each window. Windows start at multiples of this number.
+    pub window_blocks: u64,
+}
+
+impl Operation for SetSpendingLimit {
+    const NAME: &'static str = "set_spending_limit";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.spender.to_bytes());
+        msg.field(&self.limit.to_le_bytes());
+        msg.field(&self.window_blocks.to_le_bytes());
+    }
+}
+
+/// A limit on the value a spender may spend from an account in each window of blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpendingLimit {
+    /// The value the spender may spend in each window.
+    pub limit: u64,
+    /// The number of blocks in each window.
+    pub window_blocks: u64,
+    /// The window the spent value refers to.
+    pub window: u64,
+    /// The value spent in the window.
+    pub spent: u64,
+}
+
+impl SpendingLimit {
+    /// The value the spender may still spend at the given block height.
+    pub fn budget_at(&self, block_height: u64) -> u64 {
+        if block_height / self.window_blocks != self.window {
+            return self.limit;
+        }
+        self.limit - self.spent
+    }
+}
+
+/// Event emitted when a spending limit is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpendingLimitEvent {
+    /// The account whose tokens are limited.
+    pub owner: Account,
+    /// The account allowed to spend them.
+    pub spender: Account,
+    /// The value the spender may spend in each window.
+    pub limit: u64,
+    /// The number of blocks in each window.
+    pub window_blocks: u64,
+}
@@ -0,0 +1,86 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Stake tokens from the signer's balance, locking them for a number of blocks. Staking more tokens
+/// while some are already staked adds to them, locking all of them until the latest of the unlock
+/// heights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stake {
+    /// The value to stake.
+    pub value: u64,
+    /// The number of blocks to lock the tokens for.
+    pub lock_blocks: u64,
+}
+
+impl Operation for Stake {
+    const NAME: &'static str = "stake";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.lock_blocks.to_le_bytes());
+    }
+}
+
+/// Unstake tokens whose lock period has passed, moving them back into the signer's balance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Unstake {
+    /// The value to unstake.
+    pub value: u64,
+}
+
+impl Operation for Unstake {
+    const NAME: &'static str = "unstake";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// The tokens staked by an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StakeInfo {
+    /// The value staked.
+    pub value: u64,
+    /// The block height from which the tokens can be unstaked.
+    pub unlock_height: u64,
+}
+
+impl StakeInfo {
+    /// Information for an account with no tokens staked.
+    pub const EMPTY: Self = Self {
+        value: 0,
+        unlock_height: 0,
+    };
+}
+
+/// Event emitted when tokens are staked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StakedEvent {
+    /// The account staking.
+    pub account: Account,
+    /// The value staked.
+    pub value: u64,
+    /// The block height from which all of the account's staked tokens can be unstaked.
+    pub unlock_height: u64,
+}
+
+/// Event emitted when tokens are unstaked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnstakedEvent {
+    /// The account unstaking.
+    pub account: Account,
+    /// The value unstaked.
+    pub value: u64,
+}
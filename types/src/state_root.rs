@@ -0,0 +1,100 @@
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, AccountInfo};
+
+/// The root of the Merkle tree of an empty state.
+pub const EMPTY_STATE_ROOT: [u8; 32] = [0; 32];
+
+/// A proof that an account had the given data in the state of a token at a block height, as fed by
+/// the `proof_of` query.
+///
+/// The state tree has a leaf for every account that ever had data, in the order they first got it.
+/// Nodes are hashed in order, with a node without a sibling carried up to the next level unchanged.
+/// Accounts whose data was pruned keep their leaf, with [`AccountInfo::EMPTY`] as their data.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountProof {
+    /// The account.
+    pub account: Account,
+    /// The data of the account.
+    pub info: AccountInfo,
+    /// The block height the proof was produced at.
+    pub block_height: u64,
+    /// The root of the state tree at that height, as returned by `state_root`.
+    pub root: [u8; 32],
+    /// The index of the leaf of the account.
+    pub index: u64,
+    /// The number of leaves in the tree.
+    pub leaf_count: u64,
+    /// The siblings of the nodes on the path from the leaf to the root, from the bottom up.
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl AccountProof {
+    /// Whether the proof is valid for its root. The root should be checked against one known to be
+    /// that of the token at the block height, such as one returned by `state_root` at that height.
+    pub fn verify(&self) -> bool {
+        if self.index >= self.leaf_count {
+            return false;
+        }
+
+        let mut node = state_leaf(&self.account, &self.info);
+        let mut index = self.index;
+        let mut len = self.leaf_count;
+        let mut siblings = self.siblings.iter();
+
+        while len > 1 {
+            if index % 2 == 1 {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+                node = state_node(sibling, &node);
+            } else if index + 1 < len {
+                let Some(sibling) = siblings.next() else {
+                    return false;
+                };
+                node = state_node(&node, sibling);
+            }
+
+            index /= 2;
+            len = len.div_ceil(2);
+        }
+
+        siblings.next().is_none() && node == self.root
+    }
+}
+
+/// The leaf of an account in the state tree.
+pub fn state_leaf(account: &Account, info: &AccountInfo) -> [u8; 32] {
+    let mut bytes = Vec::with_capacity(1 + Account::SIZE + 16);
+
+    bytes.push(0);
+    bytes.extend_from_slice(&account.to_bytes());
+    bytes.extend_from_slice(&info.balance.to_le_bytes());
+    bytes.extend_from_slice(&info.nonce.to_le_bytes());
+
+    hash(&bytes)
+}
+
+/// The parent of two nodes in the state tree.
+pub fn state_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut bytes = [0u8; 65];
+
+    bytes[0] = 1;
+    bytes[1..33].copy_from_slice(left);
+    bytes[33..].copy_from_slice(right);
+
+    hash(&bytes)
+}
+
+fn hash(bytes: &[u8]) -> [u8; 32] {
+    let hash = blake2b_simd::Params::new().hash_length(32).hash(bytes);
+
+    let mut out = [0u8; 32];
+    out.copy_from_slice(hash.as_bytes());
+    out
+}
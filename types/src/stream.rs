@@ -0,0 +1,140 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Open a stream paying a recipient at a fixed rate for every block, out of a deposit taken from the
+/// signer's balance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpenStream {
+    /// The account the tokens stream to.
+    pub to: Account,
+    /// The value accrued to the recipient with every block.
+    pub rate_per_block: u64,
+    /// The value deposited, which the stream pays out until it runs dry.
+    pub deposit: u64,
+}
+
+impl Operation for OpenStream {
+    const NAME: &'static str = "open_stream";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.rate_per_block.to_le_bytes());
+        msg.field(&self.deposit.to_le_bytes());
+    }
+}
+
+/// Withdraw the tokens accrued to the signer by a stream so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WithdrawFromStream {
+    /// The id of the stream.
+    pub id: u64,
+}
+
+impl Operation for WithdrawFromStream {
+    const NAME: &'static str = "withdraw_from_stream";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.id.to_le_bytes());
+    }
+}
+
+/// Close a stream, paying the recipient the tokens accrued so far and returning the rest of the
+/// deposit to the sender. Either of them may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CloseStream {
+    /// The id of the stream.
+    pub id: u64,
+}
+
+impl Operation for CloseStream {
+    const NAME: &'static str = "close_stream";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.id.to_le_bytes());
+    }
+}
+
+/// A stream of tokens from a sender to a recipient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stream {
+    /// The account that opened the stream.
+    pub sender: Account,
+    /// The account the tokens stream to.
+    pub recipient: Account,
+    /// The value accrued to the recipient with every block.
+    pub rate_per_block: u64,
+    /// The value deposited.
+    pub deposit: u64,
+    /// The value already withdrawn by the recipient.
+    pub withdrawn: u64,
+    /// The block height the stream was opened at.
+    pub start_height: u64,
+}
+
+impl Stream {
+    /// The value accrued to the recipient at the given block height, including what was already
+    /// withdrawn.
+    pub fn accrued_at(&self, block_height: u64) -> u64 {
+        let blocks = block_height.saturating_sub(self.start_height);
+        let accrued = blocks as u128 * self.rate_per_block as u128;
+        accrued.min(self.deposit as u128) as u64
+    }
+
+    /// The value the recipient can withdraw at the given block height.
+    pub fn withdrawable_at(&self, block_height: u64) -> u64 {
+        self.accrued_at(block_height) - self.withdrawn
+    }
+}
+
+/// Event emitted when a stream is opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamOpenedEvent {
+    /// The id of the stream.
+    pub id: u64,
+    /// The account that opened the stream.
+    pub sender: Account,
+    /// The account the tokens stream to.
+    pub recipient: Account,
+    /// The value accrued to the recipient with every block.
+    pub rate_per_block: u64,
+    /// The value deposited.
+    pub deposit: u64,
+}
+
+/// Event emitted when tokens accrued by a stream are withdrawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamWithdrawnEvent {
+    /// The id of the stream.
+    pub id: u64,
+    /// The account the tokens stream to.
+    pub recipient: Account,
+    /// The value withdrawn.
+    pub value: u64,
+}
+
+/// Event emitted when a stream is closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamClosedEvent {
+    /// The id of the stream.
+    pub id: u64,
+    /// The value paid to the recipient on closing, accrued but not yet withdrawn.
+    pub paid: u64,
+    /// The value returned to the sender.
+    pub refunded: u64,
+}
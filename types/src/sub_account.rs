@@ -0,0 +1,52 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Transfer tokens from a sub-account of the signer's BLS key, as a `Transfer` does from the account
+/// of the key itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubAccountTransfer {
+    /// The index of the sub-account to transfer from, `0` for the account of the key itself.
+    pub from: u32,
+    /// The account to transfer to.
+    pub to: Account,
+    /// The value to transfer.
+    pub value: u64,
+}
+
+impl Operation for SubAccountTransfer {
+    const NAME: &'static str = "sub_account_transfer";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.from.to_le_bytes());
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Move tokens between two sub-accounts of the signer's BLS key. The tokens stay under the control
+/// of the same key, so the move skips the checks a transfer makes on its recipient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubAccountMove {
+    /// The index of the sub-account to move from, `0` for the account of the key itself.
+    pub from: u32,
+    /// The index of the sub-account to move to, `0` for the account of the key itself.
+    pub to: u32,
+    /// The value to move.
+    pub value: u64,
+}
+
+impl Operation for SubAccountMove {
+    const NAME: &'static str = "sub_account_move";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.from.to_le_bytes());
+        msg.field(&self.to.to_le_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
@@ -0,0 +1,49 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::Account;
+
+/// The number of recent submissions carrying an idempotency id kept for each account.
+pub const SUBMISSION_WINDOW: usize = 64;
+
+/// A transfer submitted with an idempotency id, as kept by the contract once executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Submission {
+    /// The idempotency id the transfer was signed with.
+    pub id: u64,
+    /// The nonce the transfer was signed with.
+    pub nonce: u64,
+    /// The block height the transfer was executed at.
+    pub block_height: u64,
+    /// The value transferred, including any burned tokens.
+    pub value: u64,
+    /// The value the recipient was left with. It is less than `value` if part of it was burned,
+    /// and zero if it was diverted to a recovery bucket after the receiving contract's hook failed.
+    pub received: u64,
+}
+
+/// The status of a submission, as returned by `submission_status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SubmissionStatus {
+    /// No transfer with the id was executed among the last [`SUBMISSION_WINDOW`] of the account.
+    /// Failed transfers leave no trace, so a transfer with this status may be submitted again.
+    Unknown,
+    /// The transfer was executed.
+    Executed(Submission),
+}
+
+/// Arguments to query the status of a submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubmissionId {
+    /// The account that signed the transfer - the owner of a `Transfer`, or the spender of a
+    /// `TransferFrom`.
+    pub account: Account,
+    /// The idempotency id.
+    pub id: u64,
+}
@@ -0,0 +1,177 @@
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use execution_core::signatures::bls::PublicKey;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Move tokens from the signer's balance into the treasury held by the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FundTreasury {
+    /// The value to move into the treasury.
+    pub value: u64,
+}
+
+impl Operation for FundTreasury {
+    const NAME: &'static str = "fund_treasury";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Set the key allowed to pay out of the treasury, and the length of the epochs the caps of payees
+/// apply to. Only the admin may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetTreasury {
+    /// The key allowed to sign payouts. If `None`, no payouts can be made.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+    pub payout_key: Option<PublicKey>,
+    /// The number of blocks in each epoch. Epochs start at multiples of this number.
+    pub epoch_blocks: u64,
+}
+
+impl Operation for SetTreasury {
+    const NAME: &'static str = "set_treasury";
+
+    fn write_message(&self, msg: &mut Message) {
+        // an empty field stands for no payout key
+        let payout_key = self.payout_key.map(|key| key.to_raw_bytes());
+        msg.field(payout_key.as_ref().map(|bytes| &bytes[..]).unwrap_or(&[]));
+        msg.field(&self.epoch_blocks.to_le_bytes());
+    }
+}
+
+/// Register a payee of the treasury, with the value it may be paid in each epoch. Setting a cap of
+/// zero removes the payee. Only the admin may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetPayee {
+    /// The account to pay.
+    pub payee: Account,
+    /// The value the payee may be paid in each epoch.
+    pub cap: u64,
+}
+
+impl Operation for SetPayee {
+    const NAME: &'static str = "set_payee";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.payee.to_bytes());
+        msg.field(&self.cap.to_le_bytes());
+    }
+}
+
+/// Pay registered payees out of the treasury, each within its cap for the current epoch. Only the
+/// payout key may sign this operation.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Payout {
+    /// The payees and the value paid to each. A payee may appear more than once, as long as the
+    /// sum paid to it stays within its cap.
+    pub payments: Vec<(Account, u64)>,
+}
+
+impl Operation for Payout {
+    const NAME: &'static str = "payout";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&(self.payments.len() as u32).to_le_bytes());
+        for (payee, value) in &self.payments {
+            msg.field(&payee.to_bytes());
+            msg.field(&value.to_le_bytes());
+        }
+    }
+}
+
+/// The state of the treasury.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreasuryInfo {
+    /// The value held by the treasury.
+    pub balance: u64,
+    /// The key allowed to sign payouts, if any.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+    pub payout_key: Option<PublicKey>,
+    /// The number of blocks in each epoch.
+    pub epoch_blocks: u64,
+}
+
+/// A payee of the treasury, and the value it was paid in the epoch of its last payment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Payee {
+    /// The value the payee may be paid in each epoch.
+    pub cap: u64,
+    /// The epoch the paid value refers to.
+    pub epoch: u64,
+    /// The value paid in the epoch.
+    pub paid: u64,
+}
+
+impl Payee {
+    /// The value the payee may still be paid at the given block height, with epochs of the given
+    /// number of blocks.
+    pub fn budget_at(&self, block_height: u64, epoch_blocks: u64) -> u64 {
+        if block_height / epoch_blocks != self.epoch {
+            return self.cap;
+        }
+        self.cap.saturating_sub(self.paid)
+    }
+}
+
+/// Event emitted when tokens are moved into the treasury.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreasuryFundedEvent {
+    /// The account funding the treasury.
+    pub from: Account,
+    /// The value moved into the treasury.
+    pub value: u64,
+}
+
+/// Event emitted when the payout key or the epochs of the treasury are set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreasurySetEvent {
+    /// The key allowed to sign payouts, if any.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex::option"))]
+    pub payout_key: Option<PublicKey>,
+    /// The number of blocks in each epoch.
+    pub epoch_blocks: u64,
+}
+
+/// Event emitted when a payee is registered, has its cap changed, or is removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PayeeEvent {
+    /// The payee.
+    pub payee: Account,
+    /// The value the payee may be paid in each epoch, zero if it was removed.
+    pub cap: u64,
+}
+
+/// Event emitted for each payment out of the treasury.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PayoutEvent {
+    /// The payee.
+    pub payee: Account,
+    /// The value paid.
+    pub value: u64,
+    /// The epoch the payment counts against.
+    pub epoch: u64,
+}
@@ -0,0 +1,123 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Propose a transfer to an account, holding the tokens in the contract until the recipient claims
+/// them. The transfer can be claimed until the expiry height, and cancelled by the signer until it
+/// is claimed.
+///
+/// Transfers above the two-phase threshold chosen at `init` can only be made this way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProposeTransfer {
+    /// The account to transfer to.
+    pub to: Account,
+    /// The value to transfer.
+    pub value: u64,
+    /// The block height from which the transfer can no longer be claimed.
+    pub expiry_height: u64,
+}
+
+impl Operation for ProposeTransfer {
+    const NAME: &'static str = "propose_transfer";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.expiry_height.to_le_bytes());
+    }
+}
+
+/// Claim a transfer proposed to the signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClaimTransfer {
+    /// The id of the proposed transfer.
+    pub id: u64,
+}
+
+impl Operation for ClaimTransfer {
+    const NAME: &'static str = "claim_transfer";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.id.to_le_bytes());
+    }
+}
+
+/// Cancel a transfer proposed by the signer, returning the tokens to them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CancelTransfer {
+    /// The id of the proposed transfer.
+    pub id: u64,
+}
+
+impl Operation for CancelTransfer {
+    const NAME: &'static str = "cancel_transfer";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.id.to_le_bytes());
+    }
+}
+
+/// A proposed transfer, whose tokens are held by the contract until it is claimed or cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingTransfer {
+    /// The account the tokens are transferred from.
+    pub from: Account,
+    /// The account the tokens are transferred to.
+    pub to: Account,
+    /// The value transferred.
+    pub value: u64,
+    /// The block height from which the transfer can no longer be claimed.
+    pub expiry_height: u64,
+}
+
+/// Event emitted when a transfer is proposed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferProposedEvent {
+    /// The id of the proposed transfer.
+    pub id: u64,
+    /// The account the tokens are transferred from.
+    pub from: Account,
+    /// The account the tokens are transferred to.
+    pub to: Account,
+    /// The value transferred.
+    pub value: u64,
+    /// The block height from which the transfer can no longer be claimed.
+    pub expiry_height: u64,
+}
+
+/// Event emitted when a proposed transfer is claimed by its recipient.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferClaimedEvent {
+    /// The id of the proposed transfer.
+    pub id: u64,
+    /// The account the tokens were credited to.
+    pub to: Account,
+    /// The value credited.
+    pub value: u64,
+}
+
+/// Event emitted when a proposed transfer is cancelled by its sender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransferCancelledEvent {
+    /// The id of the proposed transfer.
+    pub id: u64,
+    /// The account the tokens were returned to.
+    pub from: Account,
+    /// The value returned.
+    pub value: u64,
+}
@@ -0,0 +1,132 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use execution_core::ContractId;
+
+use crate::{Account, Message, MESSAGE_VERSION};
+
+/// A contract function that validates authorizations for a contract account, registered by the
+/// account using `register_verifier`.
+///
+/// The function is called with a [`VerifyAuthorization`], and must return `true` if the
+/// authorization is valid. It is called as a hook, so it can't change the state of the token
+/// contract.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Verifier {
+    /// The contract to call.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub contract: ContractId,
+    /// The name of the function to call.
+    pub fn_name: String,
+}
+
+/// Passed to a [`Verifier`] to validate an authorization.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VerifyAuthorization {
+    /// The contract account the authorization is for.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub account: ContractId,
+    /// The message that was authorized.
+    pub message: Vec<u8>,
+    /// The authorization, in whatever form the verifier understands.
+    pub authorization: Vec<u8>,
+}
+
+/// Transfer tokens from a contract account, authorized by an opaque blob validated by the
+/// [`Verifier`] the account registered. This enables contract accounts - such as smart wallets - to
+/// authorize spending their balance off-chain, using any signature scheme.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuthorizedTransfer {
+    version: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    from: ContractId,
+    to: Account,
+    value: u64,
+    nonce: u64,
+    authorization: Vec<u8>,
+}
+
+impl AuthorizedTransfer {
+    const NAME: &'static str = "authorized_transfer";
+
+    /// Create a new, unauthorized, transfer.
+    pub fn new(from: ContractId, to: impl Into<Account>, value: u64, nonce: u64) -> Self {
+        Self {
+            version: MESSAGE_VERSION,
+            from,
+            to: to.into(),
+            value,
+            nonce,
+            authorization: Vec::new(),
+        }
+    }
+
+    /// Set the authorization, produced over the
+    /// [`signature_message`](Self::signature_message) of the transfer.
+    pub fn set_authorization(&mut self, authorization: Vec<u8>) {
+        self.authorization = authorization;
+    }
+
+    /// The contract account to transfer from.
+    pub fn from(&self) -> &ContractId {
+        &self.from
+    }
+
+    /// The account to transfer to.
+    pub fn to(&self) -> &Account {
+        &self.to
+    }
+
+    /// The value to transfer.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// The nonce of the contract account used for the transfer.
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// The version of the message format the payload was authorized with.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// The authorization of the transfer.
+    pub fn authorization(&self) -> &[u8] {
+        &self.authorization
+    }
+
+    /// The message to be authorized.
+    pub fn signature_message(&self) -> Vec<u8> {
+        let mut msg = Message::new(self.version, Self::NAME);
+
+        msg.field(&self.from.to_bytes());
+        msg.field(&self.to.to_bytes());
+        msg.field(&self.value.to_le_bytes());
+        msg.field(&self.nonce.to_le_bytes());
+
+        msg.into_bytes()
+    }
+}
+
+/// Event emitted when a contract account registers, or unregisters, its verifier.
+#[derive(Debug, Clone, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VerifierRegisteredEvent {
+    /// The contract account.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub account: ContractId,
+    /// The verifier registered, or `None` if it was unregistered.
+    pub verifier: Option<Verifier>,
+}
@@ -0,0 +1,113 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Lock tokens from the admin's balance, to be released linearly to a beneficiary. Only the admin
+/// may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CreateVesting {
+    /// The account the tokens vest to.
+    pub beneficiary: Account,
+    /// The total number of tokens to vest.
+    pub total: u64,
+    /// The block height from which the tokens start vesting.
+    pub start_height: u64,
+    /// The number of blocks after `start_height` before any tokens can be claimed.
+    pub cliff: u64,
+    /// The number of blocks after `start_height` over which all tokens vest.
+    pub duration: u64,
+}
+
+impl Operation for CreateVesting {
+    const NAME: &'static str = "create_vesting";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.beneficiary.to_bytes());
+        msg.field(&self.total.to_le_bytes());
+        msg.field(&self.start_height.to_le_bytes());
+        msg.field(&self.cliff.to_le_bytes());
+        msg.field(&self.duration.to_le_bytes());
+    }
+}
+
+/// Claim the tokens vested to the signer so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClaimVested;
+
+impl Operation for ClaimVested {
+    const NAME: &'static str = "claim_vested";
+
+    fn write_message(&self, _msg: &mut Message) {}
+}
+
+/// The vesting schedule of an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vesting {
+    /// The total number of tokens vesting.
+    pub total: u64,
+    /// The number of tokens already claimed.
+    pub claimed: u64,
+    /// The block height from which the tokens start vesting.
+    pub start_height: u64,
+    /// The number of blocks after `start_height` before any tokens can be claimed.
+    pub cliff: u64,
+    /// The number of blocks after `start_height` over which all tokens vest.
+    pub duration: u64,
+}
+
+impl Vesting {
+    /// The number of tokens vested at the given block height, including those already claimed.
+    pub fn vested_at(&self, block_height: u64) -> u64 {
+        match self.start_height.checked_add(self.cliff) {
+            Some(cliff_height) if block_height >= cliff_height => {}
+            _ => return 0,
+        }
+
+        let elapsed = block_height - self.start_height;
+        if elapsed >= self.duration {
+            return self.total;
+        }
+
+        (self.total as u128 * elapsed as u128 / self.duration as u128) as u64
+    }
+
+    /// The number of tokens that can be claimed at the given block height.
+    pub fn claimable_at(&self, block_height: u64) -> u64 {
+        self.vested_at(block_height) - self.claimed
+    }
+}
+
+/// Event emitted when a vesting schedule is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VestingCreatedEvent {
+    /// The account the tokens vest to.
+    pub beneficiary: Account,
+    /// The total number of tokens vesting.
+    pub total: u64,
+    /// The block height from which the tokens start vesting.
+    pub start_height: u64,
+    /// The number of blocks after `start_height` before any tokens can be claimed.
+    pub cliff: u64,
+    /// The number of blocks after `start_height` over which all tokens vest.
+    pub duration: u64,
+}
+
+/// Event emitted when vested tokens are claimed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VestingClaimedEvent {
+    /// The account claiming the tokens.
+    pub beneficiary: Account,
+    /// The value claimed.
+    pub value: u64,
+}
@@ -0,0 +1,51 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::{Account, Message, Operation};
+
+/// Add an account to the whitelist, allowing it to hold tokens. Only the admin may sign this
+/// operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AddToWhitelist {
+    /// The account to add.
+    pub account: Account,
+}
+
+impl Operation for AddToWhitelist {
+    const NAME: &'static str = "add_to_whitelist";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.account.to_bytes());
+    }
+}
+
+/// Remove an account from the whitelist, preventing it from sending or receiving tokens. Only the
+/// admin may sign this operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RemoveFromWhitelist {
+    /// The account to remove.
+    pub account: Account,
+}
+
+impl Operation for RemoveFromWhitelist {
+    const NAME: &'static str = "remove_from_whitelist";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.account.to_bytes());
+    }
+}
+
+/// Event emitted when an account is added to, or removed from, the whitelist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WhitelistEvent {
+    /// The account added or removed.
+    pub account: Account,
+    /// Whether the account is now whitelisted.
+    pub whitelisted: bool,
+}
@@ -0,0 +1,69 @@
+use bytecheck::CheckBytes;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use execution_core::signatures::bls::PublicKey;
+
+use crate::{Account, Message, Operation};
+
+/// Deposit native Dusk into the contract, minting an equal amount of tokens. The Dusk must be
+/// deposited to the contract in the same transaction.
+///
+/// Only supported by contracts deployed with `wrapped` set in the [`InitArgs`](crate::InitArgs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deposit {
+    /// The account to credit the tokens to.
+    pub to: Account,
+    /// The value deposited.
+    pub value: u64,
+}
+
+/// Withdraw native Dusk from the contract, burning an equal amount of tokens from the signer's
+/// balance.
+///
+/// Only supported by contracts deployed with `wrapped` set in the [`InitArgs`](crate::InitArgs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Withdraw {
+    /// The account to send the Dusk to.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub to: PublicKey,
+    /// The value withdrawn.
+    pub value: u64,
+}
+
+impl Operation for Withdraw {
+    const NAME: &'static str = "withdraw";
+
+    fn write_message(&self, msg: &mut Message) {
+        msg.field(&self.to.to_raw_bytes());
+        msg.field(&self.value.to_le_bytes());
+    }
+}
+
+/// Event emitted when native Dusk is deposited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DepositEvent {
+    /// The account credited the tokens.
+    pub to: Account,
+    /// The value deposited.
+    pub value: u64,
+}
+
+/// Event emitted when native Dusk is withdrawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Archive, Serialize, Deserialize)]
+#[archive_attr(derive(CheckBytes))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WithdrawEvent {
+    /// The account the tokens are burned from.
+    pub from: Account,
+    /// The account the Dusk is sent to.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_hex"))]
+    pub to: PublicKey,
+    /// The value withdrawn.
+    pub value: u64,
+}
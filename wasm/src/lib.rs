@@ -0,0 +1,216 @@
+//! WebAssembly bindings to build and sign `ttoken-contract` payloads from JavaScript.
+//!
+//! Payloads are returned serialized with `rkyv`, ready to be passed as the argument of a contract
+//! call, so JavaScript never has to reproduce their byte layout. Accounts are passed in their
+//! string form, and keys and signatures in their compressed byte form.
+//!
+//! Each payload can either be signed using [`Keys`] held by the module, or by a signer elsewhere -
+//! such as a hardware wallet - over the message returned by the corresponding `*Message` function,
+//! and then assembled using the `*WithSignature` function.
+
+use dusk_bytes::Serializable;
+use rkyv::ser::serializers::AllocSerializer;
+use wasm_bindgen::prelude::*;
+
+use execution_core::signatures::bls::{PublicKey, SecretKey, Signature};
+use ttoken_types::{Account, Approve, Transfer, TransferFrom};
+
+/// The size of the scratch space used when serializing payloads.
+const SCRATCH_SIZE: usize = 1024;
+
+/// A secret key, together with its public key.
+#[wasm_bindgen]
+pub struct Keys {
+    sk: SecretKey,
+    pk: PublicKey,
+}
+
+#[wasm_bindgen]
+impl Keys {
+    /// Import a secret key from its 32 bytes.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Keys, JsError> {
+        let bytes = <[u8; SecretKey::SIZE]>::try_from(bytes)
+            .map_err(|_| JsError::new("Invalid secret key length"))?;
+        let sk = SecretKey::from_bytes(&bytes).map_err(|_| JsError::new("Invalid secret key"))?;
+        let pk = PublicKey::from(&sk);
+        Ok(Self { sk, pk })
+    }
+
+    /// The public key, in its compressed form.
+    #[wasm_bindgen(js_name = publicKey)]
+    pub fn public_key(&self) -> Vec<u8> {
+        self.pk.to_bytes().to_vec()
+    }
+
+    /// The account controlled by the key, in its string form.
+    pub fn account(&self) -> String {
+        Account::External(self.pk).to_string()
+    }
+}
+
+/// Sign a transfer, returning it serialized.
+#[wasm_bindgen(js_name = signTransfer)]
+pub fn sign_transfer(keys: &Keys, to: &str, value: u64, nonce: u64) -> Result<Vec<u8>, JsError> {
+    let transfer = Transfer::new(&keys.sk, parse_account(to)?, value, nonce);
+    serialize(&transfer)
+}
+
+/// The message to sign for a transfer.
+#[wasm_bindgen(js_name = transferMessage)]
+pub fn transfer_message(from: &[u8], to: &str, value: u64, nonce: u64) -> Result<Vec<u8>, JsError> {
+    let from = parse_public_key(from)?;
+    let transfer =
+        Transfer::from_parts(from, parse_account(to)?, value, nonce, Signature::default());
+    Ok(transfer.signature_message())
+}
+
+/// Assemble a transfer signed elsewhere, returning it serialized.
+#[wasm_bindgen(js_name = transferWithSignature)]
+pub fn transfer_with_signature(
+    from: &[u8],
+    to: &str,
+    value: u64,
+    nonce: u64,
+    signature: &[u8],
+) -> Result<Vec<u8>, JsError> {
+    let from = parse_public_key(from)?;
+    let signature = parse_signature(signature)?;
+    let transfer = Transfer::from_parts(from, parse_account(to)?, value, nonce, signature);
+    verified(transfer.verify())?;
+    serialize(&transfer)
+}
+
+/// Sign a transfer spending tokens from the `owner`, returning it serialized.
+#[wasm_bindgen(js_name = signTransferFrom)]
+pub fn sign_transfer_from(
+    keys: &Keys,
+    owner: &str,
+    to: &str,
+    value: u64,
+    nonce: u64,
+) -> Result<Vec<u8>, JsError> {
+    let owner = parse_account(owner)?;
+    let transfer = TransferFrom::new(&keys.sk, owner, parse_account(to)?, value, nonce);
+    serialize(&transfer)
+}
+
+/// The message to sign for a transfer spending tokens from the `owner`.
+#[wasm_bindgen(js_name = transferFromMessage)]
+pub fn transfer_from_message(
+    spender: &[u8],
+    owner: &str,
+    to: &str,
+    value: u64,
+    nonce: u64,
+) -> Result<Vec<u8>, JsError> {
+    let spender = parse_public_key(spender)?;
+    let owner = parse_account(owner)?;
+    let transfer = TransferFrom::from_parts(
+        spender,
+        owner,
+        parse_account(to)?,
+        value,
+        nonce,
+        Signature::default(),
+    );
+    Ok(transfer.signature_message())
+}
+
+/// Assemble a transfer spending tokens from the `owner` signed elsewhere, returning it serialized.
+#[wasm_bindgen(js_name = transferFromWithSignature)]
+pub fn transfer_from_with_signature(
+    spender: &[u8],
+    owner: &str,
+    to: &str,
+    value: u64,
+    nonce: u64,
+    signature: &[u8],
+) -> Result<Vec<u8>, JsError> {
+    let spender = parse_public_key(spender)?;
+    let owner = parse_account(owner)?;
+    let signature = parse_signature(signature)?;
+    let transfer =
+        TransferFrom::from_parts(spender, owner, parse_account(to)?, value, nonce, signature);
+    verified(transfer.verify())?;
+    serialize(&transfer)
+}
+
+/// Sign an approval, returning it serialized.
+#[wasm_bindgen(js_name = signApprove)]
+pub fn sign_approve(
+    keys: &Keys,
+    spender: &str,
+    value: u64,
+    nonce: u64,
+) -> Result<Vec<u8>, JsError> {
+    let approve = Approve::new(&keys.sk, parse_account(spender)?, value, nonce);
+    serialize(&approve)
+}
+
+/// The message to sign for an approval.
+#[wasm_bindgen(js_name = approveMessage)]
+pub fn approve_message(
+    owner: &[u8],
+    spender: &str,
+    value: u64,
+    nonce: u64,
+) -> Result<Vec<u8>, JsError> {
+    let owner = parse_public_key(owner)?;
+    let approve = Approve::from_parts(
+        owner,
+        parse_account(spender)?,
+        value,
+        nonce,
+        Signature::default(),
+    );
+    Ok(approve.signature_message())
+}
+
+/// Assemble an approval signed elsewhere, returning it serialized.
+#[wasm_bindgen(js_name = approveWithSignature)]
+pub fn approve_with_signature(
+    owner: &[u8],
+    spender: &str,
+    value: u64,
+    nonce: u64,
+    signature: &[u8],
+) -> Result<Vec<u8>, JsError> {
+    let owner = parse_public_key(owner)?;
+    let signature = parse_signature(signature)?;
+    let approve = Approve::from_parts(owner, parse_account(spender)?, value, nonce, signature);
+    verified(approve.verify())?;
+    serialize(&approve)
+}
+
+fn parse_account(account: &str) -> Result<Account, JsError> {
+    Ok(account.parse::<Account>()?)
+}
+
+fn parse_public_key(bytes: &[u8]) -> Result<PublicKey, JsError> {
+    let bytes = <[u8; PublicKey::SIZE]>::try_from(bytes)
+        .map_err(|_| JsError::new("Invalid public key length"))?;
+    PublicKey::from_bytes(&bytes).map_err(|_| JsError::new("Invalid public key"))
+}
+
+fn parse_signature(bytes: &[u8]) -> Result<Signature, JsError> {
+    let bytes = <[u8; Signature::SIZE]>::try_from(bytes)
+        .map_err(|_| JsError::new("Invalid signature length"))?;
+    Signature::from_bytes(&bytes).map_err(|_| JsError::new("Invalid signature"))
+}
+
+fn verified(valid: bool) -> Result<(), JsError> {
+    if !valid {
+        return Err(JsError::new("Invalid signature"));
+    }
+    Ok(())
+}
+
+fn serialize<T>(payload: &T) -> Result<Vec<u8>, JsError>
+where
+    T: rkyv::Serialize<AllocSerializer<SCRATCH_SIZE>>,
+{
+    rkyv::to_bytes::<_, SCRATCH_SIZE>(payload)
+        .map(|bytes| bytes.into_vec())
+        .map_err(|_| JsError::new("Failed serializing the payload"))
+}
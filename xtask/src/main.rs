@@ -0,0 +1,197 @@
+//! Build tooling for the token contract.
+//!
+//! The contract can be compiled with different sets of features, resulting in different variants
+//! a deployer can choose from. This tool builds each variant into `build/`, named after the variant,
+//! and runs the test suite against each of them.
+//!
+//! ```sh
+//! cargo xtask build [variant...]
+//! cargo xtask test [variant...]
+//! cargo xtask bench [--save] [variant...]
+//! ```
+//!
+//! If no variant is given, all of them are built and tested. Benchmarking compares the gas spent by
+//! each variant against its baseline, or records a new baseline with `--save`.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command};
+
+const COMPILER_VERSION: &str = "v0.2.0";
+
+/// A variant of the contract, named after the set of features it is compiled with.
+type Variant = (&'static str, &'static [&'static str]);
+
+/// The supported variants of the contract.
+const VARIANTS: &[Variant] = &[
+    ("minimal", &[]),
+    ("fees", &["fees"]),
+    ("compliance", &["compliance"]),
+    ("full", &["full"]),
+];
+
+const WASM_TARGET: &str = "wasm64-unknown-unknown";
+
+fn main() {
+    let mut args = env::args().skip(1);
+
+    let task = args.next();
+    let (flags, names): (Vec<String>, Vec<String>) = args.partition(|arg| arg.starts_with("--"));
+    let variants = match select_variants(names) {
+        Ok(variants) => variants,
+        Err(variant) => {
+            eprintln!("Unknown variant: {variant}");
+            usage();
+        }
+    };
+
+    let root = workspace_root();
+
+    match task.as_deref() {
+        Some("build") => build(&root, &variants),
+        Some("test") => {
+            build(&root, &variants);
+            test(&root, &variants);
+        }
+        Some("bench") => {
+            build(&root, &variants);
+            bench(&root, &variants, &flags);
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> ! {
+    let variants: Vec<&str> = VARIANTS.iter().map(|(name, _)| *name).collect();
+
+    eprintln!("Usage: cargo xtask <build|test|bench> [variant...]");
+    eprintln!();
+    eprintln!("Variants: {}", variants.join(", "));
+
+    exit(1)
+}
+
+fn select_variants(names: Vec<String>) -> Result<Vec<Variant>, String> {
+    if names.is_empty() {
+        return Ok(VARIANTS.to_vec());
+    }
+
+    names
+        .into_iter()
+        .map(|name| {
+            VARIANTS
+                .iter()
+                .find(|(variant, _)| *variant == name)
+                .copied()
+                .ok_or(name)
+        })
+        .collect()
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("The xtask crate should be in the workspace root")
+        .to_path_buf()
+}
+
+fn build(root: &Path, variants: &[Variant]) {
+    run(Command::new("./scripts/setup-compiler.sh")
+        .arg(COMPILER_VERSION)
+        .current_dir(root));
+
+    for (variant, features) in variants {
+        let target_dir = root.join("target").join("variants").join(variant);
+
+        build_wasm(root, "contract/Cargo.toml", features, &target_dir);
+        strip_wasm(
+            root,
+            &target_dir,
+            "ttoken_contract.wasm",
+            &format!("ttoken_contract_{variant}.wasm"),
+        );
+    }
+
+    let target_dir = root.join("target");
+
+    build_wasm(root, "tests/contract/Cargo.toml", &[], &target_dir);
+    strip_wasm(
+        root,
+        &target_dir,
+        "ttoken_test_contract.wasm",
+        "ttoken_test_contract.wasm",
+    );
+}
+
+fn test(root: &Path, variants: &[Variant]) {
+    for (variant, features) in variants {
+        println!("Testing the `{variant}` variant");
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(["test", "--release", "--manifest-path=tests/Cargo.toml"])
+            .current_dir(root);
+
+        if !features.is_empty() {
+            cmd.arg(format!("--features={}", features.join(",")));
+        }
+
+        run(&mut cmd);
+    }
+}
+
+fn bench(root: &Path, variants: &[Variant], flags: &[String]) {
+    for (variant, features) in variants {
+        println!("Benchmarking the `{variant}` variant");
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(["bench", "--manifest-path=tests/Cargo.toml", "--bench=gas"])
+            .current_dir(root);
+
+        if !features.is_empty() {
+            cmd.arg(format!("--features={}", features.join(",")));
+        }
+
+        cmd.arg("--").args(flags);
+
+        run(&mut cmd);
+    }
+}
+
+fn build_wasm(root: &Path, manifest_path: &str, features: &[&str], target_dir: &Path) {
+    let mut cmd = Command::new("cargo");
+    cmd.env("RUSTFLAGS", "-C link-args=-zstack-size=65536")
+        .args(["+dusk", "build", "--release", "--color=always"])
+        .arg(format!("--manifest-path={manifest_path}"))
+        .arg(format!("--target-dir={}", target_dir.display()))
+        .args(["-Z", "build-std=core,alloc"])
+        .args(["--target", WASM_TARGET])
+        .current_dir(root);
+
+    if !features.is_empty() {
+        cmd.arg(format!("--features={}", features.join(",")));
+    }
+
+    run(&mut cmd);
+}
+
+fn strip_wasm(root: &Path, target_dir: &Path, name: &str, artifact_name: &str) {
+    let build_dir = root.join("build");
+    std::fs::create_dir_all(&build_dir).expect("Creating the build directory should succeed");
+
+    let wasm = target_dir.join(WASM_TARGET).join("release").join(name);
+
+    run(Command::new("./scripts/strip.sh")
+        .arg(wasm)
+        .arg(build_dir.join(artifact_name))
+        .current_dir(root));
+}
+
+fn run(cmd: &mut Command) {
+    let status = cmd
+        .status()
+        .unwrap_or_else(|err| panic!("Failed running {cmd:?}: {err}"));
+
+    if !status.success() {
+        exit(status.code().unwrap_or(1));
+    }
+}